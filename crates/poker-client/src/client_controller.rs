@@ -12,11 +12,13 @@
 //!    to process server messages.
 //! 3. Call [`ClientController::send`] to transmit player actions.
 
-use crate::game_state::{ClientGameState, GameEvent, LogCategory, StateChanged};
+use crate::game_state::{ClientGameState, GameEvent, LogCategory, PendingAction, StateChanged};
 use crate::net_client::NetClient;
 #[cfg(feature = "native")]
 use crate::transport::Transport;
-use poker_core::protocol::{ClientMessage, ServerMessage};
+use poker_core::protocol::{
+    BUILD_ID, ClientKind, ClientMessage, Encoding, Envelope, FEATURES, PROTOCOL_VERSION,
+};
 
 /// Outcome of processing a single network event.
 #[derive(Debug)]
@@ -43,12 +45,14 @@ impl ClientController {
 
     /// Create a controller over any [`Transport`] implementation.
     ///
-    /// No handshake messages are sent automatically — the caller should send
-    /// `JoinRoom` (or `CreateRoom` + `JoinRoom`) after construction.
+    /// Only the `Hello` handshake is sent automatically, asking for
+    /// `encoding` — the caller should send `JoinRoom` (or `CreateRoom` +
+    /// `JoinRoom`) after construction.
     #[cfg(feature = "native")]
-    pub fn from_transport<T: Transport>(transport: T, name: &str) -> Self {
+    pub fn from_transport<T: Transport>(transport: T, name: &str, encoding: Encoding) -> Self {
         let net = NetClient::from_transport(transport);
         let state = ClientGameState::new(name);
+        let _ = net.send(hello(encoding));
         Self { net, state }
     }
 
@@ -58,12 +62,18 @@ impl ClientController {
 
     /// Connect to a WebSocket server (e.g. `ws://host/ws/room-id`).
     ///
-    /// No join handshake is sent — the caller should send `JoinRoom` after
-    /// construction.
+    /// Only the `Hello` handshake is sent, asking for `encoding` — the
+    /// caller should send `JoinRoom` after construction.  The server may
+    /// answer that it only speaks JSON.
     #[cfg(any(feature = "native", feature = "web"))]
-    pub async fn connect_ws(url: &str, name: &str) -> Result<Self, Box<dyn std::error::Error>> {
+    pub async fn connect_ws(
+        url: &str,
+        name: &str,
+        encoding: Encoding,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
         let net = NetClient::connect_ws(url).await?;
         let state = ClientGameState::new(name);
+        let _ = net.send(hello(encoding));
         Ok(Self { net, state })
     }
 
@@ -98,7 +108,19 @@ impl ClientController {
     }
 
     /// Send a [`ClientMessage`] to the server.
-    pub fn send(&self, msg: ClientMessage) {
+    ///
+    /// When the server acknowledges actions, betting actions are numbered
+    /// and kept in [`ClientGameState::pending_actions`] until it answers.
+    pub fn send(&mut self, mut msg: ClientMessage) {
+        let numbered = self.state.features.iter().any(|f| f == "action_ack");
+        if numbered && msg.action().is_some() && msg.request_id().is_none() {
+            self.state.next_request_id += 1;
+            msg.set_request_id(self.state.next_request_id);
+            self.state.pending_actions.push(PendingAction {
+                request_id: self.state.next_request_id,
+                message: msg.clone(),
+            });
+        }
         let _ = self.net.send(msg);
     }
 
@@ -122,8 +144,43 @@ impl ClientController {
 
     // -- private -----------------------------------------------------------
 
-    fn handle_server_message(&mut self, msg: ServerMessage) -> PollResult {
-        let changed = self.state.apply_server_message(&msg);
+    fn handle_server_message(&mut self, envelope: Envelope) -> PollResult {
+        if let Some(seq) = envelope.seq {
+            self.state.last_seq = seq;
+            if let Some(left) = &mut self.state.catching_up {
+                *left = left.saturating_sub(1);
+            }
+        }
+        let changed = self.state.apply_server_message(&envelope.msg);
+        if self.state.catching_up == Some(0) {
+            self.state.catching_up = None;
+            self.resend_pending();
+        }
         PollResult::Updated(changed)
     }
+
+    /// Send unanswered actions again after a resume.  They keep their
+    /// request IDs, so the server answers an action it already took
+    /// without taking it twice.
+    fn resend_pending(&self) {
+        for pending in &self.state.pending_actions {
+            let _ = self.net.send(pending.message.clone());
+        }
+    }
+}
+
+/// The handshake that opens every connection.
+fn hello(encoding: Encoding) -> ClientMessage {
+    let client_kind = if cfg!(all(feature = "web", not(feature = "native"))) {
+        ClientKind::Web
+    } else {
+        ClientKind::Native
+    };
+    ClientMessage::Hello {
+        protocol_version: PROTOCOL_VERSION,
+        client_kind,
+        capabilities: FEATURES.iter().map(|f| f.to_string()).collect(),
+        build: BUILD_ID.to_string(),
+        encoding,
+    }
 }
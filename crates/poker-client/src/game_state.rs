@@ -2,7 +2,8 @@ use std::collections::{HashMap, HashSet, VecDeque};
 
 use poker_core::poker::{Board, Hand, HandRank};
 use poker_core::protocol::{
-    BlindConfig, CardInfo, ClientMessage, PlayerAction, PlayerInfo, ServerMessage,
+    BlindConfig, CardInfo, ClientMessage, Encoding, GameMode, LedgerEntry, PlayerAction,
+    PlayerInfo, ProtocolError, RebuyConfig, RoomSummary, ServerMessage, SpectatorDelay, Standing,
 };
 
 /// A revealed hand during showdown, for direct UI display.
@@ -42,6 +43,13 @@ pub enum GameEvent {
     },
     /// Another player joined.
     PlayerJoined { player_id: u32, name: String },
+    /// A player moved to another seat (at the next hand if `pending`).
+    SeatChanged {
+        player_id: u32,
+        name: String,
+        seat: u32,
+        pending: bool,
+    },
     /// A player left.
     PlayerLeft { player_id: u32, name: String },
     /// Chat message from a player.
@@ -50,6 +58,8 @@ pub enum GameEvent {
         player_name: String,
         message: String,
     },
+    /// Chat message from a spectator.
+    SpectatorChat { name: String, message: String },
     /// The game started.
     GameStarted,
     /// A new hand is starting.
@@ -89,10 +99,19 @@ pub enum GameEvent {
         amount: u32,
         hand: String,
     },
-    /// A player was eliminated (out of chips).
-    PlayerEliminated { player_id: u32, name: String },
+    /// A player was eliminated (out of chips) in the given place.
+    PlayerEliminated {
+        player_id: u32,
+        name: String,
+        place: u32,
+    },
     /// The game is over.
-    GameOver { winner_id: u32, winner_name: String },
+    GameOver {
+        winner_id: u32,
+        winner_name: String,
+        /// Final prize pool (entries, rebuys and add-ons).
+        prize_pool: u32,
+    },
     /// Pong response.
     Pong,
     /// Error from the server.
@@ -115,6 +134,74 @@ pub enum GameEvent {
     PlayerSatIn { player_id: u32, name: String },
     /// The game is paused waiting for enough active players.
     WaitingForPlayers,
+    /// The host paused the game.
+    GamePaused,
+    /// The host resumed the game.
+    GameResumed,
+    /// A player bought chips in a cash game.
+    PlayerBoughtIn {
+        player_id: u32,
+        name: String,
+        amount: u32,
+        pending: bool,
+    },
+    /// A player left a cash game with their stack.
+    PlayerCashedOut {
+        player_id: u32,
+        name: String,
+        amount: u32,
+    },
+    /// A player rebought during the tournament rebuy period.
+    PlayerRebought {
+        player_id: u32,
+        name: String,
+        amount: u32,
+        pending: bool,
+    },
+    /// A player took the tournament add-on.
+    PlayerAddedOn {
+        player_id: u32,
+        name: String,
+        amount: u32,
+        pending: bool,
+    },
+    /// The rebuy period is over (and the add-on break may have started).
+    RebuyPeriodEnded { add_on_open: bool },
+    /// The add-on break is over.
+    AddOnClosed,
+    /// Final tournament standings.
+    FinalStandings { standings: Vec<Standing> },
+    /// We were moved to another table of a multi-table tournament.
+    TableMoved { room_id: String },
+    /// The host removed a player from the room (and banned them if `banned`).
+    PlayerKicked {
+        player_id: u32,
+        name: String,
+        banned: bool,
+    },
+    /// Hosting passed to another player.
+    HostChanged { player_id: u32, name: String },
+    /// The host ended the game early.
+    GameEnded,
+    /// The host changed the blinds (from the next hand if `pending`).
+    BlindsChanged {
+        small_blind: u32,
+        big_blind: u32,
+        pending: bool,
+    },
+    /// We created an invite to the room.
+    InviteCreated {
+        token: String,
+        single_use: bool,
+        expires_in_secs: u32,
+    },
+    /// The server is going down; we rejoin after `reconnect_after` seconds.
+    ServerShutdown {
+        reason: String,
+        reconnect_after: u32,
+    },
+    /// A notice from the server operators.
+    Announcement { message: String },
 }
 
 impl GameEvent {
@@ -127,15 +214,18 @@ impl GameEvent {
             | Self::NewHand { .. }
             | Self::YourTurn => LogCategory::System,
 
-            Self::Chat { .. } => LogCategory::Chat,
+            Self::Chat { .. } | Self::SpectatorChat { .. } => LogCategory::Chat,
             Self::PlayerActed { .. } => LogCategory::Action,
 
             Self::Showdown { .. }
             | Self::AllInShowdown { .. }
             | Self::RoundWinner { .. }
-            | Self::GameOver { .. } => LogCategory::Winner,
+            | Self::GameOver { .. }
+            | Self::FinalStandings { .. } => LogCategory::Winner,
 
-            Self::ServerError { .. } | Self::Disconnected => LogCategory::Error,
+            Self::ServerError { .. } | Self::Disconnected | Self::ServerShutdown { .. } => {
+                LogCategory::Error
+            }
 
             Self::PlayerJoined { .. }
             | Self::PlayerLeft { .. }
@@ -145,11 +235,20 @@ impl GameEvent {
             | Self::Pong => LogCategory::Info,
 
             Self::Text { category, .. } => *category,
-            Self::BlindsIncreased { .. } => LogCategory::System,
+            Self::BlindsIncreased { .. } | Self::Announcement { .. } => LogCategory::System,
             Self::TurnTimerStarted { .. } => LogCategory::System,
             Self::PlayerSatOut { .. } => LogCategory::Info,
             Self::PlayerSatIn { .. } => LogCategory::Info,
             Self::WaitingForPlayers => LogCategory::System,
+            Self::GamePaused | Self::GameResumed => LogCategory::System,
+            Self::PlayerBoughtIn { .. } | Self::PlayerCashedOut { .. } => LogCategory::Info,
+            Self::PlayerRebought { .. } | Self::PlayerAddedOn { .. } => LogCategory::Info,
+            Self::RebuyPeriodEnded { .. } | Self::AddOnClosed => LogCategory::System,
+            Self::TableMoved { .. } => LogCategory::System,
+            Self::SeatChanged { .. } => LogCategory::Info,
+            Self::PlayerKicked { .. } | Self::HostChanged { .. } => LogCategory::Info,
+            Self::GameEnded | Self::BlindsChanged { .. } => LogCategory::System,
+            Self::InviteCreated { .. } => LogCategory::System,
         }
     }
 }
@@ -172,12 +271,20 @@ pub struct StateChanged {
     pub phase: bool,
     /// The turn timer changed (started for a new player).
     pub timer: bool,
+    /// The server refused a request; see [`ClientGameState::last_error`].
+    pub error: bool,
 }
 
 impl StateChanged {
     /// Returns `true` if any flag is set.
     pub fn any(self) -> bool {
-        self.actions || self.players || self.cards || self.pot || self.phase || self.timer
+        self.actions
+            || self.players
+            || self.cards
+            || self.pot
+            || self.phase
+            || self.timer
+            || self.error
     }
 }
 
@@ -253,6 +360,76 @@ pub struct ClientGameState {
     pub is_host: bool,
     /// Whether late entry is currently allowed.
     pub allow_late_entry: bool,
+    /// Whether the host has paused the game.
+    pub paused: bool,
+    /// Tournament or cash game.
+    pub game_mode: GameMode,
+    /// Session ledger (latest snapshot from the server).
+    pub ledger: Vec<LedgerEntry>,
+    /// Tournament rebuy / add-on rules for this room.
+    pub rebuy_config: RebuyConfig,
+    /// Whether tournament rebuys are currently allowed.
+    pub rebuy_open: bool,
+    /// Whether the add-on break is in progress.
+    pub add_on_open: bool,
+    /// Total chips paid into the tournament.
+    pub prize_pool: u32,
+    /// Final tournament standings (empty until the game is over).
+    pub standings: Vec<Standing>,
+    /// Multi-table tournament progress (`None` for single-table games).
+    pub tournament: Option<TournamentProgress>,
+    /// Number of seats at the table.
+    pub table_size: u32,
+    /// Whether we are watching the room without a seat.
+    pub spectating: bool,
+    /// Number of spectators watching the room.
+    pub spectators: u32,
+    /// Whether spectators may chat.
+    pub spectator_chat_allowed: bool,
+    /// How far behind the table we see events while spectating.
+    pub spectator_delay: SpectatorDelay,
+    /// Streamer view: every dealt player's hole cards this hand.
+    pub spectator_hole_cards: HashMap<u32, [CardInfo; 2]>,
+    /// Token of the last invite we created as host.
+    pub invite: Option<String>,
+    /// Rooms listed by the server while browsing the lobby.
+    pub rooms: Vec<RoomSummary>,
+    /// Seconds to wait before rejoining, once the server has announced a
+    /// shutdown.
+    pub reconnect_after: Option<u32>,
+    /// Optional features agreed with the server in the handshake.
+    pub features: Vec<String>,
+    /// Encoding agreed with the server in the handshake.
+    pub encoding: Encoding,
+    /// The server cannot talk to this client until it is reloaded.
+    pub reload_required: bool,
+    /// Number of the last room event received, sent back on `Rejoin` to
+    /// have the events missed while disconnected replayed.
+    pub last_seq: u64,
+    /// The last request the server refused, and why.
+    pub last_error: Option<ProtocolError>,
+    /// Betting actions sent but not yet answered by the server.
+    pub pending_actions: Vec<PendingAction>,
+    /// Request ID given to the last numbered action.
+    pub next_request_id: u64,
+    /// Replayed events still to come after `Resumed`; pending actions are
+    /// sent again once the client has caught up.
+    pub catching_up: Option<u32>,
+}
+
+/// A betting action waiting for the server's answer.
+#[derive(Debug, Clone)]
+pub struct PendingAction {
+    pub request_id: u64,
+    pub message: ClientMessage,
+}
+
+/// Progress of a multi-table tournament.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TournamentProgress {
+    pub entrants: u32,
+    pub players_remaining: u32,
+    pub tables: u32,
 }
 
 impl ClientGameState {
@@ -296,6 +473,32 @@ impl ClientGameState {
             showdown_hands: Vec::new(),
             is_host: false,
             allow_late_entry: false,
+            paused: false,
+            game_mode: GameMode::default(),
+            ledger: Vec::new(),
+            rebuy_config: RebuyConfig::default(),
+            rebuy_open: false,
+            add_on_open: false,
+            prize_pool: 0,
+            standings: Vec::new(),
+            tournament: None,
+            table_size: 9,
+            spectating: false,
+            spectators: 0,
+            spectator_chat_allowed: true,
+            spectator_delay: SpectatorDelay::default(),
+            spectator_hole_cards: HashMap::new(),
+            invite: None,
+            rooms: Vec::new(),
+            reconnect_after: None,
+            features: Vec::new(),
+            encoding: Encoding::Json,
+            reload_required: false,
+            last_seq: 0,
+            last_error: None,
+            pending_actions: Vec::new(),
+            next_request_id: 0,
+            catching_up: None,
         }
     }
 
@@ -332,6 +535,88 @@ impl ClientGameState {
         self.folded_players.contains(&player_id)
     }
 
+    /// Returns true if this room is a cash game.
+    pub fn is_cash_game(&self) -> bool {
+        self.game_mode.is_cash()
+    }
+
+    /// The cash game buy-in range in chips at the current big blind.
+    pub fn buy_in_range(&self) -> Option<(u32, u32)> {
+        self.game_mode.buy_in_range(self.big_blind)
+    }
+
+    /// Build a `BuyIn` that tops our stack up to the maximum buy-in (or
+    /// rebuys for the maximum after busting).
+    pub fn top_up(&self) -> Option<ClientMessage> {
+        let (_, max) = self.buy_in_range()?;
+        let amount = max.saturating_sub(self.our_chips);
+        (amount > 0).then_some(ClientMessage::BuyIn { amount })
+    }
+
+    /// Our line in the session ledger, if the server has sent one.
+    pub fn our_ledger_entry(&self) -> Option<&LedgerEntry> {
+        self.ledger
+            .iter()
+            .find(|e| e.player_id == self.our_player_id)
+    }
+
+    /// Whether we may rebuy now (the server also checks our stack).
+    pub fn can_rebuy(&self) -> bool {
+        let max = self.rebuy_config.max_rebuys;
+        let taken = self.our_ledger_entry().map(|e| e.rebuys).unwrap_or(0);
+        self.rebuy_open && (max == 0 || taken < max)
+    }
+
+    /// Whether we may take the add-on now.
+    pub fn can_add_on(&self) -> bool {
+        let taken = self.our_ledger_entry().is_some_and(|e| e.add_on);
+        self.add_on_open && !taken && self.our_chips > 0
+    }
+
+    /// Our final standing, once the tournament is over.
+    pub fn our_standing(&self) -> Option<&Standing> {
+        self.standings
+            .iter()
+            .find(|s| s.player_id == self.our_player_id)
+    }
+
+    /// Number of seats to draw: the table size, or more while a multi-table
+    /// tournament is still registering entrants.
+    pub fn seat_count(&self) -> u32 {
+        let highest = self.players.iter().map(|p| p.seat + 1).max().unwrap_or(0);
+        self.table_size.max(highest)
+    }
+
+    /// The player in a seat, if any.
+    pub fn player_at_seat(&self, seat: u32) -> Option<&PlayerInfo> {
+        self.players.iter().find(|p| p.seat == seat)
+    }
+
+    /// Our seat index, if we are seated.
+    pub fn our_seat(&self) -> Option<u32> {
+        self.players
+            .iter()
+            .find(|p| p.id == self.our_player_id)
+            .map(|p| p.seat)
+    }
+
+    /// Seats in clockwise order starting from ours (or from seat 0), so a
+    /// UI can draw us at the bottom of the table.
+    pub fn seats_from_ours(&self) -> Vec<u32> {
+        let count = self.seat_count();
+        let start = self.our_seat().unwrap_or(0);
+        (0..count).map(|i| (start + i) % count).collect()
+    }
+
+    /// The next empty seat clockwise from ours, for a seat change.
+    pub fn next_empty_seat(&self) -> Option<u32> {
+        self.our_seat()?;
+        self.seats_from_ours()
+            .into_iter()
+            .skip(1)
+            .find(|&seat| seat < self.table_size && self.player_at_seat(seat).is_none())
+    }
+
     /// Look up a player's display name by ID, falling back to `"Player #N"`.
     pub fn player_name(&self, player_id: u32) -> String {
         self.players
@@ -371,6 +656,39 @@ impl ClientGameState {
         let mut changed = StateChanged::default();
 
         match msg {
+            ServerMessage::ServerHello {
+                features, encoding, ..
+            } => {
+                self.features = features.clone();
+                self.encoding = *encoding;
+            }
+            ServerMessage::UpgradeRequired { message, reload } => {
+                self.reload_required = *reload;
+                self.add_event(GameEvent::ServerError {
+                    message: message.clone(),
+                });
+            }
+            ServerMessage::Resumed {
+                room_id,
+                player_id,
+                replayed,
+            } => {
+                self.room_id = room_id.clone();
+                self.our_player_id = *player_id;
+                self.connected = true;
+                self.reconnect_after = None;
+                self.catching_up = Some(*replayed);
+                let missed = match replayed {
+                    0 => "nothing was missed".to_string(),
+                    1 => "catching up on 1 event".to_string(),
+                    n => format!("catching up on {n} events"),
+                };
+                self.add_message(
+                    format!("Reconnected to game, {missed}."),
+                    LogCategory::System,
+                );
+                changed.players = true;
+            }
             ServerMessage::Welcome { message } => {
                 self.add_event(GameEvent::Welcome {
                     message: message.clone(),
@@ -387,6 +705,7 @@ impl ClientGameState {
                 self.our_player_id = *player_id;
                 self.our_chips = *chips;
                 self.is_host = *is_host;
+                self.spectating = false;
                 self.allow_late_entry = *allow_late_entry;
                 if !session_token.is_empty() {
                     self.session_token = session_token.clone();
@@ -396,6 +715,7 @@ impl ClientGameState {
                         id: *player_id,
                         name: self.our_name.clone(),
                         chips: *chips,
+                        seat: 0,
                     });
                 }
                 self.add_event(GameEvent::Joined {
@@ -405,7 +725,11 @@ impl ClientGameState {
                 });
                 changed.players = true;
             }
-            ServerMessage::PlayerJoined { player_id, name } => {
+            ServerMessage::PlayerJoined {
+                player_id,
+                name,
+                seat,
+            } => {
                 if !self.players.iter().any(|p| p.id == *player_id) {
                     // Use our own starting chips as a best guess; the
                     // server will send ChipUpdate / PlayerList with exact
@@ -414,6 +738,7 @@ impl ClientGameState {
                         id: *player_id,
                         name: name.clone(),
                         chips: self.our_chips,
+                        seat: *seat,
                     });
                 }
                 self.add_event(GameEvent::PlayerJoined {
@@ -435,6 +760,70 @@ impl ClientGameState {
                 self.players = players.clone();
                 changed.players = true;
             }
+            ServerMessage::SeatChanged {
+                player_id,
+                seat,
+                pending,
+            } => {
+                if !pending && let Some(p) = self.players.iter_mut().find(|p| p.id == *player_id) {
+                    p.seat = *seat;
+                }
+                self.add_event(GameEvent::SeatChanged {
+                    player_id: *player_id,
+                    name: self.player_name(*player_id),
+                    seat: *seat,
+                    pending: *pending,
+                });
+                changed.players = true;
+            }
+            ServerMessage::SpectatorChat { name, message } => {
+                self.add_event(GameEvent::SpectatorChat {
+                    name: name.clone(),
+                    message: message.clone(),
+                });
+            }
+            ServerMessage::Spectating { room_id, delay } => {
+                self.room_id = room_id.clone();
+                self.spectating = true;
+                self.spectator_delay = *delay;
+                let mut lag = Vec::new();
+                if delay.secs > 0 {
+                    lag.push(format!("{}s", delay.secs));
+                }
+                if delay.hands > 0 {
+                    lag.push(format!(
+                        "{} hand{}",
+                        delay.hands,
+                        if delay.hands == 1 { "" } else { "s" }
+                    ));
+                }
+                let message = if lag.is_empty() {
+                    format!("Watching room {room_id} as a spectator.")
+                } else {
+                    format!(
+                        "Watching room {room_id} as a spectator, {} behind the table{}.",
+                        lag.join(" and "),
+                        if delay.hole_cards {
+                            " with hole cards shown"
+                        } else {
+                            ""
+                        }
+                    )
+                };
+                self.add_message(message, LogCategory::System);
+                changed.phase = true;
+            }
+            ServerMessage::SpectatorHoleCards { hands } => {
+                self.spectator_hole_cards = hands.iter().copied().collect();
+                changed.cards = true;
+            }
+            ServerMessage::Spectators {
+                count,
+                chat_allowed,
+            } => {
+                self.spectators = *count;
+                self.spectator_chat_allowed = *chat_allowed;
+            }
             ServerMessage::ChatMessage { player_id, message } => {
                 let name = self
                     .players
@@ -450,6 +839,8 @@ impl ClientGameState {
             }
             ServerMessage::GameStarted => {
                 self.game_started = true;
+                self.rebuy_open = self.rebuy_config.is_enabled();
+                self.standings.clear();
                 self.add_event(GameEvent::GameStarted);
                 changed.actions = true;
                 changed.phase = true;
@@ -468,9 +859,11 @@ impl ClientGameState {
                 self.big_blind_id = *big_blind_id;
                 self.big_blind = *big_blind;
                 self.hole_cards = None;
+                self.spectator_hole_cards.clear();
                 self.community_cards.clear();
                 self.showdown_hands.clear();
                 self.folded_players.clear();
+                self.pending_actions.clear();
                 self.pot = small_blind + big_blind;
                 self.stage = "Preflop".to_string();
                 self.is_our_turn = false;
@@ -531,6 +924,8 @@ impl ClientGameState {
             } => {
                 if *player_id == self.our_player_id {
                     self.is_our_turn = false;
+                    self.pending_actions.clear();
+                    changed.actions = true;
                 }
                 // Track folded players.
                 if *action == PlayerAction::Fold {
@@ -619,10 +1014,11 @@ impl ClientGameState {
                     });
                 }
             }
-            ServerMessage::PlayerEliminated { player_id } => {
+            ServerMessage::PlayerEliminated { player_id, place } => {
                 self.add_event(GameEvent::PlayerEliminated {
                     player_id: *player_id,
                     name: self.player_name(*player_id),
+                    place: *place,
                 });
                 changed.players = true;
             }
@@ -633,32 +1029,91 @@ impl ClientGameState {
                 self.add_event(GameEvent::GameOver {
                     winner_id: *winner_id,
                     winner_name: winner_name.clone(),
+                    prize_pool: self.prize_pool,
                 });
                 self.game_started = false;
+                self.paused = false;
+                self.rebuy_open = false;
+                self.add_on_open = false;
                 self.turn_timer_player = None;
                 self.turn_timer_secs = 0;
                 changed.actions = true;
                 changed.phase = true;
                 changed.timer = true;
             }
+            ServerMessage::FinalStandings {
+                standings,
+                prize_pool,
+            } => {
+                self.standings = standings.clone();
+                self.prize_pool = *prize_pool;
+                self.add_event(GameEvent::FinalStandings {
+                    standings: standings.clone(),
+                });
+                changed.phase = true;
+            }
             ServerMessage::Ok => {}
             ServerMessage::Pong => {
                 self.add_event(GameEvent::Pong);
             }
-            ServerMessage::Error { message } => {
+            ServerMessage::ActionAck { request_id } => {
+                self.pending_actions.retain(|p| p.request_id != *request_id);
+                changed.actions = true;
+            }
+            ServerMessage::ActionRejected {
+                request_id,
+                message,
+                error,
+            } => {
+                self.pending_actions.retain(|p| p.request_id != *request_id);
+                self.last_error = Some(error.clone());
                 self.add_event(GameEvent::ServerError {
                     message: message.clone(),
                 });
+                changed.actions = true;
+                changed.error = true;
+            }
+            ServerMessage::Error { message, error }
+            | ServerMessage::RoomError { message, error } => {
+                self.last_error = Some(error.clone());
+                self.add_event(GameEvent::ServerError {
+                    message: message.clone(),
+                });
+                changed.error = true;
             }
             ServerMessage::RoomCreated { .. } => {
                 // Handled at the connection-screen level, not game state.
             }
+            ServerMessage::RoomList { rooms } => {
+                self.rooms = rooms.clone();
+                changed.players = true;
+            }
+            ServerMessage::InviteCreated {
+                token,
+                single_use,
+                expires_in_secs,
+                ..
+            } => {
+                self.invite = Some(token.clone());
+                self.add_event(GameEvent::InviteCreated {
+                    token: token.clone(),
+                    single_use: *single_use,
+                    expires_in_secs: *expires_in_secs,
+                });
+                changed.players = true;
+            }
             ServerMessage::RoomJoined {
                 room_id,
                 blind_config,
+                game_mode,
+                rebuy_config,
+                table_size,
             } => {
                 self.room_id = room_id.clone();
                 self.blind_config = *blind_config;
+                self.game_mode = *game_mode;
+                self.rebuy_config = *rebuy_config;
+                self.table_size = *table_size;
             }
             ServerMessage::Rejoined {
                 room_id,
@@ -677,14 +1132,30 @@ impl ClientGameState {
                 blind_config,
                 allow_late_entry,
                 is_host,
+                paused,
+                game_mode,
+                rebuy_config,
+                rebuy_open,
+                add_on_open,
+                prize_pool,
+                table_size,
                 dealer_id,
                 small_blind_id,
                 big_blind_id,
                 small_blind: _,
                 big_blind,
+                bets,
+                current_bet,
+                min_raise,
+                to_act,
+                turn_secs_left,
             } => {
+                // A `TableMoved` notice precedes the snapshot of a new table.
+                let moved = !self.room_id.is_empty() && self.room_id != *room_id;
                 self.room_id = room_id.clone();
                 self.our_player_id = *player_id;
+                // The snapshot shows whether our last action was taken.
+                self.pending_actions.clear();
                 self.session_token = session_token.clone();
                 self.our_chips = *chips;
                 self.game_started = *game_started;
@@ -693,28 +1164,66 @@ impl ClientGameState {
                 self.stage = stage.clone();
                 self.community_cards = community_cards.clone();
                 self.hole_cards = *hole_cards;
+                self.spectator_hole_cards.clear();
                 self.players = players.clone();
                 self.sitting_out_players = sitting_out.iter().copied().collect();
                 self.folded_players = folded.iter().copied().collect();
                 self.blind_config = *blind_config;
                 self.allow_late_entry = *allow_late_entry;
                 self.is_host = *is_host;
+                self.paused = *paused;
+                self.game_mode = *game_mode;
+                self.rebuy_config = *rebuy_config;
+                self.rebuy_open = *rebuy_open;
+                self.add_on_open = *add_on_open;
+                self.prize_pool = *prize_pool;
+                self.table_size = *table_size;
                 self.big_blind = *big_blind;
                 self.dealer_id = *dealer_id;
                 self.small_blind_id = *small_blind_id;
                 self.big_blind_id = *big_blind_id;
                 self.connected = true;
+                self.reconnect_after = None;
+                self.player_bets = bets.iter().map(|b| (b.player_id, b.committed)).collect();
+                self.current_bet = *current_bet;
+                self.our_bet = bets
+                    .iter()
+                    .find(|b| b.player_id == *player_id)
+                    .map_or(0, |b| b.bet);
+                self.min_raise = *min_raise;
+                // A `YourTurn` follows when it is our turn.
                 self.is_our_turn = false;
                 self.valid_actions.clear();
+                if *to_act != 0 && *turn_secs_left > 0 {
+                    self.turn_timer_player = Some(*to_act);
+                    self.turn_timer_secs = *turn_secs_left;
+                    self.turn_counter += 1;
+                } else {
+                    self.turn_timer_player = None;
+                }
                 self.showdown_hands.clear();
-                self.add_message("Reconnected to game.".to_string(), LogCategory::System);
+                if !moved && !self.spectating {
+                    self.add_message("Reconnected to game.".to_string(), LogCategory::System);
+                }
                 changed.players = true;
                 changed.cards = true;
                 changed.pot = true;
                 changed.phase = true;
+                changed.actions = true;
+                changed.timer = true;
             }
-            ServerMessage::RoomError { message } => {
-                self.add_event(GameEvent::ServerError {
+            ServerMessage::ServerShutdown {
+                reason,
+                reconnect_after,
+            } => {
+                self.reconnect_after = Some(*reconnect_after);
+                self.add_event(GameEvent::ServerShutdown {
+                    reason: reason.clone(),
+                    reconnect_after: *reconnect_after,
+                });
+            }
+            ServerMessage::Announcement { message } => {
+                self.add_event(GameEvent::Announcement {
                     message: message.clone(),
                 });
             }
@@ -733,6 +1242,9 @@ impl ClientGameState {
                 player_id,
                 timeout_secs,
             } => {
+                if *player_id != self.our_player_id {
+                    self.pending_actions.clear();
+                }
                 self.turn_timer_player = Some(*player_id);
                 self.turn_timer_secs = *timeout_secs;
                 self.turn_counter += 1;
@@ -763,24 +1275,182 @@ impl ClientGameState {
                 self.allow_late_entry = *allowed;
                 changed.phase = true;
             }
+            ServerMessage::PlayerKicked { player_id, banned } => {
+                if *player_id == self.our_player_id {
+                    // The server refuses to restore a kicked session.
+                    self.session_token.clear();
+                }
+                self.add_event(GameEvent::PlayerKicked {
+                    player_id: *player_id,
+                    name: self.player_name(*player_id),
+                    banned: *banned,
+                });
+                changed.players = true;
+            }
+            ServerMessage::HostChanged { player_id } => {
+                self.is_host = *player_id == self.our_player_id;
+                self.add_event(GameEvent::HostChanged {
+                    player_id: *player_id,
+                    name: self.player_name(*player_id),
+                });
+                changed.players = true;
+                changed.phase = true;
+            }
+            ServerMessage::GameEnded => {
+                self.game_started = false;
+                self.is_our_turn = false;
+                self.paused = false;
+                self.rebuy_open = false;
+                self.add_on_open = false;
+                self.turn_timer_player = None;
+                self.turn_timer_secs = 0;
+                self.add_event(GameEvent::GameEnded);
+                changed.actions = true;
+                changed.phase = true;
+                changed.timer = true;
+            }
+            ServerMessage::BlindsChanged {
+                small_blind,
+                big_blind,
+                blind_config,
+                pending,
+            } => {
+                if !pending {
+                    self.big_blind = *big_blind;
+                    self.blind_config = *blind_config;
+                }
+                self.add_event(GameEvent::BlindsChanged {
+                    small_blind: *small_blind,
+                    big_blind: *big_blind,
+                    pending: *pending,
+                });
+                changed.phase = true;
+            }
             ServerMessage::WaitingForPlayers => {
                 self.add_event(GameEvent::WaitingForPlayers);
                 changed.phase = true;
             }
+            ServerMessage::GamePaused => {
+                self.paused = true;
+                // The server re-sends TurnTimerStarted with the remaining
+                // time on resume.
+                self.turn_timer_player = None;
+                self.add_event(GameEvent::GamePaused);
+                changed.phase = true;
+                changed.timer = true;
+            }
+            ServerMessage::GameResumed => {
+                self.paused = false;
+                self.add_event(GameEvent::GameResumed);
+                changed.phase = true;
+            }
+            ServerMessage::PlayerBoughtIn {
+                player_id,
+                amount,
+                pending,
+            } => {
+                self.add_event(GameEvent::PlayerBoughtIn {
+                    player_id: *player_id,
+                    name: self.player_name(*player_id),
+                    amount: *amount,
+                    pending: *pending,
+                });
+            }
+            ServerMessage::PlayerCashedOut { player_id, amount } => {
+                self.add_event(GameEvent::PlayerCashedOut {
+                    player_id: *player_id,
+                    name: self.player_name(*player_id),
+                    amount: *amount,
+                });
+                if *player_id == self.our_player_id {
+                    self.our_chips = 0;
+                    self.is_our_turn = false;
+                    changed.actions = true;
+                }
+                changed.players = true;
+            }
+            ServerMessage::SessionLedger { entries } => {
+                self.ledger = entries.clone();
+                changed.players = true;
+            }
+            ServerMessage::PlayerRebought {
+                player_id,
+                amount,
+                pending,
+            } => {
+                self.add_event(GameEvent::PlayerRebought {
+                    player_id: *player_id,
+                    name: self.player_name(*player_id),
+                    amount: *amount,
+                    pending: *pending,
+                });
+            }
+            ServerMessage::PlayerAddedOn {
+                player_id,
+                amount,
+                pending,
+            } => {
+                self.add_event(GameEvent::PlayerAddedOn {
+                    player_id: *player_id,
+                    name: self.player_name(*player_id),
+                    amount: *amount,
+                    pending: *pending,
+                });
+            }
+            ServerMessage::RebuyPeriodEnded { add_on_open } => {
+                self.rebuy_open = false;
+                self.add_on_open = *add_on_open;
+                self.add_event(GameEvent::RebuyPeriodEnded {
+                    add_on_open: *add_on_open,
+                });
+                changed.phase = true;
+            }
+            ServerMessage::AddOnClosed => {
+                self.add_on_open = false;
+                self.add_event(GameEvent::AddOnClosed);
+                changed.phase = true;
+            }
+            ServerMessage::PrizePool { total } => {
+                self.prize_pool = *total;
+                changed.players = true;
+            }
+            ServerMessage::TableMoved { room_id } => {
+                self.add_event(GameEvent::TableMoved {
+                    room_id: room_id.clone(),
+                });
+                changed.players = true;
+            }
+            ServerMessage::TournamentUpdate {
+                entrants,
+                players_remaining,
+                tables,
+            } => {
+                self.tournament = Some(TournamentProgress {
+                    entrants: *entrants,
+                    players_remaining: *players_remaining,
+                    tables: *tables,
+                });
+                changed.players = true;
+            }
         }
 
         changed
     }
 
+    /// Whether a betting action is still waiting for the server's answer.
+    pub fn action_pending(&self) -> bool {
+        !self.pending_actions.is_empty()
+    }
+
     /// Decide which `ClientMessage` to send for a fold/check action.
     pub fn fold_or_check(&self) -> Option<ClientMessage> {
-        if !self.is_our_turn {
+        if !self.is_our_turn || self.action_pending() {
             return None;
         }
         if self.has_action(PlayerAction::Check) {
-            Some(ClientMessage::Check)
+            Some(ClientMessage::Check { request_id: None })
         } else if self.has_action(PlayerAction::Fold) {
-            Some(ClientMessage::Fold)
+            Some(ClientMessage::Fold { request_id: None })
         } else {
             None
         }
@@ -788,10 +1458,10 @@ impl ClientGameState {
 
     /// Decide which `ClientMessage` to send for a call action.
     pub fn call(&self) -> Option<ClientMessage> {
-        if !self.is_our_turn || !self.has_action(PlayerAction::Call) {
+        if !self.is_our_turn || self.action_pending() || !self.has_action(PlayerAction::Call) {
             return None;
         }
-        Some(ClientMessage::Call)
+        Some(ClientMessage::Call { request_id: None })
     }
 
     /// Validate and build a raise `ClientMessage`.
@@ -801,11 +1471,14 @@ impl ClientGameState {
         if !self.is_our_turn {
             return Err("Not your turn".to_string());
         }
+        if self.action_pending() {
+            return Err("Still sending your last action".to_string());
+        }
         let can_raise = self.has_action(PlayerAction::Raise);
         let can_allin = self.has_action(PlayerAction::AllIn);
 
         if is_all_in && can_allin {
-            return Ok(ClientMessage::AllIn);
+            return Ok(ClientMessage::AllIn { request_id: None });
         }
         if amount == 0 {
             return Err("Raise amount must be greater than 0".to_string());
@@ -816,7 +1489,10 @@ impl ClientGameState {
             }
             return Err("Raise not available".to_string());
         }
-        Ok(ClientMessage::Raise { amount })
+        Ok(ClientMessage::Raise {
+            amount,
+            request_id: None,
+        })
     }
 
     /// Compute a pot-percentage raise amount, clamped to the player's stack.
@@ -878,3 +1554,33 @@ impl RaisePreset {
         }
     }
 }
+
+/// English ordinal for a finishing place ("1st", "2nd", "11th", ...).
+pub fn ordinal(place: u32) -> String {
+    let suffix = match (place % 10, place % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    format!("{place}{suffix}")
+}
+
+/// How an invite may be used ("single use, expires in 1h").
+pub fn invite_terms(single_use: bool, expires_in_secs: u32) -> String {
+    let mut terms = Vec::new();
+    if single_use {
+        terms.push("single use".to_string());
+    }
+    if expires_in_secs > 0 {
+        let expiry = match expires_in_secs {
+            s if s % 86_400 == 0 => format!("{}d", s / 86_400),
+            s if s % 3_600 == 0 => format!("{}h", s / 3_600),
+            s if s % 60 == 0 => format!("{}m", s / 60),
+            s => format!("{s}s"),
+        };
+        terms.push(format!("expires in {expiry}"));
+    }
+    terms.join(", ")
+}
@@ -8,25 +8,46 @@
 //! [`Transport`](crate::transport::Transport) implementation, or the
 //! convenience method [`connect_ws`](NetClient::connect_ws) (WebSocket).
 
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, watch};
 
 #[cfg(feature = "native")]
 use crate::transport::{Transport, TransportReader, TransportWriter};
-use poker_core::protocol::{ClientMessage, ServerMessage};
+use poker_core::protocol::{ClientMessage, Encoding, Envelope, Frame, ServerMessage};
 
 // ---------------------------------------------------------------------------
 // Wire-level parsing
 // ---------------------------------------------------------------------------
 
-/// Try to deserialize a raw text frame as a [`ServerMessage`].
+/// Try to deserialize a raw text frame as a server message with its
+/// sequence number, if any.
 ///
 /// Returns `None` for empty/whitespace-only input or unrecognised JSON.
-pub fn parse_server_line(line: &str) -> Option<ServerMessage> {
+pub fn parse_server_line(line: &str) -> Option<Envelope> {
     let trimmed = line.trim();
     if trimmed.is_empty() {
         return None;
     }
-    serde_json::from_str::<ServerMessage>(trimmed).ok()
+    serde_json::from_str::<Envelope>(trimmed).ok()
+}
+
+/// Try to deserialize a text or binary frame as a server message.  Binary
+/// frames are read with the `encoding` agreed in the handshake.
+///
+/// Returns `None` for empty text frames and undecodable input.
+pub fn parse_server_frame(frame: &Frame, encoding: Encoding) -> Option<Envelope> {
+    match frame {
+        Frame::Text(text) => parse_server_line(text),
+        Frame::Binary(_) => encoding.decode(frame).ok(),
+    }
+}
+
+/// Follow the encoding agreed in the handshake: every message after
+/// `ServerHello` is sent in it.
+fn note_encoding(envelope: &Envelope, encoding: &mut Encoding, agreed: &watch::Sender<Encoding>) {
+    if let ServerMessage::ServerHello { encoding: e, .. } = envelope.msg {
+        *encoding = e;
+        let _ = agreed.send(e);
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -39,7 +60,7 @@ pub fn parse_server_line(line: &str) -> Option<ServerMessage> {
 /// convenience method [`connect_ws`](NetClient::connect_ws) (WebSocket).
 ///
 /// The returned client exposes:
-/// - [`incoming`](NetClient::incoming) — an [`mpsc::UnboundedReceiver<ServerMessage>`]
+/// - [`incoming`](NetClient::incoming) — an [`mpsc::UnboundedReceiver<Envelope>`]
 ///   for server messages. The channel closing signals disconnection.
 /// - [`send`](NetClient::send) — a non-async, non-blocking method to enqueue
 ///   a [`ClientMessage`] for transmission.
@@ -48,7 +69,7 @@ pub fn parse_server_line(line: &str) -> Option<ServerMessage> {
 /// any async context.
 pub struct NetClient {
     /// Receive parsed server messages. Channel close = disconnected.
-    pub incoming: mpsc::UnboundedReceiver<ServerMessage>,
+    pub incoming: mpsc::UnboundedReceiver<Envelope>,
     /// Send-side of the writer channel (kept for [`Self::send`]).
     outgoing: mpsc::UnboundedSender<ClientMessage>,
 }
@@ -69,9 +90,10 @@ impl NetClient {
 
         let (msg_tx, msg_rx) = mpsc::unbounded_channel();
         let (cmd_tx, cmd_rx) = mpsc::unbounded_channel::<ClientMessage>();
+        let (encoding_tx, encoding_rx) = watch::channel(Encoding::Json);
 
-        Self::spawn_reader_task(reader, msg_tx);
-        Self::spawn_writer_task(writer, cmd_rx);
+        Self::spawn_reader_task(reader, msg_tx, encoding_tx);
+        Self::spawn_writer_task(writer, cmd_rx, encoding_rx);
 
         Self {
             incoming: msg_rx,
@@ -101,8 +123,11 @@ impl NetClient {
     ///
     /// This is non-blocking — the message is written to a channel and the
     /// background writer task handles the actual I/O.
-    pub fn send(&self, msg: ClientMessage) -> Result<(), mpsc::error::SendError<ClientMessage>> {
-        self.outgoing.send(msg)
+    pub fn send(
+        &self,
+        msg: ClientMessage,
+    ) -> Result<(), Box<mpsc::error::SendError<ClientMessage>>> {
+        self.outgoing.send(msg).map_err(Box::new)
     }
 
     // ------------------------------------------------------------------
@@ -123,20 +148,22 @@ impl NetClient {
 
         let (msg_tx, msg_rx) = mpsc::unbounded_channel();
         let (cmd_tx, mut cmd_rx) = mpsc::unbounded_channel::<ClientMessage>();
+        let (encoding_tx, encoding_rx) = watch::channel(Encoding::Json);
 
         // Reader task (spawn_local — no Send required)
         wasm_bindgen_futures::spawn_local(async move {
-            while let Some(frame) = stream.next().await {
-                match frame {
-                    Ok(Message::Text(text)) => {
-                        if let Some(msg) = parse_server_line(&text)
-                            && msg_tx.send(msg).is_err()
-                        {
-                            break;
-                        }
-                    }
-                    Ok(Message::Bytes(_)) => {} // skip binary frames
-                    Err(_) => break,
+            let mut encoding = Encoding::Json;
+            while let Some(Ok(message)) = stream.next().await {
+                let frame = match message {
+                    Message::Text(text) => Frame::Text(text),
+                    Message::Bytes(bytes) => Frame::Binary(bytes),
+                };
+                let Some(msg) = parse_server_frame(&frame, encoding) else {
+                    continue;
+                };
+                note_encoding(&msg, &mut encoding, &encoding_tx);
+                if msg_tx.send(msg).is_err() {
+                    break;
                 }
             }
             // Stream ended or error — channel drops, signalling disconnect.
@@ -145,11 +172,12 @@ impl NetClient {
         // Writer task (spawn_local — no Send required)
         wasm_bindgen_futures::spawn_local(async move {
             while let Some(msg) = cmd_rx.recv().await {
-                let json = match serde_json::to_string(&msg) {
-                    Ok(j) => j,
+                let message = match encoding_rx.borrow().encode(&msg) {
+                    Ok(Frame::Text(text)) => Message::Text(text),
+                    Ok(Frame::Binary(bytes)) => Message::Bytes(bytes),
                     Err(_) => continue,
                 };
-                if sink.send(Message::Text(json)).await.is_err() {
+                if sink.send(message).await.is_err() {
                     break;
                 }
             }
@@ -169,13 +197,17 @@ impl NetClient {
     #[cfg(feature = "native")]
     fn spawn_reader_task<R: TransportReader>(
         mut reader: R,
-        msg_tx: mpsc::UnboundedSender<ServerMessage>,
+        msg_tx: mpsc::UnboundedSender<Envelope>,
+        encoding_tx: watch::Sender<Encoding>,
     ) {
         tokio::spawn(async move {
-            while let Ok(Some(line)) = reader.recv().await {
-                if let Some(msg) = parse_server_line(&line)
-                    && msg_tx.send(msg).is_err()
-                {
+            let mut encoding = Encoding::Json;
+            while let Ok(Some(frame)) = reader.recv().await {
+                let Some(msg) = parse_server_frame(&frame, encoding) else {
+                    continue;
+                };
+                note_encoding(&msg, &mut encoding, &encoding_tx);
+                if msg_tx.send(msg).is_err() {
                     break;
                 }
             }
@@ -188,14 +220,15 @@ impl NetClient {
     fn spawn_writer_task<W: TransportWriter>(
         mut writer: W,
         mut cmd_rx: mpsc::UnboundedReceiver<ClientMessage>,
+        encoding_rx: watch::Receiver<Encoding>,
     ) {
         tokio::spawn(async move {
             while let Some(msg) = cmd_rx.recv().await {
-                let json = match serde_json::to_string(&msg) {
-                    Ok(j) => j,
+                let frame = match encoding_rx.borrow().encode(&msg) {
+                    Ok(f) => f,
                     Err(_) => continue,
                 };
-                if writer.send(&json).await.is_err() {
+                if writer.send(frame).await.is_err() {
                     break;
                 }
             }
@@ -5,8 +5,8 @@
 //! dependency.
 
 use crate::client_controller::{ClientController, PollResult};
-use crate::game_state::GameEvent;
-use poker_core::protocol::ClientMessage;
+use crate::game_state::ClientGameState;
+use poker_core::protocol::{ClientMessage, Encoding, ProtocolError};
 
 // ---------------------------------------------------------------------------
 // Configuration
@@ -18,6 +18,24 @@ pub const MAX_RECONNECT_ATTEMPTS: u32 = 5;
 /// Base delay between reconnection attempts in ms (doubles each attempt).
 pub const RECONNECT_BASE_DELAY_MS: u64 = 1_000;
 
+/// Delay before reconnection attempt `attempt` (counting from 0): first the
+/// wait the server asked for if it shut down, then exponential back-off.
+pub fn reconnect_delay_ms(state: &ClientGameState, attempt: u32) -> u64 {
+    match state.reconnect_after {
+        Some(secs) if attempt == 0 => u64::from(secs) * 1_000,
+        _ => RECONNECT_BASE_DELAY_MS * 2u64.pow(attempt),
+    }
+}
+
+/// Whether to sit back in after rejoining.  A restarted server sits every
+/// player out, so players who were in the game before the shutdown return
+/// to it.
+pub fn sit_back_in_after_rejoin(state: &ClientGameState) -> bool {
+    state.reconnect_after.is_some()
+        && !state.spectating
+        && !state.sitting_out_players.contains(&state.our_player_id)
+}
+
 // ---------------------------------------------------------------------------
 // Session persistence trait
 // ---------------------------------------------------------------------------
@@ -40,22 +58,50 @@ pub trait SessionStore {
 // Reconnection helper
 // ---------------------------------------------------------------------------
 
+/// Why [`try_rejoin`] did not get back into the room.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RejoinError {
+    /// The connection failed.  Trying again later may work.
+    Failed,
+    /// The server refused the rejoin, e.g. because the session expired.
+    Refused(ProtocolError),
+    /// The server has a newer build.  Keep the session and reload the page
+    /// to rejoin with the current client.
+    ReloadRequired,
+}
+
 /// Attempt to rejoin a room using a saved session token.
 ///
 /// Opens a fresh WebSocket connection, sends `Rejoin`, and waits for the
 /// server to confirm. Returns a fully-connected [`ClientController`] on
-/// success, or `None` if the session is invalid / expired.
+/// success.
+///
+/// `resume` is the state of the connection that dropped, if there was one.
+/// The new controller carries on from it, and the server replays the events
+/// missed since instead of sending a snapshot when it still can.
 #[cfg(any(feature = "native", feature = "web"))]
 pub async fn try_rejoin(
     ws_url: &str,
     room_id: &str,
     name: &str,
     session_token: &str,
-) -> Option<ClientController> {
-    let mut ctrl = ClientController::connect_ws(ws_url, name).await.ok()?;
+    resume: Option<&ClientGameState>,
+    encoding: Encoding,
+) -> Result<ClientController, RejoinError> {
+    let mut ctrl = ClientController::connect_ws(ws_url, name, encoding)
+        .await
+        .map_err(|_| RejoinError::Failed)?;
+    let last_seq = match resume {
+        Some(state) => {
+            ctrl.state = state.clone();
+            state.last_seq
+        }
+        None => 0,
+    };
     ctrl.send(ClientMessage::Rejoin {
         room_id: room_id.to_string(),
         session_token: session_token.to_string(),
+        last_seq,
     });
 
     // Wait for Rejoined or an error.
@@ -67,16 +113,17 @@ pub async fn try_rejoin(
                         && ctrl.state.our_player_id != 0
                         && !ctrl.state.room_id.is_empty()
                 {
-                    return Some(ctrl);
+                    return Ok(ctrl);
                 }
-                // Check if the latest event is an error (session expired).
-                if let Some(ev) = ctrl.state.events.back()
-                    && matches!(ev, GameEvent::ServerError { .. })
-                {
-                    return None;
+                if ctrl.state.reload_required {
+                    return Err(RejoinError::ReloadRequired);
+                }
+                if changed.error {
+                    let error = ctrl.state.last_error.clone().unwrap_or_default();
+                    return Err(RejoinError::Refused(error));
                 }
             }
-            PollResult::Disconnected => return None,
+            PollResult::Disconnected => return Err(RejoinError::Failed),
             _ => {}
         }
     }
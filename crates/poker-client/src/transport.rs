@@ -6,6 +6,7 @@
 
 use std::future::Future;
 
+use poker_core::protocol::Frame;
 use thiserror::Error;
 
 /// Errors that can occur during transport operations.
@@ -22,20 +23,21 @@ pub enum TransportError {
 
 /// Read half of a transport connection.
 ///
-/// Implementations receive text messages (typically JSON) from the remote peer.
+/// Implementations receive text (JSON) or binary (MessagePack, CBOR)
+/// frames from the remote peer.
 pub trait TransportReader: Send + 'static {
-    /// Receive the next text message.
+    /// Receive the next text or binary frame.
     ///
     /// Returns `Ok(None)` when the connection is cleanly closed.
-    fn recv(&mut self) -> impl Future<Output = Result<Option<String>, TransportError>> + Send;
+    fn recv(&mut self) -> impl Future<Output = Result<Option<Frame>, TransportError>> + Send;
 }
 
 /// Write half of a transport connection.
 ///
-/// Implementations send text messages (typically JSON) to the remote peer.
+/// Implementations send text or binary frames to the remote peer.
 pub trait TransportWriter: Send + 'static {
-    /// Send a text message to the remote peer.
-    fn send(&mut self, text: &str) -> impl Future<Output = Result<(), TransportError>> + Send;
+    /// Send a frame to the remote peer.
+    fn send(&mut self, frame: Frame) -> impl Future<Output = Result<(), TransportError>> + Send;
 }
 
 /// A bidirectional transport that can be split into independent read and write
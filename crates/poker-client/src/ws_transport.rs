@@ -5,6 +5,7 @@
 
 use futures_util::stream::{SplitSink, SplitStream};
 use futures_util::{SinkExt, StreamExt};
+use poker_core::protocol::Frame;
 use tokio::net::TcpStream;
 use tokio_tungstenite::tungstenite::Message;
 use tokio_tungstenite::{MaybeTlsStream, WebSocketStream, connect_async};
@@ -46,12 +47,13 @@ pub struct WsReader {
 }
 
 impl TransportReader for WsReader {
-    async fn recv(&mut self) -> Result<Option<String>, TransportError> {
+    async fn recv(&mut self) -> Result<Option<Frame>, TransportError> {
         loop {
             match self.stream.next().await {
-                Some(Ok(Message::Text(text))) => return Ok(Some(text.to_string())),
+                Some(Ok(Message::Text(text))) => return Ok(Some(Frame::Text(text.to_string()))),
+                Some(Ok(Message::Binary(bytes))) => return Ok(Some(Frame::Binary(bytes.to_vec()))),
                 Some(Ok(Message::Close(_))) | None => return Ok(None),
-                // Skip binary, ping, pong frames — continue to next message.
+                // Skip ping and pong frames — continue to next message.
                 Some(Ok(_)) => continue,
                 Some(Err(e)) => return Err(TransportError::Io(e.to_string())),
             }
@@ -65,9 +67,13 @@ pub struct WsWriter {
 }
 
 impl TransportWriter for WsWriter {
-    async fn send(&mut self, text: &str) -> Result<(), TransportError> {
+    async fn send(&mut self, frame: Frame) -> Result<(), TransportError> {
+        let message = match frame {
+            Frame::Text(text) => Message::text(text),
+            Frame::Binary(bytes) => Message::binary(bytes),
+        };
         self.sink
-            .send(Message::text(text))
+            .send(message)
             .await
             .map_err(|e| TransportError::Io(e.to_string()))
     }
@@ -0,0 +1,18 @@
+//! Write the protocol's JSON Schemas and TypeScript definitions.
+//!
+//! Usage: `protocol-schema [DIR]`, where `DIR` defaults to the repository's
+//! `protocol/` directory.
+
+use std::path::PathBuf;
+
+fn main() {
+    let dir = std::env::args()
+        .nth(1)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../protocol"));
+    if let Err(e) = poker_core::schema::write_files(&dir) {
+        eprintln!("Error: could not write to {}: {e}", dir.display());
+        std::process::exit(1);
+    }
+    println!("Wrote the protocol schemas to {}", dir.display());
+}
@@ -1,2 +1,4 @@
 pub mod poker;
 pub mod protocol;
+#[cfg(feature = "schema")]
+pub mod schema;
@@ -1,3 +1,4 @@
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
@@ -5,6 +6,7 @@ use crate::poker::{Card, CardSuit};
 
 /// Serializable card representation
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema, ts_rs::TS))]
 pub struct CardInfo {
     pub rank: u8, // 2-14 (14 = Ace)
     pub suit: u8, // 0-3 (Diamonds, Spades, Clubs, Hearts)
@@ -91,14 +93,30 @@ impl fmt::Display for CardInfo {
 
 /// Serializable player info for the wire protocol.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema, ts_rs::TS))]
 pub struct PlayerInfo {
     pub id: u32,
     pub name: String,
     pub chips: u32,
+    /// Seat index at the table, starting at 0.
+    #[serde(default)]
+    pub seat: u32,
+}
+
+/// What one player has bet in the hand being played.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema, ts_rs::TS))]
+pub struct PlayerBet {
+    pub player_id: u32,
+    /// Bet in the current betting round.
+    pub bet: u32,
+    /// Chips put in since the hand was dealt, blinds included.
+    pub committed: u32,
 }
 
 /// An action the player can take during a betting round.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema, ts_rs::TS))]
 #[serde(rename_all = "lowercase")]
 pub enum PlayerAction {
     Fold,
@@ -132,9 +150,11 @@ impl fmt::Display for PlayerAction {
 ///
 /// When `interval_secs` is 0 (or `None` on the wire) blinds never increase.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema, ts_rs::TS))]
 pub struct BlindConfig {
     /// Seconds between each blind increase (0 = disabled).
     #[serde(default)]
+    #[cfg_attr(feature = "schema", ts(type = "number"))]
     pub interval_secs: u64,
     /// Percentage by which blinds increase each interval (e.g. 50 = +50%).
     #[serde(default)]
@@ -148,14 +168,314 @@ impl BlindConfig {
     }
 }
 
+/// How a room is played.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema, ts_rs::TS))]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum GameMode {
+    /// Freezeout tournament: everyone starts with the same stack, blinds may
+    /// rise, and the game ends when one player holds all the chips.
+    #[default]
+    Tournament,
+    /// Cash game: players buy in for any amount within the range (in big
+    /// blinds), may top up between hands or rebuy after busting, and may
+    /// leave with their stack at any time. Blinds never change.
+    Cash {
+        min_buy_in_bbs: u32,
+        max_buy_in_bbs: u32,
+    },
+}
+
+impl GameMode {
+    /// Returns `true` for cash game rooms.
+    pub fn is_cash(&self) -> bool {
+        matches!(self, GameMode::Cash { .. })
+    }
+
+    /// The allowed buy-in range in chips for the given big blind, if this is
+    /// a cash game.
+    pub fn buy_in_range(&self, big_blind: u32) -> Option<(u32, u32)> {
+        match *self {
+            GameMode::Tournament => None,
+            GameMode::Cash {
+                min_buy_in_bbs,
+                max_buy_in_bbs,
+            } => Some((min_buy_in_bbs * big_blind, max_buy_in_bbs * big_blind)),
+        }
+    }
+}
+
+/// Tournament rebuy and add-on rules.
+///
+/// Rebuys (one starting stack each) are allowed while a player's stack is at
+/// or below the starting stack, during the first `levels` blind levels.  When
+/// the rebuy period ends the game breaks and every player still in may take
+/// a single add-on of one starting stack until the host resumes.  Without
+/// blind increases the rebuy period lasts until the host ends it.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema, ts_rs::TS))]
+pub struct RebuyConfig {
+    /// Number of blind levels during which rebuys are allowed (0 = disabled).
+    #[serde(default)]
+    pub levels: u32,
+    /// Maximum rebuys per player (0 = unlimited).
+    #[serde(default)]
+    pub max_rebuys: u32,
+    /// Whether an add-on is offered at the break after the rebuy period.
+    #[serde(default)]
+    pub add_on: bool,
+}
+
+impl RebuyConfig {
+    /// Returns `true` when the room has a rebuy period.
+    pub fn is_enabled(&self) -> bool {
+        self.levels > 0
+    }
+}
+
+/// How far spectators lag behind the table.
+///
+/// Every event is held back until `secs` seconds have passed and `hands`
+/// more hands have started.  With `hole_cards` spectators also see every
+/// player's hole cards once the delay has passed (a streamer view).
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema, ts_rs::TS))]
+pub struct SpectatorDelay {
+    #[serde(default)]
+    pub secs: u32,
+    #[serde(default)]
+    pub hands: u32,
+    #[serde(default)]
+    pub hole_cards: bool,
+}
+
+impl SpectatorDelay {
+    /// Returns `true` when spectators do not see events live.
+    pub fn is_enabled(&self) -> bool {
+        self.secs > 0 || self.hands > 0
+    }
+}
+
+/// One player's line in the session ledger: cash game buy-ins and cash-outs,
+/// or tournament entries, rebuys and add-ons.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema, ts_rs::TS))]
+pub struct LedgerEntry {
+    pub player_id: u32,
+    pub name: String,
+    /// Total chips bought (initial buy-in, top-ups, rebuys and add-ons).
+    pub bought_in: u32,
+    /// Total chips taken off the table when leaving.
+    pub cashed_out: u32,
+    /// Chips currently on the table (0 once the player has left).
+    pub stack: u32,
+    /// Whether the player is still seated.
+    pub seated: bool,
+    /// Tournaments: number of rebuys taken.
+    #[serde(default)]
+    pub rebuys: u32,
+    /// Tournaments: whether the add-on was taken.
+    #[serde(default)]
+    pub add_on: bool,
+}
+
+impl LedgerEntry {
+    /// Net session result: everything taken or still on the table, minus
+    /// everything bought.
+    pub fn net(&self) -> i64 {
+        i64::from(self.cashed_out) + i64::from(self.stack) - i64::from(self.bought_in)
+    }
+}
+
+/// One line of the final tournament standings.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema, ts_rs::TS))]
+pub struct Standing {
+    /// Finishing place, starting at 1.  Players who busted in the same hand
+    /// with the same starting stack share a place.
+    pub place: u32,
+    pub player_id: u32,
+    pub name: String,
+    /// Chips won from the prize pool.
+    pub payout: u32,
+}
+
+/// A room as shown in the lobby (`GET /api/rooms`, `RoomList`).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema, ts_rs::TS))]
+pub struct RoomSummary {
+    pub room_id: String,
+    /// Seated players.
+    pub players: u32,
+    /// Seats at the table (per table in a multi-table tournament).
+    pub table_size: u32,
+    pub spectators: u32,
+    /// Current blinds.
+    pub small_blind: u32,
+    pub big_blind: u32,
+    /// Blind increase schedule.
+    pub blind_config: BlindConfig,
+    pub game_mode: GameMode,
+    /// A table (or the registration room) of a multi-table tournament.
+    pub multi_table: bool,
+    /// Whether the game has started.
+    pub started: bool,
+    pub allow_late_entry: bool,
+    /// Whether joining needs a password (or an invite).
+    pub password: bool,
+}
+
+impl RoomSummary {
+    /// Whether a new player could take a seat right now (given the
+    /// password, if one is needed).
+    pub fn is_joinable(&self) -> bool {
+        if self.started
+            && (self.multi_table || !(self.game_mode.is_cash() || self.allow_late_entry))
+        {
+            return false;
+        }
+        // Multi-table tournaments open tables as entrants register.
+        self.multi_table || self.players < self.table_size
+    }
+
+    /// Where the room stands: "Lobby" before the game starts, then "Open"
+    /// while players can still sit down, "Full" or "In play".
+    pub fn status(&self) -> &'static str {
+        match (self.started, self.is_joinable()) {
+            (false, true) => "Lobby",
+            (_, false) if !self.multi_table && self.players >= self.table_size => "Full",
+            (true, true) => "Open",
+            _ => "In play",
+        }
+    }
+
+    /// Short description of the game: "Cash 40-100 BB", "Tournament" or
+    /// "Multi-table tournament".
+    pub fn variant(&self) -> String {
+        match self.game_mode {
+            GameMode::Cash {
+                min_buy_in_bbs,
+                max_buy_in_bbs,
+            } => format!("Cash {min_buy_in_bbs}-{max_buy_in_bbs} BB"),
+            GameMode::Tournament if self.multi_table => "Multi-table tournament".to_string(),
+            GameMode::Tournament => "Tournament".to_string(),
+        }
+    }
+}
+
+/// Everything `GET /api/rooms/{id}` tells about a room.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema, ts_rs::TS))]
+pub struct RoomDetails {
+    #[serde(flatten)]
+    pub summary: RoomSummary,
+    /// Name of the host, if seated.
+    pub host: Option<String>,
+    /// Starting stack (default buy-in in cash games), in big blinds.
+    pub starting_bbs: u32,
+    pub rebuy_config: RebuyConfig,
+    /// Tournament payout percentages per place.
+    pub payouts: Vec<u32>,
+    pub hand_number: u32,
+    pub spectator_delay: SpectatorDelay,
+    /// The players at the table; left out for password-protected rooms.
+    pub players: Vec<PlayerInfo>,
+}
+
 fn default_starting_bbs() -> u32 {
     100
 }
 
+fn default_payouts() -> Vec<u32> {
+    vec![100]
+}
+
+fn default_table_size() -> u32 {
+    9
+}
+
+// ---------------------------------------------------------------------------
+// Protocol version
+// ---------------------------------------------------------------------------
+
+/// Version of the client/server protocol.  Bump it whenever a message
+/// changes in a way an older peer cannot read.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// Oldest client protocol version the server still accepts.
+pub const MIN_PROTOCOL_VERSION: u32 = 1;
+
+/// Identifies the build.  Set `POKER_BUILD_ID` when compiling (e.g. to the
+/// git commit) so that every deploy gets its own; it defaults to the crate
+/// version.  Web clients from another build are told to reload.
+pub const BUILD_ID: &str = match option_env!("POKER_BUILD_ID") {
+    Some(id) => id,
+    None => env!("CARGO_PKG_VERSION"),
+};
+
+/// Optional features the server offers.  A client lists the ones it
+/// understands in `Hello`, and the server answers with those both support.
+pub const FEATURES: &[&str] = &[
+    "spectate",
+    "multi_table",
+    "invites",
+    "room_list",
+    "replay",
+    "action_ack",
+];
+
+/// What kind of client is connecting.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema, ts_rs::TS))]
+#[serde(rename_all = "snake_case")]
+pub enum ClientKind {
+    /// The browser client, which can reload itself to update.
+    Web,
+    /// A native client, such as the terminal client.
+    Native,
+    #[default]
+    #[serde(other)]
+    Other,
+}
+
+/// How messages are encoded on the wire.  JSON travels in text frames and
+/// the more compact binary encodings in binary frames.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema, ts_rs::TS))]
+#[serde(rename_all = "snake_case")]
+pub enum Encoding {
+    #[serde(rename = "msgpack")]
+    MessagePack,
+    Cbor,
+    /// Encodings this build does not know fall back to JSON.
+    #[default]
+    #[serde(other)]
+    Json,
+}
+
 /// Messages sent from client to server
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema, ts_rs::TS))]
 #[serde(tag = "type")]
 pub enum ClientMessage {
+    /// First message on every connection, before anything but `Ping`.
+    /// Answered with `ServerHello`, or with `UpgradeRequired` and a closed
+    /// connection when the server cannot talk to this client.
+    Hello {
+        protocol_version: u32,
+        #[serde(default)]
+        client_kind: ClientKind,
+        /// Optional features the client understands (see [`FEATURES`]).
+        #[serde(default)]
+        capabilities: Vec<String>,
+        /// The client's [`BUILD_ID`].
+        #[serde(default)]
+        build: String,
+        /// The encoding the client would like to use after the handshake.
+        #[serde(default)]
+        encoding: Encoding,
+    },
+
     /// Join the game with a player name (sent automatically on connect)
     Join { name: String },
 
@@ -165,12 +485,81 @@ pub enum ClientMessage {
         #[serde(default)]
         blind_config: BlindConfig,
         /// Number of big blinds each player starts with (default: 50).
+        /// In cash games this is the default buy-in, clamped to the range.
         #[serde(default = "default_starting_bbs")]
         starting_bbs: u32,
+        /// Tournament (default) or cash game.
+        #[serde(default)]
+        game_mode: GameMode,
+        /// Tournament rebuy / add-on rules (default: freezeout).
+        #[serde(default)]
+        rebuy_config: RebuyConfig,
+        /// Percentage of the prize pool paid to each place, best first
+        /// (default: winner takes all).
+        #[serde(default = "default_payouts")]
+        payouts: Vec<u32>,
+        /// Tournaments: spread the entrants over several tables that are
+        /// balanced and merged as players bust.
+        #[serde(default)]
+        multi_table: bool,
+        /// Seats at the table (default: 9).  In a multi-table tournament,
+        /// the seats at each table.
+        #[serde(default = "default_table_size")]
+        table_size: u32,
+        /// Delay before spectators see events (default: live, no hole
+        /// cards).
+        #[serde(default)]
+        spectator_delay: SpectatorDelay,
+        /// Password needed to join or watch the room (default: none).
+        #[serde(default)]
+        password: String,
+        /// Leave the room out of the public room list.
+        #[serde(default)]
+        unlisted: bool,
+    },
+
+    /// Join an existing room with the given ID and player name, optionally
+    /// picking an empty seat (a random one otherwise).  A password-protected
+    /// room needs its `password` or an `invite`; with an invite the room ID
+    /// may be left empty.
+    JoinRoom {
+        room_id: String,
+        name: String,
+        #[serde(default)]
+        seat: Option<u32>,
+        #[serde(default)]
+        password: String,
+        #[serde(default)]
+        invite: String,
+    },
+
+    /// Move to an empty seat.  Takes effect at once between hands, or when
+    /// the next hand starts.
+    ChangeSeat { seat: u32 },
+
+    /// Watch a room without a seat: public events only, never anyone's hole
+    /// cards.  `name` is shown next to spectator chat.  `password` and
+    /// `invite` work as for `JoinRoom`.
+    Spectate {
+        room_id: String,
+        #[serde(default)]
+        name: String,
+        #[serde(default)]
+        password: String,
+        #[serde(default)]
+        invite: String,
     },
 
-    /// Join an existing room with the given ID and player name.
-    JoinRoom { room_id: String, name: String },
+    /// Spectators: sit down in an empty seat (a random one if `seat` is
+    /// not given), on the same terms as joining the room.  The player is
+    /// dealt in from the next hand.
+    TakeSeat {
+        #[serde(default)]
+        seat: Option<u32>,
+    },
+
+    /// Allow or forbid chat from spectators (host only).
+    ToggleSpectatorChat,
 
     /// Request list of current players
     GetPlayers,
@@ -181,20 +570,45 @@ pub enum ClientMessage {
     /// Request to start the game
     StartGame,
 
+    // Betting actions may carry a `request_id` chosen by the client,
+    // counting up.  The server answers `ActionAck` or `ActionRejected` with
+    // it, and answers a repeated ID again without acting twice, so retries
+    // are safe.  An old ID whose answer is gone is rejected as a duplicate.
     /// Fold current hand
-    Fold,
+    Fold {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[cfg_attr(feature = "schema", ts(optional, type = "number"))]
+        request_id: Option<u64>,
+    },
 
     /// Check (pass without betting)
-    Check,
+    Check {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[cfg_attr(feature = "schema", ts(optional, type = "number"))]
+        request_id: Option<u64>,
+    },
 
     /// Call the current bet
-    Call,
+    Call {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[cfg_attr(feature = "schema", ts(optional, type = "number"))]
+        request_id: Option<u64>,
+    },
 
     /// Raise by a specific amount
-    Raise { amount: u32 },
+    Raise {
+        amount: u32,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[cfg_attr(feature = "schema", ts(optional, type = "number"))]
+        request_id: Option<u64>,
+    },
 
     /// Go all-in
-    AllIn,
+    AllIn {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[cfg_attr(feature = "schema", ts(optional, type = "number"))]
+        request_id: Option<u64>,
+    },
 
     /// Request to sit out (auto-fold/check each turn).
     SitOut,
@@ -205,20 +619,156 @@ pub enum ClientMessage {
     /// Toggle late entry (host only).
     ToggleLateEntry,
 
+    /// Pause the game: freezes the blind clock and turn timers (host only).
+    PauseGame,
+
+    /// Resume a paused game (host only).
+    ResumeGame,
+
+    /// Buy chips in a cash game: a top-up between hands or a rebuy after
+    /// busting. Applied at the start of the next hand if one is in progress.
+    BuyIn { amount: u32 },
+
+    /// Leave a cash game table, taking the current stack. If the player is
+    /// in a hand they fold and leave once it is over.
+    LeaveTable,
+
+    /// Request the session ledger (cash game results or tournament
+    /// rebuys and add-ons).
+    GetLedger,
+
+    /// Tournaments: rebuy one starting stack during the rebuy period.
+    Rebuy,
+
+    /// Tournaments: take the single add-on during the break.
+    AddOn,
+
+    /// End the rebuy period now and start the add-on break, if any (host only).
+    EndRebuyPeriod,
+
+    /// Remove a player from the room, folding their hand first (host only).
+    /// With `ban` they may not come back under that name or session.
+    KickPlayer {
+        player_id: u32,
+        #[serde(default)]
+        ban: bool,
+    },
+
+    /// Make another seated player the host (host only).
+    TransferHost { player_id: u32 },
+
+    /// End the game now (host only).  A hand in progress is called off.
+    EndGame,
+
+    /// Set the blinds and the blind increase schedule (host only).  Takes
+    /// effect at once before the game starts, otherwise with the next hand.
+    ChangeBlinds {
+        small_blind: u32,
+        big_blind: u32,
+        #[serde(default)]
+        blind_config: BlindConfig,
+    },
+
+    /// Create an invite token that lets its holder into the room without
+    /// the password (host only).  It works once if `single_use`, and for
+    /// `expires_in_secs` seconds if that is not 0.
+    CreateInvite {
+        #[serde(default)]
+        single_use: bool,
+        #[serde(default)]
+        expires_in_secs: u32,
+    },
+
+    /// Before joining: list the public rooms (answered with `RoomList`).
+    ListRooms,
+
     /// Re-join a room after a disconnect using a previously issued session token.
+    /// With the `seq` of the last event seen in `room_id`, the server replays
+    /// the events missed since (answering with `Resumed`) when it still has
+    /// them, and sends a `Rejoined` snapshot otherwise.
     Rejoin {
         room_id: String,
         session_token: String,
+        #[serde(default)]
+        #[cfg_attr(feature = "schema", ts(type = "number"))]
+        last_seq: u64,
     },
 
     /// Ping to check connection
     Ping,
 }
 
+impl ClientMessage {
+    /// The betting action this message takes, with the raise amount.
+    pub fn action(&self) -> Option<(PlayerAction, u32)> {
+        match self {
+            ClientMessage::Fold { .. } => Some((PlayerAction::Fold, 0)),
+            ClientMessage::Check { .. } => Some((PlayerAction::Check, 0)),
+            ClientMessage::Call { .. } => Some((PlayerAction::Call, 0)),
+            ClientMessage::Raise { amount, .. } => Some((PlayerAction::Raise, *amount)),
+            ClientMessage::AllIn { .. } => Some((PlayerAction::AllIn, 0)),
+            _ => None,
+        }
+    }
+
+    /// The request ID of a betting action, if it has one.
+    pub fn request_id(&self) -> Option<u64> {
+        match self {
+            ClientMessage::Fold { request_id }
+            | ClientMessage::Check { request_id }
+            | ClientMessage::Call { request_id }
+            | ClientMessage::Raise { request_id, .. }
+            | ClientMessage::AllIn { request_id } => *request_id,
+            _ => None,
+        }
+    }
+
+    /// Number a betting action.  Other messages are left as they are.
+    pub fn set_request_id(&mut self, id: u64) {
+        match self {
+            ClientMessage::Fold { request_id }
+            | ClientMessage::Check { request_id }
+            | ClientMessage::Call { request_id }
+            | ClientMessage::Raise { request_id, .. }
+            | ClientMessage::AllIn { request_id } => *request_id = Some(id),
+            _ => {}
+        }
+    }
+}
+
 /// Messages sent from server to client
+// `Rejoined` is much larger than the rest, but it is sent once per rejoin
+// and boxing it would only complicate every match on it.
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema, ts_rs::TS))]
 #[serde(tag = "type")]
 pub enum ServerMessage {
+    /// Answer to `Hello`: the server's protocol version and build, and the
+    /// optional features both sides support.
+    ServerHello {
+        protocol_version: u32,
+        build: String,
+        features: Vec<String>,
+        /// The encoding both sides use for every message after this one.
+        #[serde(default)]
+        encoding: Encoding,
+    },
+
+    /// The server cannot talk to this client and closes the connection.
+    /// A web client with `reload` set reloads the page to get the current
+    /// build.
+    UpgradeRequired { message: String, reload: bool },
+
+    /// Answer to `Rejoin` when the server still has every event the player
+    /// missed: the `replayed` events follow, and the client keeps the state
+    /// it had.
+    Resumed {
+        room_id: String,
+        player_id: u32,
+        replayed: u32,
+    },
+
     /// Welcome message on connection
     Welcome { message: String },
 
@@ -239,7 +789,20 @@ pub enum ServerMessage {
     },
 
     /// A new player joined
-    PlayerJoined { player_id: u32, name: String },
+    PlayerJoined {
+        player_id: u32,
+        name: String,
+        #[serde(default)]
+        seat: u32,
+    },
+
+    /// A player moved to another seat. `pending` is true when the move
+    /// happens at the start of the next hand.
+    SeatChanged {
+        player_id: u32,
+        seat: u32,
+        pending: bool,
+    },
 
     /// A player left
     PlayerLeft { player_id: u32 },
@@ -250,6 +813,25 @@ pub enum ServerMessage {
     /// Chat message from a player
     ChatMessage { player_id: u32, message: String },
 
+    /// Chat message from a spectator.
+    SpectatorChat { name: String, message: String },
+
+    /// Now watching `room_id` as a spectator.  A `Rejoined` snapshot of the
+    /// table (with no hole cards and `player_id` 0) follows, held back by
+    /// `delay` like every later event.
+    Spectating {
+        room_id: String,
+        #[serde(default)]
+        delay: SpectatorDelay,
+    },
+
+    /// Streamer view: every dealt player's hole cards, sent to spectators
+    /// once the delay has passed.
+    SpectatorHoleCards { hands: Vec<(u32, [CardInfo; 2])> },
+
+    /// Number of spectators watching the room and whether they may chat.
+    Spectators { count: u32, chat_allowed: bool },
+
     /// Game has started
     GameStarted,
 
@@ -310,12 +892,34 @@ pub enum ServerMessage {
         winners: Vec<(u32, u32, String)>, // (player_id, amount_won, hand_description)
     },
 
-    /// Player eliminated (out of chips)
-    PlayerEliminated { player_id: u32 },
+    /// Player eliminated (out of chips) in the given finishing place.
+    PlayerEliminated {
+        player_id: u32,
+        #[serde(default)]
+        place: u32,
+    },
 
     /// Game over - tournament finished
     GameOver { winner_id: u32, winner_name: String },
 
+    /// Final tournament standings, best first, sent right after `GameOver`.
+    FinalStandings {
+        standings: Vec<Standing>,
+        prize_pool: u32,
+    },
+
+    /// Multi-table tournaments: the player was moved to another table.  The
+    /// connection now belongs to `room_id`; a `Rejoined` snapshot of the new
+    /// table follows.
+    TableMoved { room_id: String },
+
+    /// Multi-table tournament progress.
+    TournamentUpdate {
+        entrants: u32,
+        players_remaining: u32,
+        tables: u32,
+    },
+
     /// Blinds have increased at the start of a new hand.
     BlindsIncreased { small_blind: u32, big_blind: u32 },
 
@@ -333,17 +937,101 @@ pub enum ServerMessage {
     /// Late-entry setting changed.
     LateEntryChanged { allowed: bool },
 
+    /// The host removed a player, who may be banned from coming back.  A
+    /// `PlayerLeft` follows once they are off the table (after the current
+    /// hand if they were in it).
+    PlayerKicked { player_id: u32, banned: bool },
+
+    /// Another player is now the host.
+    HostChanged { player_id: u32 },
+
+    /// The host ended the game early.  Tournaments follow with
+    /// `FinalStandings`, cash games with the final `SessionLedger`.
+    GameEnded,
+
+    /// The host changed the blinds; `pending` until the next hand starts.
+    BlindsChanged {
+        small_blind: u32,
+        big_blind: u32,
+        blind_config: BlindConfig,
+        pending: bool,
+    },
+
     /// The game is paused waiting for enough active players to continue.
     WaitingForPlayers,
 
+    /// The host paused the game.
+    GamePaused,
+
+    /// The host resumed the game.
+    GameResumed,
+
+    /// A player bought chips in a cash game. `pending` is true when the
+    /// chips will be added at the start of the next hand.
+    PlayerBoughtIn {
+        player_id: u32,
+        amount: u32,
+        pending: bool,
+    },
+
+    /// A player left a cash game table with `amount` chips.
+    PlayerCashedOut { player_id: u32, amount: u32 },
+
+    /// Session ledger: net result per player in cash games, rebuys and
+    /// add-ons in tournaments.
+    SessionLedger { entries: Vec<LedgerEntry> },
+
+    /// A player rebought during the tournament rebuy period. `pending` is
+    /// true when the chips will be added at the start of the next hand.
+    PlayerRebought {
+        player_id: u32,
+        amount: u32,
+        pending: bool,
+    },
+
+    /// A player took the tournament add-on.
+    PlayerAddedOn {
+        player_id: u32,
+        amount: u32,
+        pending: bool,
+    },
+
+    /// The rebuy period is over. When `add_on_open` is true the game is
+    /// paused for the add-on break until the host resumes.
+    RebuyPeriodEnded { add_on_open: bool },
+
+    /// The add-on break is over.
+    AddOnClosed,
+
+    /// Total chips paid into the tournament (entries, rebuys and add-ons).
+    /// Sent after every rebuy or add-on and just before `GameOver`.
+    PrizePool { total: u32 },
+
     /// A room was successfully created.
     RoomCreated { room_id: String },
 
+    /// The public rooms, sorted by ID (reply to `ListRooms`).
+    RoomList { rooms: Vec<RoomSummary> },
+
+    /// An invite to the room was created (sent to the host only).
+    InviteCreated {
+        room_id: String,
+        token: String,
+        single_use: bool,
+        expires_in_secs: u32,
+    },
+
     /// Successfully joined a room.
     RoomJoined {
         room_id: String,
         #[serde(default)]
         blind_config: BlindConfig,
+        #[serde(default)]
+        game_mode: GameMode,
+        #[serde(default)]
+        rebuy_config: RebuyConfig,
+        #[serde(default = "default_table_size")]
+        table_size: u32,
     },
 
     /// Full state snapshot sent on successful rejoin.
@@ -368,15 +1056,60 @@ pub enum ServerMessage {
         allow_late_entry: bool,
         #[serde(default)]
         is_host: bool,
+        #[serde(default)]
+        paused: bool,
+        #[serde(default)]
+        game_mode: GameMode,
+        #[serde(default)]
+        rebuy_config: RebuyConfig,
+        /// Whether rebuys are currently allowed.
+        #[serde(default)]
+        rebuy_open: bool,
+        /// Whether the add-on break is in progress.
+        #[serde(default)]
+        add_on_open: bool,
+        #[serde(default)]
+        prize_pool: u32,
+        #[serde(default = "default_table_size")]
+        table_size: u32,
         dealer_id: u32,
         small_blind_id: u32,
         big_blind_id: u32,
         small_blind: u32,
         big_blind: u32,
+        /// Bets of the players who have put chips in this hand.
+        #[serde(default)]
+        bets: Vec<PlayerBet>,
+        /// Bet to call in the current betting round.
+        #[serde(default)]
+        current_bet: u32,
+        #[serde(default)]
+        min_raise: u32,
+        /// Player whose turn it is, 0 when nobody is to act.
+        #[serde(default)]
+        to_act: u32,
+        /// Seconds left on their turn timer, 0 when none is running.
+        #[serde(default)]
+        turn_secs_left: u32,
+    },
+
+    /// The server is going down.  No new hands are dealt; the hands being
+    /// played are finished before the connection closes.  Clients should
+    /// try to rejoin after `reconnect_after` seconds.
+    ServerShutdown {
+        reason: String,
+        reconnect_after: u32,
     },
 
+    /// A notice from the server operators, e.g. planned maintenance.
+    Announcement { message: String },
+
     /// Room-related error (e.g. "room ID taken", "room not found").
-    RoomError { message: String },
+    RoomError {
+        message: String,
+        #[serde(default)]
+        error: ProtocolError,
+    },
 
     /// Generic OK response
     Ok,
@@ -384,8 +1117,312 @@ pub enum ServerMessage {
     /// Pong response to ping
     Pong,
 
+    /// The betting action with this `request_id` was applied.
+    ActionAck {
+        #[cfg_attr(feature = "schema", ts(type = "number"))]
+        request_id: u64,
+    },
+
+    /// The betting action with this `request_id` was refused.
+    ActionRejected {
+        #[cfg_attr(feature = "schema", ts(type = "number"))]
+        request_id: u64,
+        message: String,
+        #[serde(default)]
+        error: ProtocolError,
+    },
+
     /// Error message
-    Error { message: String },
+    Error {
+        message: String,
+        #[serde(default)]
+        error: ProtocolError,
+    },
+}
+
+impl ServerMessage {
+    /// A refused request, with the error's own message.
+    pub fn error(error: ProtocolError) -> Self {
+        ServerMessage::Error {
+            message: error.to_string(),
+            error,
+        }
+    }
+
+    /// The answer to a numbered betting action.
+    pub fn action_answer(request_id: u64, answer: Result<(), ProtocolError>) -> Self {
+        match answer {
+            Ok(()) => ServerMessage::ActionAck { request_id },
+            Err(error) => ServerMessage::ActionRejected {
+                request_id,
+                message: error.to_string(),
+                error,
+            },
+        }
+    }
+
+    /// A refused create, join, spectate or rejoin request.
+    pub fn room_error(error: ProtocolError) -> Self {
+        ServerMessage::RoomError {
+            message: error.to_string(),
+            error,
+        }
+    }
+}
+
+/// Why the server refused a request.  The `code` on the wire is stable, so
+/// clients can act on it; `Display` gives the message shown to players.
+/// Codes this client does not know read as `Unknown`.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema, ts_rs::TS))]
+#[serde(tag = "code", rename_all = "snake_case")]
+pub enum ProtocolError {
+    /// The message could not be decoded.
+    InvalidMessage {
+        message: String,
+    },
+    /// The client skipped the `Hello` handshake.
+    OutOfDate,
+    ShuttingDown,
+    /// The request needs a room, or cannot be made from one.
+    NotInRoom,
+    AlreadyInRoom,
+    RoomNotFound {
+        room_id: String,
+    },
+    RoomExists {
+        room_id: String,
+    },
+    /// The server has as many rooms as it allows.
+    TooManyRooms {
+        max: u32,
+    },
+    PasswordRequired,
+    WrongPassword,
+    InvalidInvite,
+    Banned,
+    /// The session token is unknown or its seat was given up.
+    SessionExpired,
+    /// The game has started and the room takes no more players.
+    GameInProgress,
+    RoomFull,
+    /// Seats are numbered from 0, like [`PlayerInfo::seat`].
+    NoSuchSeat {
+        seat: u32,
+    },
+    SeatTaken {
+        seat: u32,
+    },
+    TooManySpectators {
+        max: u32,
+    },
+    PlayerNotFound,
+    NotHost,
+    GameNotStarted,
+    GameAlreadyStarted,
+    GamePaused,
+    NotYourTurn,
+    /// The action is not one of those open to the player.
+    InvalidAction {
+        valid_actions: Vec<PlayerAction>,
+    },
+    /// An amount (a raise or a buy-in) outside the allowed range.
+    InvalidAmount {
+        min: u32,
+        max: u32,
+    },
+    /// A numbered action sent again after its answer was forgotten.
+    DuplicateRequest,
+    /// Settings or values that fail validation.
+    InvalidRequest {
+        message: String,
+    },
+    /// The rules of the room do not allow the request right now.
+    NotAllowed {
+        message: String,
+    },
+    #[default]
+    #[serde(other)]
+    Unknown,
+}
+
+impl ProtocolError {
+    /// An [`InvalidRequest`](Self::InvalidRequest) error.
+    pub fn invalid(message: impl Into<String>) -> Self {
+        ProtocolError::InvalidRequest {
+            message: message.into(),
+        }
+    }
+
+    /// A [`NotAllowed`](Self::NotAllowed) error.
+    pub fn not_allowed(message: impl Into<String>) -> Self {
+        ProtocolError::NotAllowed {
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ProtocolError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProtocolError::InvalidMessage { message } => write!(f, "Invalid message: {message}"),
+            ProtocolError::OutOfDate => {
+                f.write_str("This client is out of date. Reload the page or update it to play.")
+            }
+            ProtocolError::ShuttingDown => f.write_str("The server is shutting down"),
+            ProtocolError::NotInRoom => f.write_str("Must create or join a room first"),
+            ProtocolError::AlreadyInRoom => f.write_str("Already in a room"),
+            ProtocolError::RoomNotFound { room_id } => write!(f, "Room '{room_id}' not found"),
+            ProtocolError::RoomExists { room_id } => write!(f, "Room '{room_id}' already exists"),
+            ProtocolError::TooManyRooms { max } => {
+                write!(f, "The server is full ({max} rooms); try again later")
+            }
+            ProtocolError::PasswordRequired => {
+                f.write_str("This room needs a password or an invite")
+            }
+            ProtocolError::WrongPassword => f.write_str("Wrong password"),
+            ProtocolError::InvalidInvite => f.write_str("Invalid or expired invite"),
+            ProtocolError::Banned => f.write_str("You are banned from this room"),
+            ProtocolError::SessionExpired => f.write_str("Invalid or expired session"),
+            ProtocolError::GameInProgress => f.write_str("Game already in progress"),
+            ProtocolError::RoomFull => f.write_str("The table is full"),
+            ProtocolError::NoSuchSeat { seat } => write!(f, "Seat {} does not exist", seat + 1),
+            ProtocolError::SeatTaken { seat } => write!(f, "Seat {} is taken", seat + 1),
+            ProtocolError::TooManySpectators { max } => {
+                write!(f, "This room already has the maximum of {max} spectators")
+            }
+            ProtocolError::PlayerNotFound => f.write_str("No such player"),
+            ProtocolError::NotHost => f.write_str("Only the host can do that"),
+            ProtocolError::GameNotStarted => f.write_str("The game has not started"),
+            ProtocolError::GameAlreadyStarted => f.write_str("Game already started"),
+            ProtocolError::GamePaused => f.write_str("Game is paused"),
+            ProtocolError::NotYourTurn => f.write_str("Not your turn"),
+            ProtocolError::InvalidAction { valid_actions } => {
+                let valid: Vec<&str> = valid_actions.iter().map(|a| a.label()).collect();
+                write!(f, "Invalid action. Valid: {}", valid.join(", "))
+            }
+            ProtocolError::InvalidAmount { min, max } if min == max => {
+                write!(f, "The amount must be {min}")
+            }
+            ProtocolError::InvalidAmount { min, max } => {
+                write!(f, "The amount must be between {min} and {max}")
+            }
+            ProtocolError::DuplicateRequest => f.write_str("That action was already answered"),
+            ProtocolError::InvalidRequest { message } | ProtocolError::NotAllowed { message } => {
+                f.write_str(message)
+            }
+            ProtocolError::Unknown => f.write_str("The server refused the request"),
+        }
+    }
+}
+
+/// A server message as sent on the wire.  Room events sent to a player carry
+/// the room's sequence number, counting up from 1; replies such as errors
+/// and pongs carry none.  A client that rejoins sends back the last number
+/// it saw.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema, ts_rs::TS))]
+pub struct Envelope {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "schema", ts(optional, type = "number"))]
+    pub seq: Option<u64>,
+    #[serde(flatten)]
+    pub msg: ServerMessage,
+}
+
+impl From<ServerMessage> for Envelope {
+    fn from(msg: ServerMessage) -> Self {
+        Self { seq: None, msg }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Handshake
+// ---------------------------------------------------------------------------
+
+/// Check a client's `Hello`.  Returns the optional features both sides
+/// support, or why the client has to be updated first.
+pub fn negotiate(
+    protocol_version: u32,
+    client_kind: ClientKind,
+    build: &str,
+    capabilities: &[String],
+) -> Result<Vec<String>, String> {
+    let update = if client_kind == ClientKind::Web {
+        "Reload the page to update it."
+    } else {
+        "Update it to play."
+    };
+    if protocol_version < MIN_PROTOCOL_VERSION {
+        return Err(format!(
+            "This client is too old for the server (protocol {protocol_version}, \
+             at least {MIN_PROTOCOL_VERSION} needed). {update}"
+        ));
+    }
+    if protocol_version > PROTOCOL_VERSION {
+        return Err(format!(
+            "This client is newer than the server (protocol {protocol_version}, \
+             at most {PROTOCOL_VERSION} supported). {update}"
+        ));
+    }
+    // The server serves the web client, so a web client from another
+    // build is stale.
+    if client_kind == ClientKind::Web && !build.is_empty() && build != BUILD_ID {
+        return Err(format!("A new version has been released. {update}"));
+    }
+    Ok(FEATURES
+        .iter()
+        .filter(|f| capabilities.iter().any(|c| c == *f))
+        .map(|f| f.to_string())
+        .collect())
+}
+
+// ---------------------------------------------------------------------------
+// Wire encoding
+// ---------------------------------------------------------------------------
+
+/// The payload of a WebSocket frame.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Frame {
+    Text(String),
+    Binary(Vec<u8>),
+}
+
+impl Encoding {
+    /// Encode a message into the frame it is sent in.
+    pub fn encode<T: Serialize>(self, msg: &T) -> Result<Frame, String> {
+        match self {
+            Encoding::Json => serde_json::to_string(msg)
+                .map(Frame::Text)
+                .map_err(|e| e.to_string()),
+            Encoding::MessagePack => rmp_serde::to_vec_named(msg)
+                .map(Frame::Binary)
+                .map_err(|e| e.to_string()),
+            Encoding::Cbor => {
+                let mut bytes = Vec::new();
+                ciborium::into_writer(msg, &mut bytes).map_err(|e| e.to_string())?;
+                Ok(Frame::Binary(bytes))
+            }
+        }
+    }
+
+    /// Decode a received frame.  Text frames are always JSON, which is what
+    /// both sides send until the handshake is done; binary frames use this
+    /// encoding.
+    pub fn decode<T: DeserializeOwned>(self, frame: &Frame) -> Result<T, String> {
+        match (frame, self) {
+            (Frame::Text(text), _) => serde_json::from_str(text).map_err(|e| e.to_string()),
+            (Frame::Binary(_), Encoding::Json) => {
+                Err("binary frame, but no binary encoding was agreed".to_string())
+            }
+            (Frame::Binary(bytes), Encoding::MessagePack) => {
+                rmp_serde::from_slice(bytes).map_err(|e| e.to_string())
+            }
+            (Frame::Binary(bytes), Encoding::Cbor) => {
+                ciborium::from_reader(bytes.as_slice()).map_err(|e| e.to_string())
+            }
+        }
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -408,6 +1445,164 @@ pub fn validate_room_id(id: &str) -> Result<(), String> {
     Ok(())
 }
 
+/// Longest room password, in characters.
+pub const MAX_PASSWORD_LEN: usize = 64;
+
+/// Validate a room password.  An empty password means none.
+pub fn validate_room_password(password: &str) -> Result<(), String> {
+    if password.chars().count() > MAX_PASSWORD_LEN {
+        return Err(format!(
+            "Password must be at most {MAX_PASSWORD_LEN} characters"
+        ));
+    }
+    Ok(())
+}
+
+/// Longest lifetime of an invite: one week.
+pub const MAX_INVITE_EXPIRY_SECS: u32 = 7 * 24 * 60 * 60;
+
+/// Validate invite settings.  Invites must be single-use, expire, or both.
+pub fn validate_invite(single_use: bool, expires_in_secs: u32) -> Result<(), String> {
+    if !single_use && expires_in_secs == 0 {
+        return Err("An invite must be single-use or expire".to_string());
+    }
+    if expires_in_secs > MAX_INVITE_EXPIRY_SECS {
+        return Err("Invites must expire within a week".to_string());
+    }
+    Ok(())
+}
+
+/// Validate tournament rebuy rules against the game mode.
+pub fn validate_rebuy_config(config: &RebuyConfig, mode: &GameMode) -> Result<(), String> {
+    if mode.is_cash() && *config != RebuyConfig::default() {
+        return Err("Rebuys and add-ons are only available in tournaments".to_string());
+    }
+    if config.add_on && !config.is_enabled() {
+        return Err("An add-on requires a rebuy period".to_string());
+    }
+    Ok(())
+}
+
+/// Validate a tournament payout structure: one percentage per paid place,
+/// adding up to 100.
+pub fn validate_payouts(payouts: &[u32]) -> Result<(), String> {
+    if payouts.is_empty() {
+        return Err("At least one place must be paid".to_string());
+    }
+    if payouts.len() > 10 {
+        return Err("At most 10 places can be paid".to_string());
+    }
+    if payouts.contains(&0) {
+        return Err("Every paid place must get more than 0%".to_string());
+    }
+    if payouts.iter().sum::<u32>() != 100 {
+        return Err("Payout percentages must add up to 100".to_string());
+    }
+    Ok(())
+}
+
+/// Validate blinds set by the host.  Cash game blinds never rise.
+pub fn validate_blinds(
+    small_blind: u32,
+    big_blind: u32,
+    blind_config: &BlindConfig,
+    mode: &GameMode,
+) -> Result<(), String> {
+    if small_blind == 0 {
+        return Err("Small blind must be at least 1".to_string());
+    }
+    if big_blind < small_blind {
+        return Err("Big blind must not be below the small blind".to_string());
+    }
+    if mode.is_cash() && blind_config.is_enabled() {
+        return Err("Cash game blinds cannot increase".to_string());
+    }
+    Ok(())
+}
+
+/// Smallest and largest number of seats at a table.
+pub const MIN_TABLE_SIZE: u32 = 2;
+pub const MAX_TABLE_SIZE: u32 = 10;
+
+/// Validate the settings of a multi-table tournament.
+///
+/// Extra tables are named after the room with a `t<n>` suffix, so the room
+/// ID must leave room for it.
+pub fn validate_multi_table(
+    room_id: &str,
+    table_size: u32,
+    mode: &GameMode,
+    rebuy_config: &RebuyConfig,
+) -> Result<(), String> {
+    if mode.is_cash() {
+        return Err("Multi-table play is only available in tournaments".to_string());
+    }
+    if rebuy_config.is_enabled() {
+        return Err("Multi-table tournaments do not support rebuys".to_string());
+    }
+    if room_id.len() > 15 {
+        return Err("Multi-table room IDs must be at most 15 characters".to_string());
+    }
+    validate_table_size(table_size)
+}
+
+/// Validate the number of seats at a table.
+pub fn validate_table_size(table_size: u32) -> Result<(), String> {
+    if !(MIN_TABLE_SIZE..=MAX_TABLE_SIZE).contains(&table_size) {
+        return Err(format!(
+            "Table size must be between {MIN_TABLE_SIZE} and {MAX_TABLE_SIZE}"
+        ));
+    }
+    Ok(())
+}
+
+/// Longest spectator delay, in seconds and in hands.
+pub const MAX_SPECTATOR_DELAY_SECS: u32 = 600;
+pub const MAX_SPECTATOR_DELAY_HANDS: u32 = 10;
+
+/// Validate a spectator delay.  Hole cards are only shown with a delay.
+pub fn validate_spectator_delay(delay: &SpectatorDelay) -> Result<(), String> {
+    if delay.secs > MAX_SPECTATOR_DELAY_SECS {
+        return Err(format!(
+            "Spectator delay must be at most {MAX_SPECTATOR_DELAY_SECS} seconds"
+        ));
+    }
+    if delay.hands > MAX_SPECTATOR_DELAY_HANDS {
+        return Err(format!(
+            "Spectator delay must be at most {MAX_SPECTATOR_DELAY_HANDS} hands"
+        ));
+    }
+    if delay.hole_cards && !delay.is_enabled() {
+        return Err("Spectators can only see hole cards with a delay".to_string());
+    }
+    Ok(())
+}
+
+/// Chips paid to each of `tied` players sharing `place`: the percentages of
+/// the places they occupy are pooled and split evenly.
+pub fn payout_for_place(prize_pool: u32, payouts: &[u32], place: u32, tied: u32) -> u32 {
+    let first = place.saturating_sub(1) as usize;
+    let pct: u32 = payouts.iter().skip(first).take(tied as usize).sum();
+    (u64::from(prize_pool) * u64::from(pct) / (100 * u64::from(tied.max(1)))) as u32
+}
+
+/// Validate a cash game buy-in range.
+pub fn validate_game_mode(mode: &GameMode) -> Result<(), String> {
+    if let GameMode::Cash {
+        min_buy_in_bbs,
+        max_buy_in_bbs,
+    } = *mode
+    {
+        if min_buy_in_bbs == 0 {
+            return Err("Minimum buy-in must be at least 1 BB".to_string());
+        }
+        if max_buy_in_bbs < min_buy_in_bbs {
+            return Err("Maximum buy-in must not be below the minimum".to_string());
+        }
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -428,4 +1623,467 @@ mod tests {
         assert!(validate_room_id("room-1").is_err());
         assert!(validate_room_id("room_1").is_err());
     }
+
+    #[test]
+    fn cash_buy_in_range() {
+        let mode = GameMode::Cash {
+            min_buy_in_bbs: 40,
+            max_buy_in_bbs: 100,
+        };
+        assert_eq!(mode.buy_in_range(20), Some((800, 2000)));
+        assert_eq!(GameMode::Tournament.buy_in_range(20), None);
+        assert!(validate_game_mode(&mode).is_ok());
+        assert!(
+            validate_game_mode(&GameMode::Cash {
+                min_buy_in_bbs: 100,
+                max_buy_in_bbs: 40,
+            })
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn game_mode_defaults_to_tournament() {
+        let msg: ClientMessage =
+            serde_json::from_str(r#"{"type":"CreateRoom","room_id":"abc"}"#).unwrap();
+        match msg {
+            ClientMessage::CreateRoom { game_mode, .. } => {
+                assert_eq!(game_mode, GameMode::Tournament)
+            }
+            _ => panic!("expected CreateRoom"),
+        }
+    }
+
+    #[test]
+    fn multi_table_validation() {
+        let freezeout = RebuyConfig::default();
+        assert!(validate_multi_table("mtt", 9, &GameMode::Tournament, &freezeout).is_ok());
+        assert!(validate_multi_table("mtt", 1, &GameMode::Tournament, &freezeout).is_err());
+        assert!(validate_multi_table("mtt", 11, &GameMode::Tournament, &freezeout).is_err());
+        assert!(
+            validate_multi_table("abcdefghijklmnop", 9, &GameMode::Tournament, &freezeout).is_err()
+        );
+        let rebuys = RebuyConfig {
+            levels: 2,
+            max_rebuys: 1,
+            add_on: false,
+        };
+        assert!(validate_multi_table("mtt", 9, &GameMode::Tournament, &rebuys).is_err());
+        let cash = GameMode::Cash {
+            min_buy_in_bbs: 40,
+            max_buy_in_bbs: 100,
+        };
+        assert!(validate_multi_table("mtt", 9, &cash, &freezeout).is_err());
+
+        let msg: ClientMessage =
+            serde_json::from_str(r#"{"type":"CreateRoom","room_id":"abc"}"#).unwrap();
+        match msg {
+            ClientMessage::CreateRoom {
+                multi_table,
+                table_size,
+                ..
+            } => {
+                assert!(!multi_table);
+                assert_eq!(table_size, 9);
+            }
+            _ => panic!("expected CreateRoom"),
+        }
+    }
+
+    #[test]
+    fn seats_default_for_older_peers() {
+        assert!(validate_table_size(2).is_ok());
+        assert!(validate_table_size(10).is_ok());
+        assert!(validate_table_size(0).is_err());
+
+        let msg: ClientMessage =
+            serde_json::from_str(r#"{"type":"JoinRoom","room_id":"abc","name":"bob"}"#).unwrap();
+        match msg {
+            ClientMessage::JoinRoom { seat, .. } => assert_eq!(seat, None),
+            _ => panic!("expected JoinRoom"),
+        }
+        let info: PlayerInfo =
+            serde_json::from_str(r#"{"id":1,"name":"bob","chips":100}"#).unwrap();
+        assert_eq!(info.seat, 0);
+        let msg: ServerMessage =
+            serde_json::from_str(r#"{"type":"RoomJoined","room_id":"abc"}"#).unwrap();
+        match msg {
+            ServerMessage::RoomJoined { table_size, .. } => assert_eq!(table_size, 9),
+            _ => panic!("expected RoomJoined"),
+        }
+    }
+
+    #[test]
+    fn room_access_validation() {
+        assert!(validate_room_password("").is_ok());
+        assert!(validate_room_password(&"x".repeat(MAX_PASSWORD_LEN)).is_ok());
+        assert!(validate_room_password(&"x".repeat(MAX_PASSWORD_LEN + 1)).is_err());
+        assert!(validate_invite(true, 0).is_ok());
+        assert!(validate_invite(false, 3600).is_ok());
+        assert!(validate_invite(true, MAX_INVITE_EXPIRY_SECS).is_ok());
+        assert!(validate_invite(false, 0).is_err());
+        assert!(validate_invite(true, MAX_INVITE_EXPIRY_SECS + 1).is_err());
+    }
+
+    #[test]
+    fn join_room_without_credentials() {
+        let json = r#"{"type":"JoinRoom","room_id":"abc","name":"Alice"}"#;
+        match serde_json::from_str::<ClientMessage>(json).unwrap() {
+            ClientMessage::JoinRoom {
+                password, invite, ..
+            } => {
+                assert!(password.is_empty());
+                assert!(invite.is_empty());
+            }
+            _ => panic!("expected JoinRoom"),
+        }
+    }
+
+    #[test]
+    fn room_summary_joinable() {
+        let lobby = RoomSummary {
+            room_id: "abc".to_string(),
+            players: 2,
+            table_size: 9,
+            spectators: 0,
+            small_blind: 10,
+            big_blind: 20,
+            blind_config: BlindConfig::default(),
+            game_mode: GameMode::Tournament,
+            multi_table: false,
+            started: false,
+            allow_late_entry: false,
+            password: false,
+        };
+        assert!(lobby.is_joinable());
+        assert_eq!(lobby.status(), "Lobby");
+        let full = RoomSummary {
+            players: 9,
+            ..lobby.clone()
+        };
+        assert!(!full.is_joinable());
+        assert_eq!(full.status(), "Full");
+        let running = RoomSummary {
+            started: true,
+            ..lobby.clone()
+        };
+        assert!(!running.is_joinable());
+        assert_eq!(running.status(), "In play");
+        let late_entry = RoomSummary {
+            allow_late_entry: true,
+            ..running.clone()
+        };
+        assert!(late_entry.is_joinable());
+        assert_eq!(late_entry.status(), "Open");
+        let cash = RoomSummary {
+            game_mode: GameMode::Cash {
+                min_buy_in_bbs: 40,
+                max_buy_in_bbs: 100,
+            },
+            ..running.clone()
+        };
+        assert!(cash.is_joinable());
+        assert_eq!(cash.variant(), "Cash 40-100 BB");
+        let mtt = RoomSummary {
+            multi_table: true,
+            players: 30,
+            ..lobby
+        };
+        assert!(mtt.is_joinable());
+        assert!(
+            !RoomSummary {
+                started: true,
+                allow_late_entry: true,
+                ..mtt
+            }
+            .is_joinable()
+        );
+    }
+
+    #[test]
+    fn blinds_validation() {
+        let rising = BlindConfig {
+            interval_secs: 600,
+            increase_percent: 50,
+        };
+        assert!(validate_blinds(10, 20, &rising, &GameMode::Tournament).is_ok());
+        assert!(validate_blinds(10, 10, &BlindConfig::default(), &GameMode::Tournament).is_ok());
+        assert!(validate_blinds(0, 20, &rising, &GameMode::Tournament).is_err());
+        assert!(validate_blinds(20, 10, &rising, &GameMode::Tournament).is_err());
+        let cash = GameMode::Cash {
+            min_buy_in_bbs: 40,
+            max_buy_in_bbs: 100,
+        };
+        assert!(validate_blinds(10, 20, &BlindConfig::default(), &cash).is_ok());
+        assert!(validate_blinds(10, 20, &rising, &cash).is_err());
+
+        let msg: ClientMessage =
+            serde_json::from_str(r#"{"type":"KickPlayer","player_id":3}"#).unwrap();
+        match msg {
+            ClientMessage::KickPlayer { player_id, ban } => {
+                assert_eq!(player_id, 3);
+                assert!(!ban);
+            }
+            _ => panic!("expected KickPlayer"),
+        }
+    }
+
+    #[test]
+    fn spectator_delay_validation() {
+        assert!(validate_spectator_delay(&SpectatorDelay::default()).is_ok());
+        let streamer = SpectatorDelay {
+            secs: 30,
+            hands: 0,
+            hole_cards: true,
+        };
+        assert!(streamer.is_enabled());
+        assert!(validate_spectator_delay(&streamer).is_ok());
+        let live_hole_cards = SpectatorDelay {
+            hole_cards: true,
+            ..SpectatorDelay::default()
+        };
+        assert!(validate_spectator_delay(&live_hole_cards).is_err());
+        let too_long = SpectatorDelay {
+            hands: MAX_SPECTATOR_DELAY_HANDS + 1,
+            ..SpectatorDelay::default()
+        };
+        assert!(validate_spectator_delay(&too_long).is_err());
+
+        let msg: ServerMessage =
+            serde_json::from_str(r#"{"type":"Spectating","room_id":"abc"}"#).unwrap();
+        match msg {
+            ServerMessage::Spectating { delay, .. } => assert!(!delay.is_enabled()),
+            _ => panic!("expected Spectating"),
+        }
+    }
+
+    #[test]
+    fn rebuy_config_validation() {
+        let rebuys = RebuyConfig {
+            levels: 3,
+            max_rebuys: 2,
+            add_on: true,
+        };
+        assert!(validate_rebuy_config(&rebuys, &GameMode::Tournament).is_ok());
+        assert!(validate_rebuy_config(&RebuyConfig::default(), &GameMode::Tournament).is_ok());
+
+        let cash = GameMode::Cash {
+            min_buy_in_bbs: 40,
+            max_buy_in_bbs: 100,
+        };
+        assert!(validate_rebuy_config(&rebuys, &cash).is_err());
+        assert!(validate_rebuy_config(&RebuyConfig::default(), &cash).is_ok());
+
+        let add_on_only = RebuyConfig {
+            add_on: true,
+            ..RebuyConfig::default()
+        };
+        assert!(validate_rebuy_config(&add_on_only, &GameMode::Tournament).is_err());
+    }
+
+    #[test]
+    fn payout_validation() {
+        assert!(validate_payouts(&[100]).is_ok());
+        assert!(validate_payouts(&[50, 30, 20]).is_ok());
+        assert!(validate_payouts(&[]).is_err());
+        assert!(validate_payouts(&[60, 30]).is_err());
+        assert!(validate_payouts(&[100, 0]).is_err());
+    }
+
+    #[test]
+    fn payouts_split_between_tied_places() {
+        let payouts = [50, 30, 20];
+        assert_eq!(payout_for_place(1000, &payouts, 1, 1), 500);
+        assert_eq!(payout_for_place(1000, &payouts, 3, 1), 200);
+        // Two players tied for 2nd share 2nd and 3rd prizes.
+        assert_eq!(payout_for_place(1000, &payouts, 2, 2), 250);
+        // A tie straddling the last paid place shares it with unpaid places.
+        assert_eq!(payout_for_place(1000, &payouts, 3, 2), 100);
+        assert_eq!(payout_for_place(1000, &payouts, 4, 1), 0);
+    }
+
+    #[test]
+    fn create_room_defaults_to_winner_take_all() {
+        let msg: ClientMessage =
+            serde_json::from_str(r#"{"type":"CreateRoom","room_id":"abc"}"#).unwrap();
+        match msg {
+            ClientMessage::CreateRoom { payouts, .. } => assert_eq!(payouts, vec![100]),
+            _ => panic!("expected CreateRoom"),
+        }
+    }
+
+    #[test]
+    fn hello_negotiates_shared_features() {
+        let capabilities = vec!["invites".to_string(), "telepathy".to_string()];
+        let features = negotiate(PROTOCOL_VERSION, ClientKind::Native, "", &capabilities);
+        assert_eq!(features, Ok(vec!["invites".to_string()]));
+    }
+
+    #[test]
+    fn hello_refuses_other_protocol_versions() {
+        assert!(negotiate(MIN_PROTOCOL_VERSION - 1, ClientKind::Native, "", &[]).is_err());
+        assert!(negotiate(PROTOCOL_VERSION + 1, ClientKind::Native, "", &[]).is_err());
+        let err = negotiate(0, ClientKind::Web, BUILD_ID, &[]).unwrap_err();
+        assert!(err.contains("Reload the page"));
+    }
+
+    #[test]
+    fn hello_sends_stale_web_clients_to_reload() {
+        assert!(negotiate(PROTOCOL_VERSION, ClientKind::Web, BUILD_ID, &[]).is_ok());
+        assert!(negotiate(PROTOCOL_VERSION, ClientKind::Web, "", &[]).is_ok());
+        assert!(negotiate(PROTOCOL_VERSION, ClientKind::Web, "stale", &[]).is_err());
+        // Only the web client is served by this server.
+        assert!(negotiate(PROTOCOL_VERSION, ClientKind::Native, "stale", &[]).is_ok());
+    }
+
+    #[test]
+    fn hello_defaults_unknown_client_kinds() {
+        let msg: ClientMessage =
+            serde_json::from_str(r#"{"type":"Hello","protocol_version":1,"client_kind":"fridge"}"#)
+                .unwrap();
+        match msg {
+            ClientMessage::Hello {
+                client_kind,
+                capabilities,
+                ..
+            } => {
+                assert_eq!(client_kind, ClientKind::Other);
+                assert!(capabilities.is_empty());
+            }
+            _ => panic!("expected Hello"),
+        }
+    }
+
+    #[test]
+    fn envelope_carries_seq_next_to_the_message() {
+        let env = Envelope {
+            seq: Some(7),
+            msg: ServerMessage::PlayerSatOut { player_id: 2 },
+        };
+        let json = serde_json::to_string(&env).unwrap();
+        assert_eq!(json, r#"{"seq":7,"type":"PlayerSatOut","player_id":2}"#);
+        // Clients that do not know about sequence numbers read the message.
+        let msg: ServerMessage = serde_json::from_str(&json).unwrap();
+        assert!(matches!(msg, ServerMessage::PlayerSatOut { player_id: 2 }));
+
+        let reply: Envelope = serde_json::from_str(r#"{"type":"Pong"}"#).unwrap();
+        assert_eq!(reply.seq, None);
+        assert!(matches!(reply.msg, ServerMessage::Pong));
+        assert_eq!(
+            serde_json::to_string(&Envelope::from(ServerMessage::Pong)).unwrap(),
+            r#"{"type":"Pong"}"#
+        );
+    }
+
+    #[test]
+    fn errors_carry_a_code_and_details() {
+        let msg = ServerMessage::error(ProtocolError::InvalidAmount { min: 40, max: 960 });
+        let json = serde_json::to_string(&msg).unwrap();
+        assert_eq!(
+            json,
+            r#"{"type":"Error","message":"The amount must be between 40 and 960","error":{"code":"invalid_amount","min":40,"max":960}}"#
+        );
+        let env: Envelope = serde_json::from_str(&json).unwrap();
+        match env.msg {
+            ServerMessage::Error { error, .. } => {
+                assert_eq!(error, ProtocolError::InvalidAmount { min: 40, max: 960 })
+            }
+            _ => panic!("expected Error"),
+        }
+        assert_eq!(
+            ProtocolError::SeatTaken { seat: 2 }.to_string(),
+            "Seat 3 is taken"
+        );
+    }
+
+    #[test]
+    fn unknown_and_missing_error_codes() {
+        let newer: ServerMessage = serde_json::from_str(
+            r#"{"type":"RoomError","message":"Try later","error":{"code":"rate_limited","secs":5}}"#,
+        )
+        .unwrap();
+        let older: ServerMessage =
+            serde_json::from_str(r#"{"type":"Error","message":"Not your turn"}"#).unwrap();
+        for msg in [newer, older] {
+            match msg {
+                ServerMessage::Error { error, .. } | ServerMessage::RoomError { error, .. } => {
+                    assert_eq!(error, ProtocolError::Unknown)
+                }
+                _ => panic!("expected an error"),
+            }
+        }
+    }
+
+    #[test]
+    fn actions_carry_an_optional_request_id() {
+        let mut msg: ClientMessage = serde_json::from_str(r#"{"type":"Fold"}"#).unwrap();
+        assert_eq!(msg.action(), Some((PlayerAction::Fold, 0)));
+        assert_eq!(msg.request_id(), None);
+        msg.set_request_id(3);
+        assert_eq!(
+            serde_json::to_string(&msg).unwrap(),
+            r#"{"type":"Fold","request_id":3}"#
+        );
+
+        let raise: ClientMessage =
+            serde_json::from_str(r#"{"type":"Raise","amount":80,"request_id":4}"#).unwrap();
+        assert_eq!(raise.action(), Some((PlayerAction::Raise, 80)));
+        assert_eq!(raise.request_id(), Some(4));
+
+        let rejected = ServerMessage::action_answer(4, Err(ProtocolError::NotYourTurn));
+        assert_eq!(
+            serde_json::to_string(&rejected).unwrap(),
+            r#"{"type":"ActionRejected","request_id":4,"message":"Not your turn","error":{"code":"not_your_turn"}}"#
+        );
+    }
+
+    #[test]
+    fn binary_encodings_round_trip() {
+        let samples = [
+            r#"{"seq":12,"type":"Showdown","hands":[[1,[{"rank":14,"suit":3},{"rank":13,"suit":3}],"Flush"]]}"#,
+            r#"{"type":"AllInShowdown","hands":[[2,[{"rank":2,"suit":0},{"rank":2,"suit":1}],48.5]],"community_cards":[]}"#,
+            r#"{"type":"ActionRejected","request_id":4,"message":"Not your turn","error":{"code":"not_your_turn"}}"#,
+            r#"{"seq":3,"type":"YourTurn","current_bet":40,"your_bet":20,"pot":60,"min_raise":40,"valid_actions":["fold","call","raise","allin"]}"#,
+        ];
+        for encoding in [Encoding::MessagePack, Encoding::Cbor] {
+            for json in samples {
+                let env: Envelope = serde_json::from_str(json).unwrap();
+                let frame = encoding.encode(&env).unwrap();
+                let Frame::Binary(ref bytes) = frame else {
+                    panic!("expected a binary frame");
+                };
+                assert!(bytes.len() < json.len());
+                let back: Envelope = encoding.decode(&frame).unwrap();
+                assert_eq!(serde_json::to_string(&back).unwrap(), json);
+            }
+            let raise = ClientMessage::Raise {
+                amount: 120,
+                request_id: Some(9),
+            };
+            let back: ClientMessage = encoding.decode(&encoding.encode(&raise).unwrap()).unwrap();
+            assert_eq!(back.action(), Some((PlayerAction::Raise, 120)));
+            assert_eq!(back.request_id(), Some(9));
+        }
+    }
+
+    #[test]
+    fn json_is_the_default_encoding() {
+        let msg: ClientMessage =
+            serde_json::from_str(r#"{"type":"Hello","protocol_version":1,"encoding":"zstd"}"#)
+                .unwrap();
+        assert!(matches!(
+            msg,
+            ClientMessage::Hello {
+                encoding: Encoding::Json,
+                ..
+            }
+        ));
+        let frame = Encoding::Json.encode(&ServerMessage::Pong).unwrap();
+        assert_eq!(frame, Frame::Text(r#"{"type":"Pong"}"#.to_string()));
+        // Text frames are JSON whatever was agreed.
+        let pong: ServerMessage = Encoding::Cbor.decode(&frame).unwrap();
+        assert!(matches!(pong, ServerMessage::Pong));
+        let binary = Frame::Binary(vec![0x80]);
+        assert!(Encoding::Json.decode::<ServerMessage>(&binary).is_err());
+    }
 }
@@ -0,0 +1,252 @@
+//! Machine-readable description of the wire protocol for clients and bots
+//! written in other languages: a JSON Schema for each direction and
+//! TypeScript type definitions, generated from the types in
+//! [`protocol`](crate::protocol).
+//!
+//! Only built with the `schema` feature.  The generated files are kept in
+//! the repository's `protocol/` directory.  Regenerate them after changing a
+//! message:
+//!
+//! ```text
+//! cargo run -p poker-core --features schema --bin protocol-schema
+//! ```
+
+use std::any::TypeId;
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+
+use schemars::generate::SchemaSettings;
+use serde_json::Value;
+use ts_rs::{TS, TypeVisitor};
+
+use crate::protocol::{ClientMessage, Envelope, ServerMessage};
+
+/// Name of the JSON Schema for messages sent by clients.
+pub const CLIENT_SCHEMA_FILE: &str = "client_message.schema.json";
+/// Name of the JSON Schema for messages sent by the server.
+pub const SERVER_SCHEMA_FILE: &str = "server_message.schema.json";
+/// Name of the TypeScript definitions of both.
+pub const TYPESCRIPT_FILE: &str = "protocol.ts";
+
+/// JSON Schema for a `ClientMessage`, as the server reads it: fields with a
+/// default may be left out.
+pub fn client_schema() -> Value {
+    let schema = SchemaSettings::draft2020_12()
+        .for_deserialize()
+        .into_generator()
+        .into_root_schema_for::<ClientMessage>();
+    schema.to_value()
+}
+
+/// JSON Schema for a server message with its sequence number (an
+/// [`Envelope`]), as the server writes it.
+pub fn server_schema() -> Value {
+    let mut schema = SchemaSettings::draft2020_12()
+        .for_serialize()
+        .into_generator()
+        .into_root_schema_for::<Envelope>();
+    schema.insert("title".to_string(), "ServerMessage".into());
+    schema.to_value()
+}
+
+/// TypeScript definitions of `ClientMessage`, `ServerMessage`, `Envelope`
+/// and every type they use, in one module.
+pub fn typescript() -> String {
+    let mut decls = Declarations::default();
+    decls.visit::<ClientMessage>();
+    decls.visit::<ServerMessage>();
+    decls.visit::<Envelope>();
+    let mut out = String::from("// Generated from poker-core by `protocol-schema`. Do not edit.\n");
+    for decl in decls.out.values() {
+        out.push('\n');
+        out.push_str(decl);
+        out.push('\n');
+    }
+    out
+}
+
+/// The generated files, by name.
+pub fn files() -> Vec<(&'static str, String)> {
+    let json = |schema: Value| {
+        let mut text = serde_json::to_string_pretty(&schema).expect("schemas serialize");
+        text.push('\n');
+        text
+    };
+    vec![
+        (CLIENT_SCHEMA_FILE, json(client_schema())),
+        (SERVER_SCHEMA_FILE, json(server_schema())),
+        (TYPESCRIPT_FILE, typescript()),
+    ]
+}
+
+/// Write the generated files into `dir`.
+pub fn write_files(dir: &Path) -> std::io::Result<()> {
+    std::fs::create_dir_all(dir)?;
+    for (name, contents) in files() {
+        std::fs::write(dir.join(name), contents)?;
+    }
+    Ok(())
+}
+
+/// Collects the declaration of each type once, by name so that the output
+/// does not depend on the order the types are visited in.
+#[derive(Default)]
+struct Declarations {
+    seen: HashSet<TypeId>,
+    out: BTreeMap<String, String>,
+}
+
+impl TypeVisitor for Declarations {
+    fn visit<T: TS + 'static + ?Sized>(&mut self) {
+        // Only named types have a declaration of their own.
+        if T::output_path().is_none() || !self.seen.insert(TypeId::of::<T>()) {
+            return;
+        }
+        let docs = T::docs().unwrap_or_default();
+        self.out
+            .insert(T::ident(), format!("{docs}export {}", T::decl()));
+        T::visit_dependencies(self);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::{
+        BlindConfig, ClientKind, Encoding, GameMode, PlayerAction, ProtocolError, RebuyConfig,
+        SpectatorDelay,
+    };
+
+    fn client_validator() -> jsonschema::Validator {
+        jsonschema::validator_for(&client_schema()).unwrap()
+    }
+
+    fn server_validator() -> jsonschema::Validator {
+        jsonschema::validator_for(&server_schema()).unwrap()
+    }
+
+    #[test]
+    fn generated_files_are_up_to_date() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../protocol");
+        for (name, contents) in files() {
+            let committed = std::fs::read_to_string(dir.join(name)).unwrap_or_default();
+            assert!(
+                committed == contents,
+                "protocol/{name} is out of date; run `cargo run -p poker-core --features schema --bin protocol-schema`"
+            );
+        }
+    }
+
+    #[test]
+    fn client_messages_match_the_schema() {
+        let validator = client_validator();
+        let messages = [
+            ClientMessage::Hello {
+                protocol_version: 1,
+                client_kind: ClientKind::Native,
+                capabilities: vec!["replay".to_string()],
+                build: "0.1.0".to_string(),
+                encoding: Encoding::MessagePack,
+            },
+            ClientMessage::CreateRoom {
+                room_id: "friday".to_string(),
+                blind_config: BlindConfig::default(),
+                starting_bbs: 50,
+                game_mode: GameMode::Tournament,
+                rebuy_config: RebuyConfig::default(),
+                payouts: vec![60, 40],
+                multi_table: false,
+                table_size: 6,
+                spectator_delay: SpectatorDelay::default(),
+                password: String::new(),
+                unlisted: true,
+            },
+            ClientMessage::Raise {
+                amount: 80,
+                request_id: Some(3),
+            },
+            ClientMessage::Fold { request_id: None },
+            ClientMessage::Ping,
+        ];
+        for msg in messages {
+            let json = serde_json::to_value(&msg).unwrap();
+            assert!(
+                validator.is_valid(&json),
+                "{json} does not match the schema"
+            );
+            let back: ClientMessage = serde_json::from_value(json.clone()).unwrap();
+            assert_eq!(serde_json::to_value(&back).unwrap(), json);
+        }
+        // Fields with a default may be left out, as older clients do.
+        assert!(validator.is_valid(&serde_json::json!({"type": "Fold"})));
+        assert!(validator.is_valid(&serde_json::json!({
+            "type": "JoinRoom",
+            "room_id": "friday",
+            "name": "Ann"
+        })));
+        assert!(!validator.is_valid(&serde_json::json!({"type": "Raise"})));
+        assert!(!validator.is_valid(&serde_json::json!({"type": "Shuffle"})));
+    }
+
+    #[test]
+    fn server_messages_match_the_schema() {
+        let validator = server_validator();
+        let samples = [
+            r#"{"seq":12,"type":"Showdown","hands":[[1,[{"rank":14,"suit":3},{"rank":13,"suit":3}],"Flush"]]}"#,
+            r#"{"seq":3,"type":"YourTurn","current_bet":40,"your_bet":20,"pot":60,"min_raise":40,"valid_actions":["fold","call","raise","allin"]}"#,
+            r#"{"type":"ServerHello","protocol_version":1,"build":"0.1.0","features":["replay"],"encoding":"json"}"#,
+            r#"{"type":"Pong"}"#,
+        ];
+        for json in samples {
+            let env: Envelope = serde_json::from_str(json).unwrap();
+            let value = serde_json::to_value(&env).unwrap();
+            assert!(
+                validator.is_valid(&value),
+                "{json} does not match the schema"
+            );
+            assert_eq!(value, serde_json::from_str::<Value>(json).unwrap());
+        }
+        let messages = [
+            ServerMessage::action_answer(
+                4,
+                Err(ProtocolError::InvalidAmount { min: 40, max: 960 }),
+            ),
+            ServerMessage::action_answer(5, Ok(())),
+            ServerMessage::room_error(ProtocolError::RoomNotFound {
+                room_id: "friday".to_string(),
+            }),
+            ServerMessage::PlayerActed {
+                player_id: 2,
+                action: PlayerAction::AllIn,
+                amount: Some(500),
+            },
+        ];
+        for msg in messages {
+            let value = serde_json::to_value(Envelope { seq: Some(1), msg }).unwrap();
+            assert!(
+                validator.is_valid(&value),
+                "{value} does not match the schema"
+            );
+        }
+        assert!(!validator.is_valid(&serde_json::json!({"type": "ActionAck"})));
+    }
+
+    #[test]
+    fn typescript_declares_every_message() {
+        let ts = typescript();
+        for name in [
+            "ClientMessage",
+            "Envelope",
+            "ServerMessage",
+            "ProtocolError",
+            "CardInfo",
+        ] {
+            assert!(
+                ts.contains(&format!("export type {name} =")),
+                "{name} is not declared"
+            );
+        }
+        assert!(ts.contains(r#""type": "Raise""#));
+        assert!(!ts.contains("bigint"));
+    }
+}
@@ -0,0 +1,2025 @@
+//! Room tasks.
+//!
+//! Every room runs as a single task, the [`RoomActor`], which owns the
+//! room's [`Room`] and [`GameState`] outright.  Connections, the admin API
+//! and the other tables of a tournament never touch that state: they send
+//! [`Command`]s through a [`RoomHandle`] and the task handles them one at a
+//! time, so nothing needs a lock.
+//!
+//! Delays are [`Timer`]s scheduled on the task rather than sleeps: the pause
+//! between hands, the streets of an all-in run-out, turn timers and the
+//! grace period of disconnected players.  While one is pending the room
+//! keeps answering chat, pings and everything else.
+//!
+//! A room also removes itself once it is abandoned: when nobody has joined
+//! it within `unjoined_room_timeout_secs`, or when none of its players has
+//! been connected for `idle_room_timeout_secs`.
+
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+use poker_core::poker::{Hand, calculate_equity_multi};
+use poker_core::protocol::{
+    CardInfo, ClientMessage, PlayerAction, ProtocolError, ServerMessage, card_to_info,
+    validate_blinds, validate_invite,
+};
+use tokio::sync::{mpsc, oneshot};
+
+use crate::game_logic::{GamePhase, GameState, PlayerStatus};
+use crate::metrics::METRICS;
+use crate::room::{Admission, PlayerRx, RelocationTx, Room, RoomMap, generate_session_token};
+use crate::spectator::{Spectator, SpectatorFeed};
+use crate::tournament::{self, BetweenHands, Mover, TableLink};
+
+/// How long to wait before acting for a sitting-out player, so clients see
+/// the turn come round before the action.
+const AUTO_ACT_DELAY: Duration = Duration::from_millis(100);
+
+/// Sends commands to a room's task.
+#[derive(Clone)]
+pub struct RoomHandle {
+    tx: mpsc::UnboundedSender<Command>,
+}
+
+/// A [`RoomHandle`] that does not keep the room's task running.
+#[derive(Clone)]
+pub struct WeakRoomHandle {
+    tx: mpsc::WeakUnboundedSender<Command>,
+}
+
+impl RoomHandle {
+    /// Queue a command.  Returns `false` if the room's task has ended.
+    pub fn send(&self, command: Command) -> bool {
+        self.tx.send(command).is_ok()
+    }
+
+    /// Run `f` on the room's task without waiting for it.
+    pub fn run(&self, f: impl FnOnce(&mut RoomActor) + Send + 'static) {
+        self.send(Command::Run(Box::new(f)));
+    }
+
+    /// Run `f` on the room's task and wait for its result.  `None` if the
+    /// task has ended.
+    pub async fn call<T: Send + 'static>(
+        &self,
+        f: impl FnOnce(&mut RoomActor) -> T + Send + 'static,
+    ) -> Option<T> {
+        let (tx, rx) = oneshot::channel();
+        self.run(move |actor| {
+            let _ = tx.send(f(actor));
+        });
+        rx.await.ok()
+    }
+
+    pub fn downgrade(&self) -> WeakRoomHandle {
+        WeakRoomHandle {
+            tx: self.tx.downgrade(),
+        }
+    }
+}
+
+impl WeakRoomHandle {
+    pub fn upgrade(&self) -> Option<RoomHandle> {
+        self.tx.upgrade().map(|tx| RoomHandle { tx })
+    }
+}
+
+/// Something for a room's task to handle.
+pub enum Command {
+    /// A message from a seated player's connection.
+    Client { player_id: u32, msg: ClientMessage },
+    /// A message from a spectator's connection.
+    Spectator {
+        spectator_id: u32,
+        msg: ClientMessage,
+    },
+    /// A player's connection closed.
+    Disconnect { player_id: u32 },
+    /// A spectator's connection closed.
+    StopSpectating { spectator_id: u32 },
+    /// Players a multi-table tournament moved here from the table `from`.
+    SeatMovers {
+        movers: Vec<Mover>,
+        from: WeakRoomHandle,
+    },
+    /// Players who moved away have been seated at their new table.
+    ForgetMovers { player_ids: Vec<u32> },
+    /// Messages for everyone in the room, e.g. tournament results.
+    Broadcast(Vec<ServerMessage>),
+    /// Anything else, run against the room's state.
+    Run(Box<dyn FnOnce(&mut RoomActor) + Send>),
+}
+
+/// A delayed event.  Scheduling a timer replaces the same one if it is
+/// still pending.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Timer {
+    /// The pause after `hand` is over: deal the next one.
+    NextHand { hand: u32 },
+    /// Deal the next street of `hand`, which is being run out all-in.
+    RunOut { hand: u32 },
+    /// The player to act in `turn` ran out of time.
+    TurnTimeout { turn: u64, player_id: u32 },
+    /// Act for a player who is sitting out in `turn`.
+    AutoAct {
+        turn: u64,
+        player_id: u32,
+        action: PlayerAction,
+    },
+    /// A disconnected player's seat is no longer held.
+    GraceExpired { player_id: u32 },
+    /// The room has been without connected players for its idle timeout.
+    Idle,
+}
+
+/// The task that runs one room.
+pub struct RoomActor {
+    pub room_id: String,
+    pub room: Room,
+    pub gs: GameState,
+    /// Every room, so an empty one can remove itself.
+    rooms: RoomMap,
+    /// Pending timers in the order they are due.  The sequence number keeps
+    /// timers due at the same instant apart.
+    timers: BTreeMap<(Instant, u64), Timer>,
+    next_timer: u64,
+    /// This room's own handle, for the tables it sends players to.
+    handle: WeakRoomHandle,
+    rx: mpsc::UnboundedReceiver<Command>,
+    /// When the last connected player left, or the room was created if
+    /// nobody has joined yet.
+    idle_since: Option<Instant>,
+    /// Set once the room is closed; the task ends.
+    stopped: bool,
+}
+
+impl RoomActor {
+    /// Set up the task of a room.  Commands sent through the returned
+    /// handle wait until [`spawn`](Self::spawn) starts it.
+    pub fn new(room_id: String, room: Room, gs: GameState, rooms: RoomMap) -> (Self, RoomHandle) {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let handle = RoomHandle { tx };
+        let actor = Self {
+            room_id,
+            room,
+            gs,
+            rooms,
+            timers: BTreeMap::new(),
+            next_timer: 0,
+            handle: handle.downgrade(),
+            rx,
+            idle_since: None,
+            stopped: false,
+        };
+        (actor, handle)
+    }
+
+    /// Run the room in the background.  The task ends when the room is
+    /// closed or nothing holds a handle to it any more.
+    pub fn spawn(self) {
+        tokio::spawn(self.run());
+    }
+
+    async fn run(mut self) {
+        while !self.stopped {
+            let next = self.timers.keys().next().map(|&(at, _)| at);
+            tokio::select! {
+                command = self.rx.recv() => match command {
+                    Some(command) => self.handle(command),
+                    None => break,
+                },
+                () = sleep_until(next) => self.fire_due_timers(),
+            }
+            self.watch_idle();
+        }
+        tracing::debug!(room = %self.room_id, "Room task ended");
+    }
+
+    fn handle(&mut self, command: Command) {
+        match command {
+            Command::Client { player_id, msg } => self.client_message(msg, player_id),
+            Command::Spectator { spectator_id, msg } => {
+                self.spectator_message(msg, spectator_id);
+            }
+            Command::Disconnect { player_id } => self.disconnect_player(player_id),
+            Command::StopSpectating { spectator_id } => self.stop_spectating(spectator_id),
+            Command::SeatMovers { movers, from } => self.move_in(movers, &from),
+            Command::ForgetMovers { player_ids } => {
+                tournament::forget_movers(&mut self.room, &player_ids);
+            }
+            Command::Broadcast(messages) => {
+                for m in &messages {
+                    self.room.broadcast(m);
+                }
+            }
+            Command::Run(f) => f(self),
+        }
+    }
+
+    // ─── Timers ──────────────────────────────────────────────────────────
+
+    /// Fire `timer` once `delay` has passed.
+    fn schedule(&mut self, delay: Duration, timer: Timer) {
+        self.timers.retain(|_, pending| *pending != timer);
+        self.timers
+            .insert((Instant::now() + delay, self.next_timer), timer);
+        self.next_timer += 1;
+    }
+
+    fn fire_due_timers(&mut self) {
+        let now = Instant::now();
+        while let Some(entry) = self.timers.first_entry() {
+            if entry.key().0 > now {
+                break;
+            }
+            let timer = entry.remove();
+            self.fire(timer);
+        }
+    }
+
+    fn fire(&mut self, timer: Timer) {
+        match timer {
+            Timer::NextHand { hand } => self.deal_next_hand(hand),
+            Timer::RunOut { hand } => self.run_out_street(hand),
+            Timer::TurnTimeout { turn, player_id } => self.force_timeout_action(turn, player_id),
+            Timer::AutoAct {
+                turn,
+                player_id,
+                action,
+            } => {
+                if self.room.turn_counter == turn {
+                    tracing::info!(
+                        player = player_id,
+                        ?action,
+                        "Sitting-out player, auto-acting"
+                    );
+                    let _ = self.process_action(player_id, action, 0);
+                }
+            }
+            Timer::GraceExpired { player_id } => self.grace_expired(player_id),
+            Timer::Idle => self.idle_expired(),
+        }
+    }
+
+    /// Start the idle timeout when the last connected player leaves, and
+    /// forget it when one is back.
+    fn watch_idle(&mut self) {
+        if self.stopped || !self.room.player_senders.is_empty() {
+            self.idle_since = None;
+            return;
+        }
+        if self.idle_since.is_some() {
+            return;
+        }
+        self.idle_since = Some(Instant::now());
+        let timing = &self.room.config.timing;
+        let timeout = if self.never_joined() {
+            timing.unjoined_room_timeout()
+        } else {
+            timing.idle_room_timeout()
+        };
+        self.schedule(timeout, Timer::Idle);
+    }
+
+    /// Player IDs start at 1 and are never reused, so a room still handing
+    /// out 1 has never had a player.
+    fn never_joined(&self) -> bool {
+        self.gs.next_player_id == 1
+    }
+
+    /// Close the room if it is still without connected players.  Tables of
+    /// a multi-table tournament in play are left alone: their players are
+    /// blinded out and the tournament ends on its own.
+    fn idle_expired(&mut self) {
+        if self.idle_since.is_none() {
+            return;
+        }
+        if let Some(link) = &self.room.tournament
+            && link.lock().in_play()
+        {
+            return;
+        }
+        let reason = if self.never_joined() {
+            "nobody joined"
+        } else {
+            "no players connected"
+        };
+        self.shut_down("This room was closed because it was left empty");
+        self.log_removed(reason);
+    }
+
+    /// Log why the room was removed.
+    fn log_removed(&self, reason: &str) {
+        tracing::info!(room = %self.room_id, reason, "Room removed");
+    }
+
+    // ─── Joining and leaving ─────────────────────────────────────────────
+
+    /// Add a player, in the requested seat or a random empty one, and send
+    /// them the state of the room.  Entrants of a multi-table tournament
+    /// are seated when it starts.
+    ///
+    /// Returns the player ID and the receiver for their messages.
+    pub fn join(
+        &mut self,
+        player_name: &str,
+        seat: Option<u32>,
+        admission: &Admission,
+        relocator: RelocationTx,
+    ) -> Result<(u32, PlayerRx), ProtocolError> {
+        let (room, gs) = (&mut self.room, &mut self.gs);
+        if room.closing {
+            return Err(ProtocolError::ShuttingDown);
+        }
+        if room.is_banned(player_name) {
+            return Err(ProtocolError::Banned);
+        }
+        room.check_admission(admission)?;
+
+        if gs.game_started && room.tournament.is_some() {
+            return Err(ProtocolError::GameInProgress);
+        }
+        // Cash games can always be joined.
+        if gs.game_started && !gs.allow_late_entry && !gs.game_mode.is_cash() {
+            return Err(ProtocolError::GameInProgress);
+        }
+        let seat = if room.tournament.is_some() {
+            gs.players.values().map(|p| p.seat + 1).max().unwrap_or(0)
+        } else {
+            gs.pick_seat(seat)?
+        };
+        let player = if gs.game_started {
+            // Late entry: give the frozen starting chip amount.
+            let chips = gs.starting_chips;
+            let p = gs.add_player_with_chips(player_name.to_string(), seat, Some(chips));
+            // Late-joiners sit out until the next hand.
+            gs.set_sitting_out(p.id);
+            p
+        } else {
+            gs.add_player(player_name.to_string(), seat)
+        };
+        // First player to join becomes the host.
+        if gs.host_id == 0 {
+            gs.host_id = player.id;
+        }
+        let (player_id, seat, player_count) = (player.id, player.seat, gs.player_count());
+
+        room.use_invite(admission);
+        let session_token = generate_session_token();
+        room.register_session(player_id, session_token.clone());
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        room.player_senders.insert(player_id, tx);
+        room.relocators.insert(player_id, relocator);
+
+        // Notify existing players about the new player.
+        room.broadcast_except(
+            &ServerMessage::PlayerJoined {
+                player_id,
+                name: player_name.to_string(),
+                seat,
+            },
+            player_id,
+        );
+        self.welcome_player(player_id, session_token, player_count);
+        Ok((player_id, rx))
+    }
+
+    /// Send a newly seated player the room settings, their seat and chips,
+    /// the player list and, if the game is running, the current hand.
+    fn welcome_player(&self, player_id: u32, session_token: String, player_count: usize) {
+        let (room, gs) = (&self.room, &self.gs);
+        let send = |msg: ServerMessage| room.send_to_player(player_id, &msg);
+        send(ServerMessage::RoomJoined {
+            room_id: self.room_id.clone(),
+            blind_config: gs.blind_config,
+            game_mode: gs.game_mode,
+            rebuy_config: gs.rebuy_config,
+            table_size: gs.table_size,
+        });
+        send(ServerMessage::JoinedGame {
+            player_id,
+            chips: gs.players.get(&player_id).map(|p| p.chips).unwrap_or(0),
+            player_count,
+            session_token,
+            is_host: gs.host_id == player_id,
+            allow_late_entry: gs.allow_late_entry,
+        });
+
+        // The full player list so the newcomer sees existing participants.
+        send(ServerMessage::PlayerList {
+            players: gs.player_infos(),
+        });
+        send(room.spectator_status());
+
+        if !gs.game_started {
+            return;
+        }
+
+        // Late join: GameStarted so the client knows the game is running,
+        // then the current hand.
+        send(ServerMessage::GameStarted);
+        if gs.hand_number > 0 {
+            let n = gs.player_order.len();
+            let (dealer_id, sb_id, bb_id) = if n >= 2 {
+                let d = gs.player_order[gs.dealer_index % n];
+                let sb = gs.player_order[(gs.dealer_index + 1) % n];
+                let bb = gs.player_order[(gs.dealer_index + 2) % n];
+                (d, sb, bb)
+            } else {
+                (0, 0, 0)
+            };
+            send(ServerMessage::NewHand {
+                hand_number: gs.hand_number,
+                dealer_id,
+                small_blind_id: sb_id,
+                big_blind_id: bb_id,
+                small_blind: gs.small_blind,
+                big_blind: gs.big_blind,
+            });
+        }
+        if !gs.community_cards.is_empty() {
+            let stage = match gs.phase {
+                GamePhase::Flop => "flop",
+                GamePhase::Turn => "turn",
+                GamePhase::River => "river",
+                _ => "flop",
+            };
+            send(ServerMessage::CommunityCards {
+                stage: stage.to_string(),
+                cards: gs.community_cards.iter().map(card_to_info).collect(),
+            });
+        }
+        send(ServerMessage::PotUpdate { pot: gs.pot });
+        for p in gs.players.values() {
+            if p.sitting_out {
+                send(ServerMessage::PlayerSatOut { player_id: p.id });
+            }
+        }
+        if gs.paused {
+            send(ServerMessage::GamePaused);
+        }
+    }
+
+    /// Start watching the room as a spectator.
+    ///
+    /// `Spectating` is sent at once; a snapshot of the table (and the hole
+    /// cards, in a streamer view) is queued behind the room's spectator
+    /// delay so it lines up with the events that follow it.  Returns the
+    /// spectator ID and the receiver for public room events.
+    pub fn spectate(
+        &mut self,
+        name: &str,
+        admission: &Admission,
+    ) -> Result<(u32, PlayerRx), ProtocolError> {
+        let room = &mut self.room;
+        if room.is_banned(name) {
+            return Err(ProtocolError::Banned);
+        }
+        room.check_admission(admission)?;
+        let max_spectators = room.config.limits.max_spectators;
+        if max_spectators > 0 && room.spectators.len() >= max_spectators {
+            return Err(ProtocolError::TooManySpectators {
+                max: max_spectators as u32,
+            });
+        }
+        room.use_invite(admission);
+        let spectator_id = room.next_spectator_id;
+        room.next_spectator_id += 1;
+        let (tx, rx) = mpsc::unbounded_channel();
+        let _ = tx.send(
+            ServerMessage::Spectating {
+                room_id: self.room_id.clone(),
+                delay: room.spectator_delay,
+            }
+            .into(),
+        );
+        let feed = SpectatorFeed::spawn(
+            tx.clone(),
+            room.spectator_delay,
+            room.spectator_hand.subscribe(),
+        );
+        feed.send(room.build_rejoin_snapshot(&self.gs, &self.room_id, 0, ""));
+        if let Some(link) = &room.tournament {
+            feed.send(link.lock().progress());
+        }
+        let hands = self.gs.dealt_hole_cards();
+        if room.spectator_delay.hole_cards && !hands.is_empty() {
+            feed.send(ServerMessage::SpectatorHoleCards { hands });
+        }
+        room.spectators.insert(
+            spectator_id,
+            Spectator {
+                name: name.to_string(),
+                tx,
+                feed,
+            },
+        );
+        room.broadcast(&room.spectator_status());
+        Ok((spectator_id, rx))
+    }
+
+    /// Seat a spectator, on the same terms as [`join`](Self::join).
+    pub fn take_seat(
+        &mut self,
+        spectator_id: u32,
+        seat: Option<u32>,
+        relocator: RelocationTx,
+    ) -> Result<(u32, PlayerRx), ProtocolError> {
+        let name = self
+            .room
+            .spectators
+            .get(&spectator_id)
+            .map(|s| s.name.clone())
+            .ok_or_else(|| ProtocolError::not_allowed("You are not watching this room"))?;
+        if name.trim().is_empty() {
+            return Err(ProtocolError::invalid("Choose a name to take a seat"));
+        }
+        let joined = self.join(&name, seat, &Admission::Admitted, relocator)?;
+        self.stop_spectating(spectator_id);
+        Ok(joined)
+    }
+
+    /// Stop sending room events to a spectator.
+    fn stop_spectating(&mut self, spectator_id: u32) {
+        if self.room.spectators.remove(&spectator_id).is_some() {
+            self.room.broadcast(&self.room.spectator_status());
+        }
+    }
+
+    /// Reconnect a disconnected player with their session token.  With the
+    /// number of the last event they saw (`last_seq`, 0 for none) they are
+    /// sent the events they missed, if the room still has them all, and a
+    /// full snapshot of the room otherwise.
+    ///
+    /// Returns the player ID and a new receiver for their messages.
+    pub fn rejoin(
+        &mut self,
+        session_token: &str,
+        last_seq: u64,
+        relocator: RelocationTx,
+    ) -> Result<(u32, PlayerRx), ProtocolError> {
+        let room = &mut self.room;
+        if room.banned_sessions.contains(session_token) {
+            return Err(ProtocolError::Banned);
+        }
+        let player_id = *room
+            .sessions
+            .get(session_token)
+            .ok_or(ProtocolError::SessionExpired)?;
+
+        if !self.gs.players.contains_key(&player_id) {
+            // Token was valid but player was already fully removed.
+            room.sessions.remove(session_token);
+            room.player_sessions.remove(&player_id);
+            return Err(ProtocolError::SessionExpired);
+        }
+
+        // Clearing the disconnected-at timestamp cancels the grace period.
+        room.disconnected_at.remove(&player_id);
+
+        // Replace the sender channel.
+        let (tx, rx) = mpsc::unbounded_channel();
+        room.player_senders.insert(player_id, tx);
+        room.relocators.insert(player_id, relocator);
+
+        if last_seq > 0 && self.resume(player_id, last_seq) {
+            return Ok((player_id, rx));
+        }
+        // A client starting over from a snapshot numbers its actions afresh.
+        self.room.requests.forget(player_id);
+        self.send_snapshot(player_id, session_token);
+        Ok((player_id, rx))
+    }
+
+    /// Replay the events a rejoining player missed since `last_seq`.
+    /// Returns `false`, sending nothing, if some are no longer kept.
+    fn resume(&self, player_id: u32, last_seq: u64) -> bool {
+        let room = &self.room;
+        let Some(missed) = room.replay.borrow().since(last_seq, player_id) else {
+            return false;
+        };
+        room.send_to_player(
+            player_id,
+            &ServerMessage::Resumed {
+                room_id: self.room_id.clone(),
+                player_id,
+                replayed: missed.len() as u32,
+            },
+        );
+        if let Some(tx) = room.player_senders.get(&player_id) {
+            for envelope in missed {
+                let _ = tx.send(envelope);
+            }
+        }
+        tracing::debug!(room = %self.room_id, player = player_id, last_seq, "Replayed missed events");
+        true
+    }
+
+    /// Send a full snapshot of the room as seen by `player_id`, tournament
+    /// progress and the spectator count, and `YourTurn` if they are to act.
+    fn send_snapshot(&self, player_id: u32, session_token: &str) {
+        let room = &self.room;
+        let snapshot =
+            room.build_rejoin_snapshot(&self.gs, &self.room_id, player_id, session_token);
+        room.send_to_player(player_id, &snapshot);
+        if let Some(link) = &room.tournament {
+            room.send_to_player(player_id, &link.lock().progress());
+        }
+        room.send_to_player(player_id, &room.spectator_status());
+        if !self.gs.paused && self.gs.player_to_act() == Some(player_id) {
+            send_turn_notification(&self.gs, room);
+        }
+    }
+
+    /// Soft-disconnect a player during a game: mark them as sitting out and
+    /// hold their seat for the grace period.  Their game state is
+    /// preserved.  Before the game starts they are removed at once.
+    fn disconnect_player(&mut self, player_id: u32) {
+        let (room, gs) = (&mut self.room, &mut self.gs);
+        room.player_senders.remove(&player_id);
+        room.relocators.remove(&player_id);
+
+        if gs.game_started && gs.players.contains_key(&player_id) {
+            // Sit the player out so auto-check/fold kicks in.
+            if !gs
+                .players
+                .get(&player_id)
+                .map(|p| p.sitting_out)
+                .unwrap_or(true)
+            {
+                gs.set_sitting_out(player_id);
+                room.broadcast(&ServerMessage::PlayerSatOut { player_id });
+            }
+            tracing::info!(
+                room = %self.room_id,
+                player = player_id,
+                "Player disconnected — seat held for {:?}",
+                room.config.timing.session_grace(),
+            );
+            self.hold_seat(player_id, Instant::now());
+            return;
+        }
+
+        // Game hasn't started — remove immediately.
+        if let Some(token) = room.player_sessions.remove(&player_id) {
+            room.sessions.remove(&token);
+        }
+        room.requests.forget(player_id);
+        gs.remove_player(player_id, &mut Vec::new());
+        room.broadcast(&ServerMessage::PlayerLeft { player_id });
+
+        if room.player_senders.is_empty() {
+            self.shut_down("Everyone left, so this room was closed");
+            self.log_removed("everyone left before the game started");
+        }
+    }
+
+    /// Hold a disconnected player's seat for the grace period, counted from
+    /// when they disconnected.
+    pub fn hold_seat(&mut self, player_id: u32, since: Instant) {
+        self.room.disconnected_at.insert(player_id, since);
+        let grace = self
+            .room
+            .config
+            .timing
+            .session_grace()
+            .saturating_sub(since.elapsed());
+        self.schedule(grace, Timer::GraceExpired { player_id });
+    }
+
+    /// Permanently remove a player who did not reconnect in time.
+    fn grace_expired(&mut self, player_id: u32) {
+        let (room, gs) = (&mut self.room, &mut self.gs);
+        // Only remove if they're still marked as disconnected.
+        if room.disconnected_at.remove(&player_id).is_none() {
+            return;
+        }
+        if let Some(token) = room.player_sessions.remove(&player_id) {
+            room.sessions.remove(&token);
+        }
+        room.requests.forget(player_id);
+        tracing::info!(
+            room = %self.room_id,
+            player = player_id,
+            "Grace period expired — player permanently removed"
+        );
+        // During a hand they are folded and go once it is over.
+        let mut outcome = Vec::new();
+        let Some(amount) = gs.leave(player_id, &mut outcome) else {
+            return;
+        };
+        if gs.game_mode.is_cash() {
+            room.broadcast(&ServerMessage::PlayerCashedOut { player_id, amount });
+            room.broadcast(&ServerMessage::SessionLedger {
+                entries: gs.ledger_entries(),
+            });
+        }
+        room.broadcast(&ServerMessage::PlayerLeft { player_id });
+        for m in &outcome {
+            room.broadcast(m);
+        }
+        // Multi-table tournaments: report the player out at once, as the
+        // table may be left waiting for players.
+        if gs.multi_table && gs.game_started {
+            self.maybe_start_new_hand();
+        }
+    }
+
+    /// Shut the room down at once: end its game, tell everyone in it why
+    /// and drop their connections.  Tables of a multi-table tournament
+    /// cannot be closed on their own.
+    pub fn close(&mut self, reason: &str) -> Result<(), String> {
+        if self.room.tournament.is_some() {
+            return Err("Tables of a multi-table tournament cannot be closed".to_string());
+        }
+        self.shut_down(reason);
+        self.log_removed("closed by an operator");
+        Ok(())
+    }
+
+    /// End the room's game, tell everyone in it `reason`, drop their
+    /// connections and stop the task.
+    fn shut_down(&mut self, reason: &str) {
+        let room = &mut self.room;
+        // Invalidate the running turn timer and stop any further hands.
+        room.turn_counter += 1;
+        room.closing = true;
+        if self.gs.game_started {
+            for m in &self.gs.end_game() {
+                room.broadcast(m);
+            }
+        }
+        room.notify_all(&ServerMessage::Announcement {
+            message: reason.to_string(),
+        });
+        if let Some(file) = &room.snapshot_file {
+            file.remove();
+        }
+        // Dropping the senders and relocation handles ends each connection
+        // once its queued messages are out.
+        room.player_senders.clear();
+        room.relocators.clear();
+        room.spectators.clear();
+        self.rooms.remove(&self.room_id);
+        self.stopped = true;
+    }
+
+    /// Remove a player from the room, banning them if `ban` is set.  The
+    /// host and the admin API both use this.
+    ///
+    /// During a hand the player folds if still in it (now if it is their
+    /// turn, otherwise when it comes round) and is removed once the hand is
+    /// resolved.
+    pub fn kick_player(&mut self, target: u32, ban: bool) -> Result<(), ProtocolError> {
+        let (room, gs) = (&mut self.room, &mut self.gs);
+        if !gs.players.contains_key(&target) {
+            return Err(ProtocolError::PlayerNotFound);
+        }
+        if room.tournament.is_some() && gs.game_started {
+            return Err(ProtocolError::not_allowed(
+                "Players cannot be kicked from a running multi-table tournament",
+            ));
+        }
+
+        // The kicked player hears it before their connection is cut.
+        room.broadcast(&ServerMessage::PlayerKicked {
+            player_id: target,
+            banned: ban,
+        });
+        let token = room.detach_player(target);
+        if ban {
+            if let Some(player) = gs.players.get(&target) {
+                room.banned_names.insert(player.name.trim().to_lowercase());
+            }
+            room.banned_sessions.extend(token);
+        }
+
+        let mut outcome = Vec::new();
+        if let Some(amount) = gs.leave(target, &mut outcome) {
+            if gs.game_mode.is_cash() {
+                room.broadcast(&ServerMessage::PlayerCashedOut {
+                    player_id: target,
+                    amount,
+                });
+                room.broadcast(&ServerMessage::SessionLedger {
+                    entries: gs.ledger_entries(),
+                });
+            }
+            room.broadcast(&ServerMessage::PlayerLeft { player_id: target });
+            for m in &outcome {
+                room.broadcast(m);
+            }
+            return Ok(());
+        }
+
+        if !gs.players.get(&target).is_some_and(|p| p.sitting_out) {
+            gs.set_sitting_out(target);
+            room.broadcast(&ServerMessage::PlayerSatOut { player_id: target });
+        }
+        if gs.current_player_id() == Some(target)
+            && gs.valid_actions(target).contains(&PlayerAction::Fold)
+        {
+            let _ = self.process_action(target, PlayerAction::Fold, 0);
+        }
+        Ok(())
+    }
+
+    // ─── Message processing ──────────────────────────────────────────────
+
+    /// Reply to a player with an error.
+    fn error(&self, player_id: u32, error: ProtocolError) {
+        send_error(&self.room, player_id, error);
+    }
+
+    /// Handle a message from a spectator.  `TakeSeat` is handled by the
+    /// connection through [`take_seat`](Self::take_seat).
+    fn spectator_message(&mut self, msg: ClientMessage, spectator_id: u32) {
+        let room = &self.room;
+        match msg {
+            ClientMessage::Chat { message } => {
+                if !room.allow_spectator_chat {
+                    room.send_to_spectator(
+                        spectator_id,
+                        &ServerMessage::error(ProtocolError::not_allowed(
+                            "The host has turned off spectator chat",
+                        )),
+                    );
+                    return;
+                }
+                let name = room
+                    .spectators
+                    .get(&spectator_id)
+                    .map(|s| s.name.clone())
+                    .unwrap_or_default();
+                room.broadcast(&ServerMessage::SpectatorChat { name, message });
+            }
+            ClientMessage::GetPlayers => {
+                // Stacks are table information, so they wait out the delay.
+                if let Some(spectator) = room.spectators.get(&spectator_id) {
+                    spectator.feed.send(ServerMessage::PlayerList {
+                        players: self.gs.player_infos(),
+                    });
+                }
+            }
+            ClientMessage::Ping => room.send_to_spectator(spectator_id, &ServerMessage::Pong),
+            _ => room.send_to_spectator(
+                spectator_id,
+                &ServerMessage::error(ProtocolError::not_allowed(
+                    "Spectators must take a seat to play",
+                )),
+            ),
+        }
+    }
+
+    /// Process a single [`ClientMessage`] from a seated player.
+    fn client_message(&mut self, msg: ClientMessage, player_id: u32) {
+        match msg {
+            // ── Join / room ops are no-ops once in a room ────────────────
+            ClientMessage::Join { .. }
+            | ClientMessage::CreateRoom { .. }
+            | ClientMessage::JoinRoom { .. }
+            | ClientMessage::Spectate { .. }
+            | ClientMessage::ListRooms
+            | ClientMessage::Rejoin { .. } => self.error(player_id, ProtocolError::AlreadyInRoom),
+            ClientMessage::Hello { .. } => {
+                self.error(player_id, ProtocolError::invalid("Already said hello"));
+            }
+
+            ClientMessage::Ping => self.room.send_to_player(player_id, &ServerMessage::Pong),
+
+            ClientMessage::GetPlayers => {
+                let players = self.gs.player_infos();
+                self.room
+                    .send_to_player(player_id, &ServerMessage::PlayerList { players });
+            }
+
+            ClientMessage::Chat { message } => {
+                self.room
+                    .broadcast(&ServerMessage::ChatMessage { player_id, message });
+            }
+
+            ClientMessage::StartGame => self.start_game(player_id),
+
+            // ── Betting actions ─────────────────────────────────────────
+            ClientMessage::Fold { .. }
+            | ClientMessage::Check { .. }
+            | ClientMessage::Call { .. }
+            | ClientMessage::Raise { .. }
+            | ClientMessage::AllIn { .. } => self.player_action(player_id, &msg),
+
+            ClientMessage::SitOut => {
+                if self
+                    .gs
+                    .players
+                    .get(&player_id)
+                    .map(|p| p.sitting_out)
+                    .unwrap_or(true)
+                {
+                    return; // already sitting out or unknown player
+                }
+                self.gs.set_sitting_out(player_id);
+                self.room
+                    .broadcast(&ServerMessage::PlayerSatOut { player_id });
+            }
+
+            ClientMessage::SitIn => {
+                if !self
+                    .gs
+                    .players
+                    .get(&player_id)
+                    .map(|p| p.sitting_out && !p.leaving)
+                    .unwrap_or(false)
+                {
+                    return; // already sitting in, leaving the table, or unknown player
+                }
+                self.gs.set_sitting_in(player_id);
+                self.room
+                    .broadcast(&ServerMessage::PlayerSatIn { player_id });
+
+                // If the game was paused waiting for players, check whether
+                // we now have enough active players to start a new hand.
+                if self.gs.waiting_for_players && can_deal(&self.gs) {
+                    self.maybe_start_new_hand();
+                }
+            }
+
+            ClientMessage::ToggleLateEntry => {
+                if self.gs.host_id != player_id {
+                    self.error(player_id, ProtocolError::NotHost);
+                    return;
+                }
+                self.gs.allow_late_entry = !self.gs.allow_late_entry;
+                self.room.broadcast(&ServerMessage::LateEntryChanged {
+                    allowed: self.gs.allow_late_entry,
+                });
+            }
+
+            ClientMessage::KickPlayer {
+                player_id: target,
+                ban,
+            } => {
+                let result = if self.gs.host_id != player_id {
+                    Err(ProtocolError::NotHost)
+                } else if target == player_id {
+                    Err(ProtocolError::not_allowed("You cannot kick yourself"))
+                } else {
+                    self.kick_player(target, ban)
+                };
+                match result {
+                    Ok(()) => tracing::info!(player = target, ban, "Player kicked by host"),
+                    Err(error) => self.error(player_id, error),
+                }
+            }
+
+            ClientMessage::TransferHost { player_id: target } => {
+                if self.gs.host_id != player_id {
+                    self.error(player_id, ProtocolError::NotHost);
+                    return;
+                }
+                if !self.gs.players.contains_key(&target) {
+                    self.error(player_id, ProtocolError::PlayerNotFound);
+                    return;
+                }
+                self.gs.host_id = target;
+                self.room
+                    .broadcast(&ServerMessage::HostChanged { player_id: target });
+                tracing::info!(from = player_id, to = target, "Host transferred");
+            }
+
+            ClientMessage::EndGame => {
+                let refusal = if self.gs.host_id != player_id {
+                    Some(ProtocolError::NotHost)
+                } else if self.room.tournament.is_some() {
+                    Some(ProtocolError::not_allowed(
+                        "Multi-table tournaments cannot be ended early",
+                    ))
+                } else if !self.gs.game_started {
+                    Some(ProtocolError::GameNotStarted)
+                } else {
+                    None
+                };
+                if let Some(error) = refusal {
+                    self.error(player_id, error);
+                    return;
+                }
+                // Invalidate the running turn timer.
+                self.room.turn_counter += 1;
+                for m in &self.gs.end_game() {
+                    self.room.broadcast(m);
+                }
+                self.room.save_snapshot(&self.gs);
+                tracing::info!(player = player_id, "Game ended by host");
+            }
+
+            ClientMessage::ChangeBlinds {
+                small_blind,
+                big_blind,
+                blind_config,
+            } => {
+                let valid = if self.gs.host_id != player_id {
+                    Err(ProtocolError::NotHost)
+                } else if self.room.tournament.is_some() {
+                    Err(ProtocolError::not_allowed(
+                        "Multi-table tournament blinds follow the tournament clock",
+                    ))
+                } else {
+                    validate_blinds(small_blind, big_blind, &blind_config, &self.gs.game_mode)
+                        .map_err(ProtocolError::invalid)
+                };
+                if let Err(error) = valid {
+                    self.error(player_id, error);
+                    return;
+                }
+                let pending = self.gs.change_blinds(small_blind, big_blind, blind_config);
+                self.room.broadcast(&ServerMessage::BlindsChanged {
+                    small_blind,
+                    big_blind,
+                    blind_config,
+                    pending,
+                });
+                tracing::info!(
+                    player = player_id,
+                    small_blind,
+                    big_blind,
+                    pending,
+                    "Blinds changed by host"
+                );
+            }
+
+            ClientMessage::TakeSeat { .. } => {
+                self.error(
+                    player_id,
+                    ProtocolError::not_allowed("You already have a seat"),
+                );
+            }
+
+            ClientMessage::CreateInvite {
+                single_use,
+                expires_in_secs,
+            } => {
+                if self.gs.host_id != player_id {
+                    self.error(player_id, ProtocolError::NotHost);
+                    return;
+                }
+                if let Err(message) = validate_invite(single_use, expires_in_secs) {
+                    self.error(player_id, ProtocolError::invalid(message));
+                    return;
+                }
+                let token = self.room.create_invite(single_use, expires_in_secs);
+                self.room.send_to_player(
+                    player_id,
+                    &ServerMessage::InviteCreated {
+                        room_id: self.room_id.clone(),
+                        token,
+                        single_use,
+                        expires_in_secs,
+                    },
+                );
+            }
+
+            ClientMessage::ToggleSpectatorChat => {
+                if self.gs.host_id != player_id {
+                    self.error(player_id, ProtocolError::NotHost);
+                    return;
+                }
+                self.room.allow_spectator_chat = !self.room.allow_spectator_chat;
+                self.room.broadcast(&self.room.spectator_status());
+            }
+
+            ClientMessage::ChangeSeat { seat } => {
+                if self.room.tournament.is_some() && !self.gs.game_started {
+                    self.error(
+                        player_id,
+                        ProtocolError::not_allowed("Seats are drawn when the tournament starts"),
+                    );
+                    return;
+                }
+                match self.gs.change_seat(player_id, seat) {
+                    Ok(pending) => self.room.broadcast(&ServerMessage::SeatChanged {
+                        player_id,
+                        seat,
+                        pending,
+                    }),
+                    Err(error) => self.error(player_id, error),
+                }
+            }
+
+            ClientMessage::BuyIn { amount } => match self.gs.buy_in(player_id, amount) {
+                Ok(pending) => {
+                    let (room, gs) = (&self.room, &self.gs);
+                    room.broadcast(&ServerMessage::PlayerBoughtIn {
+                        player_id,
+                        amount,
+                        pending,
+                    });
+                    if !pending {
+                        let chips = gs.players.get(&player_id).map(|p| p.chips).unwrap_or(0);
+                        room.broadcast(&ServerMessage::ChipUpdate { player_id, chips });
+                        room.broadcast(&ServerMessage::SessionLedger {
+                            entries: gs.ledger_entries(),
+                        });
+                    }
+                    // A rebuy may be what the table was waiting for.
+                    if gs.waiting_for_players {
+                        self.maybe_start_new_hand();
+                    }
+                }
+                Err(error) => self.error(player_id, error),
+            },
+
+            ClientMessage::LeaveTable => self.leave_table(player_id),
+
+            ClientMessage::Rebuy => match self.gs.rebuy(player_id) {
+                Ok((amount, pending)) => {
+                    let bought = ServerMessage::PlayerRebought {
+                        player_id,
+                        amount,
+                        pending,
+                    };
+                    self.announce_tournament_buy(player_id, &bought);
+                }
+                Err(error) => self.error(player_id, error),
+            },
+
+            ClientMessage::AddOn => match self.gs.add_on(player_id) {
+                Ok((amount, pending)) => {
+                    let bought = ServerMessage::PlayerAddedOn {
+                        player_id,
+                        amount,
+                        pending,
+                    };
+                    self.announce_tournament_buy(player_id, &bought);
+                }
+                Err(error) => self.error(player_id, error),
+            },
+
+            ClientMessage::EndRebuyPeriod => {
+                if self.gs.host_id != player_id {
+                    self.error(player_id, ProtocolError::NotHost);
+                    return;
+                }
+                if !self.gs.rebuy_open() {
+                    return;
+                }
+                // The add-on break pauses the game: invalidate the running
+                // turn timer first.
+                if self.gs.rebuy_config.add_on && !self.gs.paused {
+                    self.room.turn_counter += 1;
+                }
+                for m in &self.gs.end_rebuy_period() {
+                    self.room.broadcast(m);
+                }
+                tracing::info!(player = player_id, "Rebuy period ended by host");
+            }
+
+            ClientMessage::GetLedger => {
+                self.room.send_to_player(
+                    player_id,
+                    &ServerMessage::SessionLedger {
+                        entries: self.gs.ledger_entries(),
+                    },
+                );
+            }
+
+            ClientMessage::PauseGame => {
+                if self.gs.host_id != player_id {
+                    self.error(player_id, ProtocolError::NotHost);
+                    return;
+                }
+                if !self.gs.game_started || self.gs.paused {
+                    return;
+                }
+                // Invalidate the running turn timer and remember what was left.
+                self.room.turn_counter += 1;
+                self.gs.freeze_turn_timer();
+                self.gs.set_paused(true);
+                self.room.broadcast(&ServerMessage::GamePaused);
+                tracing::info!(player = player_id, "Game paused by host");
+            }
+
+            ClientMessage::ResumeGame => self.resume_game(player_id),
+        }
+    }
+
+    fn start_game(&mut self, player_id: u32) {
+        let refusal = if self.gs.game_started {
+            Some(ProtocolError::GameAlreadyStarted)
+        } else if self.room.closing {
+            Some(ProtocolError::ShuttingDown)
+        } else if self.gs.player_count() < 2 {
+            Some(ProtocolError::not_allowed(
+                "Need at least 2 players to start",
+            ))
+        } else if self
+            .room
+            .tournament
+            .as_ref()
+            .is_some_and(|link| link.lock().started())
+        {
+            Some(ProtocolError::not_allowed(
+                "This tournament has already been played",
+            ))
+        } else {
+            None
+        };
+        if let Some(error) = refusal {
+            self.error(player_id, error);
+            return;
+        }
+
+        let gs = &mut self.gs;
+        gs.game_started = true;
+
+        // Freeze the starting chip amount for late entries and rebuys.
+        gs.starting_chips = gs.starting_bbs * gs.big_blind;
+        gs.blind_level = 1;
+        gs.rebuy_period_over = false;
+        gs.eliminations.clear();
+
+        // Initialise the blind increase timer if configured.
+        if gs.blind_config.is_enabled() {
+            gs.blind_clock.restart();
+        }
+
+        // Multi-table tournaments seat the entrants across tables.
+        if let Some(link) = self.room.tournament.clone() {
+            for mut table in tournament::start(gs, &mut self.room, &self.handle, &link) {
+                table.start_table();
+                table.spawn();
+            }
+        }
+
+        self.room.broadcast(&ServerMessage::GameStarted);
+        self.room.save_snapshot(&self.gs);
+        self.deal();
+    }
+
+    fn leave_table(&mut self, player_id: u32) {
+        let (room, gs) = (&self.room, &mut self.gs);
+        if !gs.game_mode.is_cash() {
+            send_error(
+                room,
+                player_id,
+                ProtocolError::not_allowed(
+                    "Leaving with your stack is only possible in cash games",
+                ),
+            );
+            return;
+        }
+        if !gs.players.contains_key(&player_id) {
+            return;
+        }
+
+        // Cash games only: leaving ends nothing.
+        if let Some(amount) = gs.leave(player_id, &mut Vec::new()) {
+            room.broadcast(&ServerMessage::PlayerCashedOut { player_id, amount });
+            room.broadcast(&ServerMessage::PlayerLeft { player_id });
+            room.broadcast(&ServerMessage::SessionLedger {
+                entries: gs.ledger_entries(),
+            });
+            tracing::info!(player = player_id, amount, "Player cashed out");
+            return;
+        }
+
+        // In a hand: fold if still in it (now if it is their turn,
+        // otherwise when it comes round) and cash out once the hand is
+        // resolved.
+        if !gs.players.get(&player_id).is_some_and(|p| p.sitting_out) {
+            gs.set_sitting_out(player_id);
+            room.broadcast(&ServerMessage::PlayerSatOut { player_id });
+        }
+        if gs.current_player_id() == Some(player_id)
+            && gs.valid_actions(player_id).contains(&PlayerAction::Fold)
+        {
+            let _ = self.process_action(player_id, PlayerAction::Fold, 0);
+        }
+    }
+
+    fn resume_game(&mut self, player_id: u32) {
+        let (room, gs) = (&self.room, &mut self.gs);
+        if gs.host_id != player_id {
+            send_error(room, player_id, ProtocolError::NotHost);
+            return;
+        }
+        if !gs.paused {
+            return;
+        }
+        if let Some(closed) = gs.close_add_on() {
+            room.broadcast(&closed);
+        }
+        gs.set_paused(false);
+        room.broadcast(&ServerMessage::GameResumed);
+        tracing::info!(player = player_id, "Game resumed by host");
+
+        if !gs.game_started || gs.waiting_for_players {
+            return;
+        }
+
+        // Pick up exactly where the game was paused.  A finished hand has
+        // already been reported to the tournament; only the deal is left.
+        if gs.hand_complete {
+            self.schedule_next_hand();
+        } else if gs.actionable_players().is_empty() {
+            self.run_out_board();
+        } else {
+            let remaining = gs
+                .paused_turn_remaining
+                .take()
+                .unwrap_or(room.config.timing.turn_timeout_secs);
+            self.notify_turn_with_timeout(remaining);
+        }
+    }
+
+    /// Take a betting action sent by a player and answer it.  A numbered
+    /// action is acknowledged or rejected by its request ID; one that was
+    /// answered before gets the same answer again and is not retaken.
+    fn player_action(&mut self, player_id: u32, msg: &ClientMessage) {
+        let Some((action, amount)) = msg.action() else {
+            return;
+        };
+        let Some(request_id) = msg.request_id() else {
+            if let Err(error) = self.process_action(player_id, action, amount) {
+                self.error(player_id, error);
+            }
+            return;
+        };
+        let answer = match self.room.requests.answer(player_id, request_id) {
+            Some(answer) => answer,
+            None => {
+                let answer = self.process_action(player_id, action, amount);
+                self.room
+                    .requests
+                    .record(player_id, request_id, answer.clone());
+                answer
+            }
+        };
+        self.room
+            .send_to_player(player_id, &ServerMessage::action_answer(request_id, answer));
+    }
+
+    /// Handle a betting action from a player.
+    fn process_action(
+        &mut self,
+        player_id: u32,
+        action: PlayerAction,
+        amount: u32,
+    ) -> Result<(), ProtocolError> {
+        let (room, gs) = (&self.room, &mut self.gs);
+
+        // ── Pre-checks ───────────────────────────────────────────────────
+        if !gs.game_started {
+            return Err(ProtocolError::GameNotStarted);
+        }
+        if gs.paused {
+            return Err(ProtocolError::GamePaused);
+        }
+        if gs.current_player_id() != Some(player_id) {
+            return Err(ProtocolError::NotYourTurn);
+        }
+        let valid_actions = gs.valid_actions(player_id);
+        if !valid_actions.contains(&action) {
+            return Err(ProtocolError::InvalidAction { valid_actions });
+        }
+        let Some(player) = gs.players.get(&player_id).cloned() else {
+            return Err(ProtocolError::PlayerNotFound);
+        };
+
+        let to_call = gs.current_bet.saturating_sub(player.current_bet);
+        let mut action_amount: Option<u32> = None;
+
+        // ── Apply the action ─────────────────────────────────────────────
+        match action {
+            PlayerAction::Fold => {
+                if let Some(p) = gs.players.get_mut(&player_id) {
+                    p.status = PlayerStatus::Folded;
+                }
+            }
+            PlayerAction::Check => {
+                if to_call != 0 {
+                    let valid_actions = gs.valid_actions(player_id);
+                    return Err(ProtocolError::InvalidAction { valid_actions });
+                }
+                if gs.phase == GamePhase::PreFlop && gs.big_blind_option {
+                    gs.big_blind_option = false;
+                    gs.last_raiser_index = None;
+                }
+            }
+            PlayerAction::Call => {
+                let call_amount = to_call.min(player.chips);
+                {
+                    let p = gs.players.get_mut(&player_id).unwrap();
+                    p.chips -= call_amount;
+                    p.current_bet += call_amount;
+                    if p.chips == 0 {
+                        p.status = PlayerStatus::AllIn;
+                    }
+                }
+                gs.pot += call_amount;
+                action_amount = Some(call_amount);
+            }
+            PlayerAction::Raise => {
+                // A raise smaller than the minimum is only allowed all in.
+                let max = player.chips.saturating_sub(to_call);
+                let min = gs.min_raise.min(max);
+                if amount < min || amount > max {
+                    return Err(ProtocolError::InvalidAmount { min, max });
+                }
+                let raise_total = to_call + amount;
+
+                let new_bet;
+                {
+                    let p = gs.players.get_mut(&player_id).unwrap();
+                    p.chips -= raise_total;
+                    p.current_bet += raise_total;
+                    new_bet = p.current_bet;
+                    if p.chips == 0 {
+                        p.status = PlayerStatus::AllIn;
+                    }
+                }
+                gs.pot += raise_total;
+                gs.current_bet = new_bet;
+                gs.min_raise = gs.big_blind;
+                gs.last_raiser_index = Some(gs.current_player_index);
+                gs.big_blind_option = false;
+                action_amount = Some(raise_total);
+            }
+            PlayerAction::AllIn => {
+                let all_in = player.chips;
+                let new_bet;
+                {
+                    let p = gs.players.get_mut(&player_id).unwrap();
+                    p.chips = 0;
+                    p.current_bet += all_in;
+                    new_bet = p.current_bet;
+                    p.status = PlayerStatus::AllIn;
+                }
+                gs.pot += all_in;
+                if new_bet > gs.current_bet {
+                    gs.current_bet = new_bet;
+                    gs.last_raiser_index = Some(gs.current_player_index);
+                }
+                action_amount = Some(all_in);
+            }
+        }
+
+        gs.record_action(action);
+
+        // ── Broadcast the action + pot update ────────────────────────────
+        room.broadcast(&ServerMessage::PlayerActed {
+            player_id,
+            action,
+            amount: action_amount,
+        });
+        room.broadcast(&ServerMessage::PotUpdate { pot: gs.pot });
+
+        gs.has_acted_this_round = true;
+        gs.next_player();
+
+        // ── Post-action: check hand / betting status ─────────────────────
+        if gs.active_player_count() == 1
+            || (gs.is_betting_complete() && gs.phase == GamePhase::River)
+        {
+            for m in &gs.resolve_hand() {
+                room.broadcast(m);
+            }
+            self.maybe_start_new_hand();
+        } else if gs.is_betting_complete() {
+            // Advance to next phase.
+            for m in &gs.advance_phase() {
+                room.broadcast(m);
+            }
+            // If only all-in players remain, run it out.
+            if gs.actionable_players().is_empty() {
+                broadcast_allin_showdown(gs, room);
+                self.run_out_board();
+            } else {
+                self.notify_turn_and_start_timer();
+            }
+        } else {
+            self.notify_turn_and_start_timer();
+        }
+        Ok(())
+    }
+
+    // ─── Hands ───────────────────────────────────────────────────────────
+
+    /// If the game is still running with ≥ 2 active (not sitting-out)
+    /// players, schedule the next hand after the pause between hands.
+    /// Otherwise wait for players to sit back in.  The room is saved first
+    /// (see [`crate::store`]).
+    ///
+    /// While the host has paused the game the next hand is held back;
+    /// resuming schedules it (see [`schedule_next_hand`](Self::schedule_next_hand)).
+    fn maybe_start_new_hand(&mut self) {
+        // Between hands: keep the room across restarts.
+        self.room.save_snapshot(&self.gs);
+        if !self.gs.game_started || self.room.closing {
+            return;
+        }
+
+        // Multi-table tournaments: report to the tournament and move
+        // players to balance the tables before dealing again.
+        if let Some(link) = &self.room.tournament {
+            match tournament::between_hands(&mut self.gs, &self.room, link) {
+                BetweenHands::Finished => {
+                    self.gs.game_started = false;
+                    self.gs.phase = GamePhase::Lobby;
+                    return;
+                }
+                BetweenHands::Continue(transfers) => {
+                    for transfer in transfers {
+                        transfer.room.send(Command::SeatMovers {
+                            movers: transfer.movers,
+                            from: self.handle.clone(),
+                        });
+                    }
+                }
+            }
+        }
+
+        self.schedule_next_hand();
+    }
+
+    /// Schedule the next hand after the pause between hands if there are
+    /// enough players to deal to, otherwise wait for players.  The finished
+    /// hand must already have been wrapped up by
+    /// [`maybe_start_new_hand`](Self::maybe_start_new_hand).
+    fn schedule_next_hand(&mut self) {
+        if can_deal(&self.gs) {
+            self.gs.set_waiting_for_players(false);
+            if !self.gs.paused {
+                let hand = self.gs.hand_number;
+                self.schedule(
+                    self.room.config.timing.hand_pause(),
+                    Timer::NextHand { hand },
+                );
+            }
+        } else {
+            self.gs.set_waiting_for_players(true);
+            self.room.broadcast(&ServerMessage::WaitingForPlayers);
+        }
+    }
+
+    /// Deal the hand after `hand` once the pause between hands is over.
+    fn deal_next_hand(&mut self, hand: u32) {
+        let (room, gs) = (&self.room, &mut self.gs);
+        if gs.hand_number != hand
+            || gs.hand_in_progress()
+            || !gs.game_started
+            || gs.paused
+            || room.closing
+        {
+            return;
+        }
+        // The table kept playing during the pause: players may have sat
+        // out or left.
+        if !can_deal(gs) {
+            gs.set_waiting_for_players(true);
+            room.broadcast(&ServerMessage::WaitingForPlayers);
+            return;
+        }
+        // The rebuy period ends as the blinds go up; the add-on break (if
+        // any) comes before the next hand is dealt.
+        if gs.rebuy_period_due() {
+            for m in &gs.end_rebuy_period() {
+                room.broadcast(m);
+            }
+            if gs.paused {
+                return;
+            }
+        }
+        if let Some(link) = &room.tournament {
+            sync_tournament_blinds(gs, room, link);
+        }
+        self.deal();
+    }
+
+    /// Deal a hand, send the hole cards and start the first turn.
+    fn deal(&mut self) {
+        for m in &self.gs.start_new_hand() {
+            self.room.broadcast(m);
+        }
+        send_hole_cards(&self.gs, &self.room);
+        self.notify_turn_and_start_timer();
+    }
+
+    /// Deal the first hand at a table opened when a multi-table tournament
+    /// starts.
+    pub fn start_table(&mut self) {
+        self.room.broadcast(&ServerMessage::GameStarted);
+        if self.gs.player_count() < 2 {
+            // A lone player waits for the tournament to send company.
+            self.gs.set_waiting_for_players(true);
+            self.room.broadcast(&ServerMessage::WaitingForPlayers);
+            return;
+        }
+        self.deal();
+    }
+
+    /// Seat players a multi-table tournament moved here, holding the seats
+    /// of those who are disconnected.  Returns their IDs.
+    pub fn seat_movers(&mut self, movers: Vec<Mover>) -> Vec<u32> {
+        let moved: Vec<u32> = movers.iter().map(|m| m.player.id).collect();
+        tournament::seat_movers(&mut self.room, &mut self.gs, &self.room_id, movers);
+        for &player_id in &moved {
+            if let Some(&since) = self.room.disconnected_at.get(&player_id) {
+                self.hold_seat(player_id, since);
+            }
+        }
+        moved
+    }
+
+    /// Take in players moved from the table `from`, then tell it to forget
+    /// them.
+    fn move_in(&mut self, movers: Vec<Mover>, from: &WeakRoomHandle) {
+        let moved = self.seat_movers(movers);
+        if let Some(link) = &self.room.tournament {
+            link.lock().seated(&self.room_id, moved.len());
+        }
+        // A table left waiting for players can deal again.
+        if self.gs.waiting_for_players {
+            self.maybe_start_new_hand();
+        }
+        if let Some(from) = from.upgrade() {
+            from.send(Command::ForgetMovers { player_ids: moved });
+        }
+    }
+
+    /// Broadcast a tournament rebuy or add-on along with the updated stack,
+    /// ledger and prize pool.
+    fn announce_tournament_buy(&mut self, player_id: u32, bought: &ServerMessage) {
+        let (room, gs) = (&self.room, &self.gs);
+        room.broadcast(bought);
+        let pending = matches!(
+            bought,
+            ServerMessage::PlayerRebought { pending: true, .. }
+                | ServerMessage::PlayerAddedOn { pending: true, .. }
+        );
+        if !pending {
+            let chips = gs.players.get(&player_id).map(|p| p.chips).unwrap_or(0);
+            room.broadcast(&ServerMessage::ChipUpdate { player_id, chips });
+        }
+        room.broadcast(&ServerMessage::SessionLedger {
+            entries: gs.ledger_entries(),
+        });
+        room.broadcast(&ServerMessage::PrizePool {
+            total: gs.prize_pool(),
+        });
+        tracing::info!(player = player_id, "Tournament chips bought");
+
+        // A busted player's rebuy may be what the table was waiting for.
+        if gs.waiting_for_players {
+            self.maybe_start_new_hand();
+        }
+    }
+
+    /// Run out the remaining community cards, one street per run-out
+    /// delay, when all players are all-in.
+    fn run_out_board(&mut self) {
+        let hand = self.gs.hand_number;
+        self.schedule(
+            self.room.config.timing.runout_delay(),
+            Timer::RunOut { hand },
+        );
+    }
+
+    /// Deal the next street of a run-out.  Stops if the game is paused;
+    /// resuming restarts the run-out from the current street.
+    fn run_out_street(&mut self, hand: u32) {
+        let (room, gs) = (&self.room, &mut self.gs);
+        if gs.hand_number != hand || gs.hand_complete || gs.paused || !gs.game_started {
+            return;
+        }
+        for m in &gs.advance_phase() {
+            room.broadcast(m);
+        }
+        if gs.phase == GamePhase::Showdown {
+            for m in &gs.resolve_hand() {
+                room.broadcast(m);
+            }
+            self.maybe_start_new_hand();
+        } else {
+            self.run_out_board();
+        }
+    }
+
+    // ─── Turns ───────────────────────────────────────────────────────────
+
+    /// Send the turn notification **and** start a full-length turn timer.
+    fn notify_turn_and_start_timer(&mut self) {
+        let timeout_secs = self.room.config.timing.turn_timeout_secs;
+        self.notify_turn_with_timeout(timeout_secs);
+    }
+
+    /// Send the turn notification and start a turn timer of `timeout_secs`.
+    ///
+    /// Increments the room's turn counter so any timer still pending for an
+    /// earlier turn does nothing, then schedules a check-or-fold for when
+    /// the timeout elapses.
+    ///
+    /// If the current player is sitting out, their action is resolved
+    /// right away (auto-check or auto-fold) instead of waiting for input.
+    fn notify_turn_with_timeout(&mut self, timeout_secs: u32) {
+        let (room, gs) = (&mut self.room, &mut self.gs);
+        if gs.paused {
+            return;
+        }
+
+        // Send the private YourTurn message to the current player.
+        send_turn_notification(gs, room);
+
+        let Some(current_id) = gs.current_player_id() else {
+            return;
+        };
+
+        room.turn_counter += 1;
+        let turn = room.turn_counter;
+
+        if gs.is_current_player_sitting_out() {
+            // Sitting-out player: no timer broadcast.  Players leaving a
+            // cash table always fold.
+            let valid = gs.valid_actions(current_id);
+            let leaving = gs.players.get(&current_id).is_some_and(|p| p.leaving);
+            let action = if valid.contains(&PlayerAction::Check) && !leaving {
+                PlayerAction::Check
+            } else {
+                PlayerAction::Fold
+            };
+            self.schedule(
+                AUTO_ACT_DELAY,
+                Timer::AutoAct {
+                    turn,
+                    player_id: current_id,
+                    action,
+                },
+            );
+            return;
+        }
+
+        // Broadcast the timer start to all players so UIs can show a countdown.
+        gs.start_turn_timer(timeout_secs);
+        room.broadcast(&ServerMessage::TurnTimerStarted {
+            player_id: current_id,
+            timeout_secs,
+        });
+        self.schedule(
+            Duration::from_secs(u64::from(timeout_secs)),
+            Timer::TurnTimeout {
+                turn,
+                player_id: current_id,
+            },
+        );
+    }
+
+    /// Run out the current player's clock now, as if their turn timer had
+    /// expired.  Returns the player whose turn it was.
+    pub fn force_turn_timeout(&mut self) -> Result<u32, String> {
+        let gs = &self.gs;
+        if !gs.game_started || gs.hand_complete {
+            return Err("No hand is being played".to_string());
+        }
+        if gs.paused {
+            return Err("The game is paused".to_string());
+        }
+        let player_id = gs
+            .current_player_id()
+            .ok_or_else(|| "Nobody is to act".to_string())?;
+        self.force_timeout_action(self.room.turn_counter, player_id);
+        Ok(player_id)
+    }
+
+    /// Force a check-or-fold for a player whose turn timer has expired.
+    ///
+    /// If the forced action is a fold (i.e. the player could not simply
+    /// check), the player is also automatically sat out.
+    fn force_timeout_action(&mut self, expected_turn: u64, player_id: u32) {
+        let (room, gs) = (&self.room, &mut self.gs);
+        if room.turn_counter != expected_turn
+            || !gs.game_started
+            || gs.paused
+            || gs.current_player_id() != Some(player_id)
+        {
+            return;
+        }
+
+        let action = if gs.valid_actions(player_id).contains(&PlayerAction::Check) {
+            PlayerAction::Check
+        } else {
+            PlayerAction::Fold
+        };
+
+        // If forced to fold, automatically sit the player out.
+        if action == PlayerAction::Fold
+            && !gs
+                .players
+                .get(&player_id)
+                .map(|p| p.sitting_out)
+                .unwrap_or(true)
+        {
+            gs.set_sitting_out(player_id);
+            room.broadcast(&ServerMessage::PlayerSatOut { player_id });
+            tracing::info!(player = player_id, "Auto sitting out after timeout fold");
+        }
+
+        tracing::info!(
+            player = player_id,
+            ?action,
+            "Turn timer expired, forcing action"
+        );
+        METRICS.turn_timeouts.inc();
+
+        // Reuse the normal action processing pipeline.
+        let _ = self.process_action(player_id, action, 0);
+    }
+}
+
+/// Reply to a player with an error.
+fn send_error(room: &Room, player_id: u32, error: ProtocolError) {
+    room.send_to_player(player_id, &ServerMessage::error(error));
+}
+
+/// Sleep until the next timer is due, or forever if there is none.
+async fn sleep_until(next: Option<Instant>) {
+    match next {
+        Some(at) => tokio::time::sleep_until(at.into()).await,
+        None => std::future::pending().await,
+    }
+}
+
+/// Whether at least two players can be dealt in: seated, not sitting out
+/// and with chips.
+fn can_deal(gs: &GameState) -> bool {
+    gs.player_order
+        .iter()
+        .filter(|id| {
+            gs.players
+                .get(id)
+                .map(|p| !p.sitting_out && p.chips > 0)
+                .unwrap_or(false)
+        })
+        .count()
+        >= 2
+}
+
+/// Bring a multi-table tournament table up to the shared blind level.
+fn sync_tournament_blinds(gs: &mut GameState, room: &Room, link: &TableLink) {
+    let (small_blind, big_blind, level) = link.lock().current_blinds();
+    if small_blind != gs.small_blind || big_blind != gs.big_blind {
+        gs.small_blind = small_blind;
+        gs.big_blind = big_blind;
+        gs.blind_level = level;
+        room.broadcast(&ServerMessage::BlindsIncreased {
+            small_blind,
+            big_blind,
+        });
+    }
+}
+
+/// Send each player their private hole cards, and all of them to the
+/// spectators in a streamer view.
+fn send_hole_cards(gs: &GameState, room: &Room) {
+    for (&pid, player) in &gs.players {
+        if let Some((c1, c2)) = player.hole_cards {
+            let cards = [card_to_info(&c1), card_to_info(&c2)];
+            room.send_to_player(pid, &ServerMessage::HoleCards { cards });
+        }
+    }
+    room.send_spectator_hole_cards(gs);
+}
+
+/// Notify the player whose turn it is.
+fn send_turn_notification(gs: &GameState, room: &Room) {
+    if let Some(current_id) = gs.current_player_id() {
+        let your_bet = gs
+            .players
+            .get(&current_id)
+            .map(|p| p.current_bet)
+            .unwrap_or(0);
+        let valid_actions = gs.valid_actions(current_id);
+
+        room.send_to_player(
+            current_id,
+            &ServerMessage::YourTurn {
+                current_bet: gs.current_bet,
+                your_bet,
+                pot: gs.pot,
+                min_raise: gs.min_raise,
+                valid_actions,
+            },
+        );
+    }
+}
+
+/// Broadcast an all-in showdown with equity percentages.
+fn broadcast_allin_showdown(gs: &GameState, room: &Room) {
+    let mut player_hands: Vec<(u32, [CardInfo; 2], Hand)> = Vec::new();
+
+    for &id in &gs.player_order {
+        if let Some(player) = gs.players.get(&id)
+            && (player.status == PlayerStatus::Active || player.status == PlayerStatus::AllIn)
+            && let Some((c1, c2)) = player.hole_cards
+        {
+            let cards = [card_to_info(&c1), card_to_info(&c2)];
+            player_hands.push((id, cards, Hand(c1, c2)));
+        }
+    }
+
+    if player_hands.len() < 2 {
+        return;
+    }
+
+    let board = gs.build_board();
+    let hands_for_calc: Vec<Hand> = player_hands
+        .iter()
+        .map(|(_, _, h)| Hand(h.0, h.1))
+        .collect();
+    let equities = calculate_equity_multi(
+        &hands_for_calc,
+        &board,
+        room.config.limits.equity_iterations,
+    );
+
+    let hands_with_equity: Vec<(u32, [CardInfo; 2], f64)> = player_hands
+        .iter()
+        .enumerate()
+        .map(|(i, (id, cards, _))| (*id, *cards, equities.get(i).copied().unwrap_or(0.0)))
+        .collect();
+
+    let community_cards: Vec<CardInfo> = gs.community_cards.iter().map(card_to_info).collect();
+
+    room.broadcast(&ServerMessage::AllInShowdown {
+        hands: hands_with_equity,
+        community_cards,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use poker_core::protocol::{BlindConfig, GameMode, RebuyConfig, SpectatorDelay};
+
+    use super::*;
+    use crate::config::Config;
+    use crate::room::RoomSettings;
+    use crate::tournament::Tournament;
+
+    /// A multi-table tournament started with `n` players, all at this
+    /// table.  The room task is not spawned.
+    fn tournament_table(n: u32) -> (RoomActor, RoomHandle, TableLink) {
+        let settings = RoomSettings {
+            blind_config: BlindConfig::default(),
+            starting_bbs: 50,
+            game_mode: GameMode::Tournament,
+            rebuy_config: RebuyConfig::default(),
+            payouts: vec![100],
+            multi_table: true,
+            table_size: 9,
+            spectator_delay: SpectatorDelay::default(),
+            password: String::new(),
+            unlisted: false,
+        };
+        let link = TableLink::new(
+            Tournament::new("mtt", settings.clone(), RoomMap::default()),
+            "mtt",
+        );
+        let (mut room, mut gs) = Room::new(settings, Arc::new(Config::default()));
+        room.tournament = Some(link.clone());
+        for seat in 0..n {
+            gs.add_player(format!("P{}", seat + 1), seat);
+        }
+        gs.game_started = true;
+        gs.starting_chips = gs.starting_bbs * gs.big_blind;
+        let (mut actor, handle) = RoomActor::new("mtt".to_string(), room, gs, RoomMap::default());
+        let tables = tournament::start(&mut actor.gs, &mut actor.room, &actor.handle, &link);
+        assert!(tables.is_empty());
+        (actor, handle, link)
+    }
+
+    fn players_remaining(link: &TableLink) -> u32 {
+        match link.lock().progress() {
+            ServerMessage::TournamentUpdate {
+                players_remaining, ..
+            } => players_remaining,
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn grace_expiry_at_a_tournament_table_is_reported_to_the_tournament() {
+        let (mut actor, _handle, link) = tournament_table(3);
+        actor.room.disconnected_at.insert(3, Instant::now());
+        actor.grace_expired(3);
+        assert!(!actor.gs.players.contains_key(&3));
+        assert_eq!(players_remaining(&link), 2);
+        assert!(link.lock().in_play());
+
+        // The last opponent timing out ends the tournament.
+        actor.room.disconnected_at.insert(2, Instant::now());
+        actor.grace_expired(2);
+        assert_eq!(players_remaining(&link), 1);
+        assert!(!link.lock().in_play());
+        assert!(!actor.gs.game_started);
+    }
+}
@@ -0,0 +1,307 @@
+//! Token-protected operator API, mounted at `/admin` when an admin token is
+//! configured.  Every request needs `Authorization: Bearer <token>`.
+//!
+//! | Method   | Path                                  | Description                          |
+//! |----------|---------------------------------------|--------------------------------------|
+//! | `GET`    | `/admin/rooms`                        | Internal state of every room         |
+//! | `GET`    | `/admin/rooms/{id}`                   | Internal state of one room           |
+//! | `POST`   | `/admin/rooms/{id}/close`             | End the game and close the room      |
+//! | `POST`   | `/admin/rooms/{id}/timeout`           | Time out the player to act           |
+//! | `DELETE` | `/admin/rooms/{id}/players/{player}`  | Remove a player (`?ban=true` to ban) |
+//! | `POST`   | `/admin/announce`                     | Announce to one room or all rooms    |
+
+use std::sync::Arc;
+use std::time::Instant;
+
+use axum::extract::{Path, Query, Request, State};
+use axum::http::{StatusCode, header};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{delete, get, post};
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+
+use crate::AppState;
+use crate::actor::{RoomActor, RoomHandle};
+use crate::game_logic::GameState;
+use crate::room::Room;
+
+/// Longest announcement accepted.
+const MAX_ANNOUNCEMENT_LEN: usize = 500;
+
+/// Reason given to players when no other is supplied.
+const DEFAULT_CLOSE_REASON: &str = "This room was closed by the server operators";
+
+type AdminError = (StatusCode, String);
+
+/// The `/admin` routes, guarded by `token`.
+pub fn router(token: &str) -> Router<AppState> {
+    Router::new()
+        .route("/rooms", get(list_rooms))
+        .route("/rooms/{id}", get(room_state))
+        .route("/rooms/{id}/close", post(close_room))
+        .route("/rooms/{id}/timeout", post(force_timeout))
+        .route("/rooms/{id}/players/{player_id}", delete(remove_player))
+        .route("/announce", post(announce))
+        .route_layer(middleware::from_fn_with_state(
+            Arc::<str>::from(token),
+            require_token,
+        ))
+}
+
+/// Reject requests without the right bearer token.
+async fn require_token(State(token): State<Arc<str>>, request: Request, next: Next) -> Response {
+    let given = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+    match given {
+        Some(given) if tokens_match(given.as_bytes(), token.as_bytes()) => next.run(request).await,
+        _ => (StatusCode::UNAUTHORIZED, "Missing or invalid admin token").into_response(),
+    }
+}
+
+/// Compare tokens in time that does not depend on where they differ.
+fn tokens_match(given: &[u8], expected: &[u8]) -> bool {
+    given.len() == expected.len()
+        && given
+            .iter()
+            .zip(expected)
+            .fold(0, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+/// A player as an operator sees them.
+#[derive(Debug, Serialize)]
+pub struct AdminPlayer {
+    pub id: u32,
+    pub name: String,
+    pub seat: u32,
+    pub chips: u32,
+    pub current_bet: u32,
+    pub status: String,
+    pub sitting_out: bool,
+    pub leaving: bool,
+    pub connected: bool,
+    /// Seconds since the player disconnected, while their seat is held.
+    pub disconnected_secs: Option<u64>,
+}
+
+/// Everything about a room an operator needs to tell what state it is in.
+#[derive(Debug, Serialize)]
+pub struct AdminRoom {
+    pub room_id: String,
+    pub phase: String,
+    pub game_started: bool,
+    pub hand_number: u32,
+    pub hand_complete: bool,
+    pub paused: bool,
+    pub waiting_for_players: bool,
+    pub closing: bool,
+    pub current_player: Option<u32>,
+    pub pot: u32,
+    pub current_bet: u32,
+    pub turn_counter: u64,
+    /// Seconds the current player has been on the clock.
+    pub turn_elapsed_secs: Option<u64>,
+    pub host_id: u32,
+    pub small_blind: u32,
+    pub big_blind: u32,
+    pub multi_table: bool,
+    pub password: bool,
+    pub unlisted: bool,
+    pub spectators: usize,
+    pub players: Vec<AdminPlayer>,
+}
+
+impl AdminRoom {
+    pub fn capture(room_id: &str, room: &Room, gs: &GameState) -> Self {
+        let now = Instant::now();
+        let mut players: Vec<AdminPlayer> = gs
+            .players
+            .values()
+            .map(|p| AdminPlayer {
+                id: p.id,
+                name: p.name.clone(),
+                seat: p.seat,
+                chips: p.chips,
+                current_bet: p.current_bet,
+                status: format!("{:?}", p.status),
+                sitting_out: p.sitting_out,
+                leaving: p.leaving,
+                connected: room.player_senders.contains_key(&p.id),
+                disconnected_secs: room
+                    .disconnected_at
+                    .get(&p.id)
+                    .map(|at| now.duration_since(*at).as_secs()),
+            })
+            .collect();
+        players.sort_by_key(|p| p.seat);
+        Self {
+            room_id: room_id.to_string(),
+            phase: format!("{:?}", gs.phase),
+            game_started: gs.game_started,
+            hand_number: gs.hand_number,
+            hand_complete: gs.hand_complete,
+            paused: gs.paused,
+            waiting_for_players: gs.waiting_for_players,
+            closing: room.closing,
+            current_player: gs
+                .hand_in_progress()
+                .then(|| gs.current_player_id())
+                .flatten(),
+            pot: gs.pot,
+            current_bet: gs.current_bet,
+            turn_counter: room.turn_counter,
+            turn_elapsed_secs: gs.turn_started_at.map(|at| at.elapsed().as_secs()),
+            host_id: gs.host_id,
+            small_blind: gs.small_blind,
+            big_blind: gs.big_blind,
+            multi_table: room.tournament.is_some(),
+            password: !room.password.is_empty(),
+            unlisted: room.unlisted,
+            spectators: room.spectators.len(),
+            players,
+        }
+    }
+}
+
+/// `GET /admin/rooms`
+async fn list_rooms(State(state): State<AppState>) -> Json<Vec<AdminRoom>> {
+    let mut rooms = Vec::new();
+    for (_, room) in state.room_manager.all_rooms() {
+        rooms.extend(room.call(capture).await);
+    }
+    Json(rooms)
+}
+
+/// `GET /admin/rooms/{id}`
+async fn room_state(
+    Path(room_id): Path<String>,
+    State(state): State<AppState>,
+) -> Result<Json<AdminRoom>, AdminError> {
+    find_room(&state, &room_id)?
+        .call(capture)
+        .await
+        .map(Json)
+        .ok_or_else(|| not_found(&room_id))
+}
+
+fn capture(a: &mut RoomActor) -> AdminRoom {
+    AdminRoom::capture(&a.room_id, &a.room, &a.gs)
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct CloseRequest {
+    /// Shown to the players; a stock reason is used when empty.
+    reason: String,
+}
+
+/// `POST /admin/rooms/{id}/close`, with an optional `{"reason": "…"}` body.
+async fn close_room(
+    Path(room_id): Path<String>,
+    State(state): State<AppState>,
+    body: Option<Json<CloseRequest>>,
+) -> Result<StatusCode, AdminError> {
+    let reason = body.map(|Json(b)| b.reason).unwrap_or_default();
+    let reason = match reason.trim() {
+        "" => DEFAULT_CLOSE_REASON,
+        reason => reason,
+    };
+    find_room(&state, &room_id)?;
+    state
+        .room_manager
+        .close_room(&room_id, reason)
+        .await
+        .map_err(|e| (StatusCode::CONFLICT, e))?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[derive(Debug, Serialize)]
+struct TimedOut {
+    player_id: u32,
+}
+
+/// `POST /admin/rooms/{id}/timeout`
+async fn force_timeout(
+    Path(room_id): Path<String>,
+    State(state): State<AppState>,
+) -> Result<Json<TimedOut>, AdminError> {
+    let player_id = find_room(&state, &room_id)?
+        .call(|a| a.force_turn_timeout())
+        .await
+        .ok_or_else(|| not_found(&room_id))?
+        .map_err(|e| (StatusCode::CONFLICT, e))?;
+    tracing::info!(room = %room_id, player = player_id, "Turn timed out by admin");
+    Ok(Json(TimedOut { player_id }))
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct RemoveQuery {
+    ban: bool,
+}
+
+/// `DELETE /admin/rooms/{id}/players/{player_id}`
+async fn remove_player(
+    Path((room_id, player_id)): Path<(String, u32)>,
+    Query(query): Query<RemoveQuery>,
+    State(state): State<AppState>,
+) -> Result<StatusCode, AdminError> {
+    find_room(&state, &room_id)?
+        .call(move |a| a.kick_player(player_id, query.ban))
+        .await
+        .ok_or_else(|| not_found(&room_id))?
+        .map_err(|e| (StatusCode::CONFLICT, e.to_string()))?;
+    tracing::info!(room = %room_id, player = player_id, ban = query.ban, "Player removed by admin");
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[derive(Debug, Deserialize)]
+struct AnnounceRequest {
+    message: String,
+    /// Only this room; every room when absent.
+    #[serde(default)]
+    room_id: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct Announced {
+    rooms: usize,
+}
+
+/// `POST /admin/announce` with `{"message": "…", "room_id": "…"}`.
+async fn announce(
+    State(state): State<AppState>,
+    Json(request): Json<AnnounceRequest>,
+) -> Result<Json<Announced>, AdminError> {
+    let message = request.message.trim();
+    if message.is_empty() {
+        return Err((StatusCode::BAD_REQUEST, "The message is empty".to_string()));
+    }
+    if message.chars().count() > MAX_ANNOUNCEMENT_LEN {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("The message is longer than {MAX_ANNOUNCEMENT_LEN} characters"),
+        ));
+    }
+    let rooms = state
+        .room_manager
+        .announce(message, request.room_id.as_deref())
+        .map_err(|e| (StatusCode::NOT_FOUND, e))?;
+    tracing::info!(rooms, "Announcement sent: {message}");
+    Ok(Json(Announced { rooms }))
+}
+
+fn find_room(state: &AppState, room_id: &str) -> Result<RoomHandle, AdminError> {
+    state
+        .room_manager
+        .get_room(room_id)
+        .ok_or_else(|| not_found(room_id))
+}
+
+fn not_found(room_id: &str) -> AdminError {
+    (StatusCode::NOT_FOUND, format!("Room '{room_id}' not found"))
+}
@@ -0,0 +1,397 @@
+//! Server configuration: built-in defaults, overridden by an optional TOML
+//! file, overridden in turn by command-line flags and environment variables.
+//!
+//! ```toml
+//! [server]
+//! bind = "0.0.0.0:8080"
+//! static_dir = "./dist"
+//! state_dir = "/var/lib/poker"   # omit to keep games in memory only
+//! log_format = "full"            # full, compact or pretty
+//! admin_token = "…"              # enables /admin; omit to turn it off
+//!
+//! [timing]
+//! turn_timeout_secs = 30
+//! session_grace_secs = 300
+//! hand_pause_ms = 5000
+//! runout_delay_ms = 1500
+//! shutdown_timeout_secs = 60
+//! reconnect_after_secs = 10
+//! unjoined_room_timeout_secs = 300
+//! idle_room_timeout_secs = 900
+//!
+//! [limits]
+//! max_rooms = 1000               # 0 = no limit
+//! max_spectators = 0             # per room, 0 = no limit
+//! equity_iterations = 1000
+//! replay_events = 500            # per room, kept for players who rejoin
+//!
+//! [room]
+//! small_blind = 10
+//! big_blind = 20
+//! allow_spectator_chat = true
+//! allow_late_entry = false
+//! ```
+//!
+//! Unknown keys are rejected, so a typo does not silently fall back to a
+//! default.
+
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use clap::{Parser, ValueEnum};
+use serde::{Deserialize, Serialize};
+
+/// Command-line interface of the server.
+#[derive(Debug, Parser)]
+#[command(version, about = "Multi-room poker server")]
+pub struct Cli {
+    /// TOML config file
+    #[arg(short, long, env = "POKER_CONFIG")]
+    pub config: Option<PathBuf>,
+
+    /// Address to listen on, e.g. 127.0.0.1:8080
+    #[arg(long, env = "BIND")]
+    pub bind: Option<SocketAddr>,
+
+    /// Port to listen on (replaces the port of the bind address)
+    #[arg(short, long, env = "PORT")]
+    pub port: Option<u16>,
+
+    /// Directory of the combined static site output
+    #[arg(long, env = "STATIC_DIR")]
+    pub static_dir: Option<PathBuf>,
+
+    /// Directory to keep running games in across restarts
+    #[arg(long, env = "STATE_DIR")]
+    pub state_dir: Option<PathBuf>,
+
+    /// Bearer token for the /admin API (unset = API off)
+    #[arg(long, env = "ADMIN_TOKEN", hide_env_values = true)]
+    pub admin_token: Option<String>,
+
+    /// Log line format
+    #[arg(long, value_enum)]
+    pub log_format: Option<LogFormat>,
+
+    /// Seconds a player has to act
+    #[arg(long)]
+    pub turn_timeout: Option<u32>,
+
+    /// Maximum number of rooms (0 = no limit)
+    #[arg(long)]
+    pub max_rooms: Option<usize>,
+
+    /// Print the effective configuration and exit
+    #[arg(long)]
+    pub print_config: bool,
+}
+
+/// How log lines are formatted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    #[default]
+    Full,
+    Compact,
+    Pretty,
+}
+
+/// The effective server configuration.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub server: ServerConfig,
+    pub timing: TimingConfig,
+    pub limits: LimitsConfig,
+    pub room: RoomDefaults,
+}
+
+/// Where the server listens and what it serves.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServerConfig {
+    pub bind: SocketAddr,
+    pub static_dir: PathBuf,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state_dir: Option<PathBuf>,
+    pub log_format: LogFormat,
+    /// Bearer token for the `/admin` API; empty turns the API off.
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub admin_token: String,
+}
+
+/// Shortest admin token accepted.
+const MIN_ADMIN_TOKEN_LEN: usize = 16;
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            bind: SocketAddr::from(([0, 0, 0, 0], 8080)),
+            static_dir: PathBuf::from("./dist"),
+            state_dir: None,
+            log_format: LogFormat::default(),
+            admin_token: String::new(),
+        }
+    }
+}
+
+/// Timers and pauses.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TimingConfig {
+    /// Seconds a player has to act.  When the time runs out the server
+    /// checks for them if it can and folds otherwise.
+    pub turn_timeout_secs: u32,
+    /// Seconds a disconnected player's seat is held.
+    pub session_grace_secs: u64,
+    /// Pause between the end of a hand and the next deal.
+    pub hand_pause_ms: u64,
+    /// Pause between streets when the board is run out all-in.
+    pub runout_delay_ms: u64,
+    /// How long the hands being played may take to finish on shutdown.
+    pub shutdown_timeout_secs: u64,
+    /// Seconds clients are told to wait before rejoining after a shutdown.
+    pub reconnect_after_secs: u32,
+    /// Seconds a room nobody has joined is kept.
+    pub unjoined_room_timeout_secs: u64,
+    /// Seconds a room is kept once none of its players is connected.
+    pub idle_room_timeout_secs: u64,
+}
+
+impl Default for TimingConfig {
+    fn default() -> Self {
+        Self {
+            turn_timeout_secs: 30,
+            session_grace_secs: 5 * 60,
+            hand_pause_ms: 5000,
+            runout_delay_ms: 1500,
+            shutdown_timeout_secs: 60,
+            reconnect_after_secs: 10,
+            unjoined_room_timeout_secs: 5 * 60,
+            idle_room_timeout_secs: 15 * 60,
+        }
+    }
+}
+
+impl TimingConfig {
+    pub fn session_grace(&self) -> Duration {
+        Duration::from_secs(self.session_grace_secs)
+    }
+
+    pub fn hand_pause(&self) -> Duration {
+        Duration::from_millis(self.hand_pause_ms)
+    }
+
+    pub fn runout_delay(&self) -> Duration {
+        Duration::from_millis(self.runout_delay_ms)
+    }
+
+    pub fn shutdown_timeout(&self) -> Duration {
+        Duration::from_secs(self.shutdown_timeout_secs)
+    }
+
+    pub fn unjoined_room_timeout(&self) -> Duration {
+        Duration::from_secs(self.unjoined_room_timeout_secs)
+    }
+
+    pub fn idle_room_timeout(&self) -> Duration {
+        Duration::from_secs(self.idle_room_timeout_secs)
+    }
+}
+
+/// Caps on what clients can make the server do.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LimitsConfig {
+    /// Rooms that may exist at once, tournament tables included (0 = no
+    /// limit).
+    pub max_rooms: usize,
+    /// Spectators per room (0 = no limit).
+    pub max_spectators: usize,
+    /// Boards dealt to estimate all-in equity.
+    pub equity_iterations: usize,
+    /// Room events kept per room so that a player who rejoins can be sent
+    /// what they missed (0 = always send a snapshot).
+    pub replay_events: usize,
+}
+
+impl Default for LimitsConfig {
+    fn default() -> Self {
+        Self {
+            max_rooms: 1000,
+            max_spectators: 0,
+            equity_iterations: 1000,
+            replay_events: 500,
+        }
+    }
+}
+
+/// Settings new rooms start with that clients do not choose.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RoomDefaults {
+    pub small_blind: u32,
+    pub big_blind: u32,
+    pub allow_spectator_chat: bool,
+    pub allow_late_entry: bool,
+}
+
+impl Default for RoomDefaults {
+    fn default() -> Self {
+        Self {
+            small_blind: 10,
+            big_blind: 20,
+            allow_spectator_chat: true,
+            allow_late_entry: false,
+        }
+    }
+}
+
+impl Config {
+    /// Build the configuration from the defaults, the config file named on
+    /// the command line (if any) and the command-line overrides, then
+    /// validate it.
+    pub fn load(cli: &Cli) -> Result<Self, String> {
+        let mut config = match &cli.config {
+            Some(path) => Self::from_file(path)?,
+            None => Self::default(),
+        };
+        config.apply(cli);
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn from_file(path: &Path) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("Cannot read config file {}: {e}", path.display()))?;
+        toml::from_str(&text).map_err(|e| format!("Invalid config file {}: {e}", path.display()))
+    }
+
+    fn apply(&mut self, cli: &Cli) {
+        if let Some(bind) = cli.bind {
+            self.server.bind = bind;
+        }
+        if let Some(port) = cli.port {
+            self.server.bind.set_port(port);
+        }
+        if let Some(dir) = &cli.static_dir {
+            self.server.static_dir = dir.clone();
+        }
+        if let Some(dir) = &cli.state_dir {
+            self.server.state_dir = Some(dir.clone());
+        }
+        if let Some(token) = &cli.admin_token {
+            self.server.admin_token = token.clone();
+        }
+        if let Some(format) = cli.log_format {
+            self.server.log_format = format;
+        }
+        if let Some(secs) = cli.turn_timeout {
+            self.timing.turn_timeout_secs = secs;
+        }
+        if let Some(max) = cli.max_rooms {
+            self.limits.max_rooms = max;
+        }
+    }
+
+    /// Check every value, reporting all the problems at once.
+    pub fn validate(&self) -> Result<(), String> {
+        let mut errors = Vec::new();
+        let t = &self.timing;
+        if !(5..=600).contains(&t.turn_timeout_secs) {
+            errors.push(format!(
+                "timing.turn_timeout_secs must be between 5 and 600 (got {})",
+                t.turn_timeout_secs
+            ));
+        }
+        if !(10..=86_400).contains(&t.session_grace_secs) {
+            errors.push(format!(
+                "timing.session_grace_secs must be between 10 and 86400 (got {})",
+                t.session_grace_secs
+            ));
+        }
+        if t.hand_pause_ms > 60_000 {
+            errors.push(format!(
+                "timing.hand_pause_ms must be at most 60000 (got {})",
+                t.hand_pause_ms
+            ));
+        }
+        if t.runout_delay_ms > 10_000 {
+            errors.push(format!(
+                "timing.runout_delay_ms must be at most 10000 (got {})",
+                t.runout_delay_ms
+            ));
+        }
+        if t.shutdown_timeout_secs > 3600 {
+            errors.push(format!(
+                "timing.shutdown_timeout_secs must be at most 3600 (got {})",
+                t.shutdown_timeout_secs
+            ));
+        }
+        if t.reconnect_after_secs > 600 {
+            errors.push(format!(
+                "timing.reconnect_after_secs must be at most 600 (got {})",
+                t.reconnect_after_secs
+            ));
+        }
+        if !(10..=86_400).contains(&t.unjoined_room_timeout_secs) {
+            errors.push(format!(
+                "timing.unjoined_room_timeout_secs must be between 10 and 86400 (got {})",
+                t.unjoined_room_timeout_secs
+            ));
+        }
+        if !(10..=604_800).contains(&t.idle_room_timeout_secs) {
+            errors.push(format!(
+                "timing.idle_room_timeout_secs must be between 10 and 604800 (got {})",
+                t.idle_room_timeout_secs
+            ));
+        }
+        if !(100..=100_000).contains(&self.limits.equity_iterations) {
+            errors.push(format!(
+                "limits.equity_iterations must be between 100 and 100000 (got {})",
+                self.limits.equity_iterations
+            ));
+        }
+        if self.limits.replay_events > 100_000 {
+            errors.push(format!(
+                "limits.replay_events must be at most 100000 (got {})",
+                self.limits.replay_events
+            ));
+        }
+        let r = &self.room;
+        if r.small_blind == 0 {
+            errors.push("room.small_blind must be at least 1".to_string());
+        }
+        if r.big_blind < r.small_blind {
+            errors.push(format!(
+                "room.big_blind ({}) must be at least room.small_blind ({})",
+                r.big_blind, r.small_blind
+            ));
+        }
+        let token = &self.server.admin_token;
+        if !token.is_empty() && token.len() < MIN_ADMIN_TOKEN_LEN {
+            errors.push(format!(
+                "server.admin_token must be at least {MIN_ADMIN_TOKEN_LEN} characters"
+            ));
+        }
+        if self.server.state_dir.as_deref() == Some(Path::new("")) {
+            errors.push("server.state_dir must not be empty".to_string());
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(format!("Invalid configuration:\n  {}", errors.join("\n  ")))
+        }
+    }
+
+    /// The configuration as TOML, for printing at startup.  The admin
+    /// token is masked.
+    pub fn to_toml(&self) -> String {
+        let mut shown = self.clone();
+        if !shown.server.admin_token.is_empty() {
+            shown.server.admin_token = "********".to_string();
+        }
+        toml::to_string_pretty(&shown).unwrap_or_else(|e| format!("<unprintable: {e}>"))
+    }
+}
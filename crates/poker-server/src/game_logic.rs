@@ -5,19 +5,18 @@
 //! concrete transport.
 
 use std::collections::HashMap;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use poker_core::poker::{Board, Card, Hand, get_all_cards};
-use poker_core::protocol::{BlindConfig, CardInfo, PlayerAction, ServerMessage, card_to_info};
+use poker_core::protocol::{
+    BlindConfig, CardInfo, GameMode, LedgerEntry, PlayerAction, PlayerInfo, ProtocolError,
+    RebuyConfig, ServerMessage, Standing, card_to_info, payout_for_place,
+};
 use rand::rng;
-use rand::seq::SliceRandom;
+use rand::seq::{IndexedRandom, SliceRandom};
+use serde::{Deserialize, Serialize};
 
-/// Fixed per-turn timer duration in seconds.
-///
-/// When a player's turn begins the server starts a countdown.  If the player
-/// has not acted by the time it reaches zero, the server forces a *check* (if
-/// allowed) or a *fold*.
-pub const TURN_TIMEOUT_SECS: u32 = 30;
+use crate::metrics::METRICS;
 
 // ---------------------------------------------------------------------------
 // Types
@@ -50,6 +49,29 @@ pub struct Player {
     pub current_bet: u32,
     /// Whether the player is sitting out (auto-check/fold each turn).
     pub sitting_out: bool,
+    /// Chips bought mid-hand (cash game buy-ins, tournament rebuys and
+    /// add-ons), added at the start of the next hand.
+    pub pending_buy_in: u32,
+    /// The player asked to leave (cash games) or was kicked by the host and
+    /// will be removed once the current hand is over.
+    pub leaving: bool,
+    /// Stack at the start of the current hand (ranks same-hand busts).
+    pub hand_start_chips: u32,
+    /// Seat index at the table, starting at 0.
+    pub seat: u32,
+    /// Seat the player asked to move to, taken at the start of the next hand.
+    pub pending_seat: Option<u32>,
+}
+
+/// Buy-in bookkeeping for one player (kept after they leave or bust).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LedgerRecord {
+    pub name: String,
+    pub bought_in: u32,
+    pub cashed_out: u32,
+    pub seated: bool,
+    pub rebuys: u32,
+    pub add_on: bool,
 }
 
 /// Game phase.
@@ -63,6 +85,51 @@ pub enum GamePhase {
     Showdown,
 }
 
+/// A stopwatch that only accumulates time while it is running.
+///
+/// Used for the blind-level clock so that host pauses and
+/// waiting-for-players periods do not count towards the next blind increase.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PausableClock {
+    accumulated: Duration,
+    running_since: Option<Instant>,
+}
+
+impl PausableClock {
+    /// A stopped clock showing `elapsed`.
+    pub fn stopped_at(elapsed: Duration) -> Self {
+        Self {
+            accumulated: elapsed,
+            running_since: None,
+        }
+    }
+
+    /// Reset the accumulated time to zero and start running.
+    pub fn restart(&mut self) {
+        self.accumulated = Duration::ZERO;
+        self.running_since = Some(Instant::now());
+    }
+
+    /// Stop accumulating time (no-op if already paused).
+    pub fn pause(&mut self) {
+        if let Some(since) = self.running_since.take() {
+            self.accumulated += since.elapsed();
+        }
+    }
+
+    /// Continue accumulating time (no-op if already running).
+    pub fn resume(&mut self) {
+        if self.running_since.is_none() {
+            self.running_since = Some(Instant::now());
+        }
+    }
+
+    /// Total running time since the last [`restart`](Self::restart).
+    pub fn elapsed(&self) -> Duration {
+        self.accumulated + self.running_since.map(|s| s.elapsed()).unwrap_or_default()
+    }
+}
+
 // ---------------------------------------------------------------------------
 // GameState
 // ---------------------------------------------------------------------------
@@ -70,13 +137,18 @@ pub enum GamePhase {
 /// Server-side game state shared across all connections.
 pub struct GameState {
     pub players: HashMap<u32, Player>,
-    /// Order of play (seat positions).
+    /// Order of play, by seat.  Re-sorted at the start of every hand.
     pub player_order: Vec<u32>,
     pub next_player_id: u32,
     pub game_started: bool,
     pub phase: GamePhase,
     pub hand_number: u32,
     pub dealer_index: usize,
+    /// Seat that had the button last hand; the button moves to the next
+    /// occupied seat.
+    pub dealer_seat: Option<u32>,
+    /// Number of seats at the table.
+    pub table_size: u32,
     pub current_player_index: usize,
     pub pot: u32,
     /// Current bet to match.
@@ -96,8 +168,9 @@ pub struct GameState {
     pub has_acted_this_round: bool,
     /// Configuration for automatic blind increases.
     pub blind_config: BlindConfig,
-    /// When blinds were last increased (or when the game started).
-    pub last_blind_increase: Option<Instant>,
+    /// Running time since blinds were last increased (or since the game
+    /// started).  Frozen while the game is paused or waiting for players.
+    pub blind_clock: PausableClock,
     /// Number of big blinds each player starts with.
     pub starting_bbs: u32,
     /// Whether late entry is allowed (toggled by host).
@@ -110,6 +183,49 @@ pub struct GameState {
     /// (not sitting out). Cleared when a player sits back in and triggers
     /// a new hand.
     pub waiting_for_players: bool,
+    /// True while the host has paused the game.  No actions are accepted and
+    /// no new hands are dealt until the host resumes.
+    pub paused: bool,
+    /// True once the current hand has been resolved and the next one has not
+    /// been dealt yet.
+    pub hand_complete: bool,
+    /// When the current player's turn timer started.
+    pub turn_started_at: Option<Instant>,
+    /// When the hand being played was dealt.
+    pub hand_started_at: Option<Instant>,
+    /// Length of the current player's turn timer in seconds.
+    pub turn_timeout_secs: u32,
+    /// Seconds left on the turn timer when the game was paused.
+    pub paused_turn_remaining: Option<u32>,
+    /// Tournament or cash game.
+    pub game_mode: GameMode,
+    /// Per-player buy-in / cash-out totals (cash games) or entries, rebuys
+    /// and add-ons (tournaments).
+    pub ledger: HashMap<u32, LedgerRecord>,
+    /// Tournament rebuy / add-on rules.
+    pub rebuy_config: RebuyConfig,
+    /// Current blind level, starting at 1.
+    pub blind_level: u32,
+    /// True once the rebuy period has ended (by level or by the host).
+    pub rebuy_period_over: bool,
+    /// True during the add-on break.
+    pub add_on_open: bool,
+    /// Tournament payout percentages per place, best first.
+    pub payouts: Vec<u32>,
+    /// Tournament eliminations, first out first.  Each entry holds the
+    /// players who tied for one finishing place.
+    pub eliminations: Vec<Vec<u32>>,
+    /// True for the tables of a multi-table tournament.  Busted players are
+    /// collected in `pending_busts` and placed by the tournament instead.
+    pub multi_table: bool,
+    /// Multi-table tournaments: bust groups not yet reported to the
+    /// tournament, first out first.
+    pub pending_busts: Vec<Vec<u32>>,
+    /// Multi-table tournaments: the prize pool across all tables.
+    pub shared_prize_pool: Option<u32>,
+    /// Blinds and schedule set by the host during a hand, applied when the
+    /// next hand starts.
+    pub pending_blinds: Option<(u32, u32, BlindConfig)>,
 }
 
 impl Default for GameState {
@@ -122,6 +238,8 @@ impl Default for GameState {
             phase: GamePhase::Lobby,
             hand_number: 0,
             dealer_index: 0,
+            dealer_seat: None,
+            table_size: 9,
             current_player_index: 0,
             pot: 0,
             current_bet: 0,
@@ -135,12 +253,30 @@ impl Default for GameState {
             first_actor_index: None,
             has_acted_this_round: false,
             blind_config: BlindConfig::default(),
-            last_blind_increase: None,
+            blind_clock: PausableClock::default(),
             starting_bbs: 50,
             allow_late_entry: false,
             host_id: 0,
             starting_chips: 0,
             waiting_for_players: false,
+            paused: false,
+            hand_complete: false,
+            turn_started_at: None,
+            hand_started_at: None,
+            turn_timeout_secs: 0,
+            paused_turn_remaining: None,
+            game_mode: GameMode::Tournament,
+            ledger: HashMap::new(),
+            rebuy_config: RebuyConfig::default(),
+            blind_level: 1,
+            rebuy_period_over: false,
+            add_on_open: false,
+            payouts: vec![100],
+            eliminations: Vec::new(),
+            multi_table: false,
+            pending_busts: Vec::new(),
+            shared_prize_pool: None,
+            pending_blinds: None,
         }
     }
 }
@@ -150,12 +286,17 @@ impl GameState {
         Self::default()
     }
 
-    pub fn add_player(&mut self, name: String) -> Player {
-        self.add_player_with_chips(name, None)
+    pub fn add_player(&mut self, name: String, seat: u32) -> Player {
+        self.add_player_with_chips(name, seat, None)
     }
 
     /// Add a player with an optional chip override (used for late entries).
-    pub fn add_player_with_chips(&mut self, name: String, chips_override: Option<u32>) -> Player {
+    pub fn add_player_with_chips(
+        &mut self,
+        name: String,
+        seat: u32,
+        chips_override: Option<u32>,
+    ) -> Player {
         let starting_chips = chips_override.unwrap_or(self.starting_bbs * self.big_blind);
         let player = Player {
             id: self.next_player_id,
@@ -165,16 +306,473 @@ impl GameState {
             hole_cards: None,
             current_bet: 0,
             sitting_out: false,
+            pending_buy_in: 0,
+            leaving: false,
+            hand_start_chips: 0,
+            seat,
+            pending_seat: None,
         };
+        self.ledger.insert(
+            player.id,
+            LedgerRecord {
+                name: player.name.clone(),
+                bought_in: starting_chips,
+                cashed_out: 0,
+                seated: true,
+                rebuys: 0,
+                add_on: false,
+            },
+        );
         self.players.insert(player.id, player.clone());
         self.player_order.push(player.id);
         self.next_player_id += 1;
         player
     }
 
-    pub fn remove_player(&mut self, id: u32) {
-        self.players.remove(&id);
+    /// Remove a player from the game.  Their stack is recorded as cashed out
+    /// in the ledger; a tournament entry is dropped entirely if the game has
+    /// not started yet, and counts as an elimination if it has.  The
+    /// elimination, and the end of the tournament if only one player is
+    /// left, are added to `messages`.
+    ///
+    /// Returns the number of chips the player left with.
+    pub fn remove_player(&mut self, id: u32, messages: &mut Vec<ServerMessage>) -> u32 {
+        let chips = self.players.remove(&id).map(|p| p.chips).unwrap_or(0);
         self.player_order.retain(|&pid| pid != id);
+        if !self.game_mode.is_cash() && !self.game_started {
+            self.ledger.remove(&id);
+        } else if !self.game_mode.is_cash() && chips > 0 && self.multi_table {
+            // The tournament places them with the table's busts.
+            self.pending_busts.push(vec![id]);
+        } else if !self.game_mode.is_cash() && chips > 0 {
+            // Out with chips left: behind everyone still in.
+            let place = self.players_in() + 1;
+            messages.push(ServerMessage::PlayerEliminated {
+                player_id: id,
+                place,
+            });
+            self.eliminations.push(vec![id]);
+        }
+        if let Some(record) = self.ledger.get_mut(&id)
+            && record.seated
+        {
+            record.cashed_out += chips;
+            record.seated = false;
+        }
+        self.finish_if_last_standing(messages);
+        chips
+    }
+
+    /// A player leaves the table (or is kicked).  Between hands they go at
+    /// once; during a hand they are only marked as leaving and go once it is
+    /// resolved, whatever their part in it, because the betting round keeps
+    /// positions in `player_order`.
+    ///
+    /// Returns the chips the player left with, or `None` if they leave after
+    /// the hand.  What their leaving means for a tournament is added to
+    /// `messages` (see [`remove_player`](Self::remove_player)).
+    pub fn leave(&mut self, id: u32, messages: &mut Vec<ServerMessage>) -> Option<u32> {
+        if !self.hand_in_progress() {
+            return Some(self.remove_player(id, messages));
+        }
+        if let Some(player) = self.players.get_mut(&id) {
+            player.leaving = true;
+        }
+        None
+    }
+
+    /// Take a player off this table without settling their stack, so they
+    /// can be seated at another table of the same tournament.
+    pub fn unseat_player(&mut self, id: u32) -> Option<(Player, Option<LedgerRecord>)> {
+        let player = self.players.remove(&id)?;
+        self.player_order.retain(|&pid| pid != id);
+        Some((player, self.ledger.remove(&id)))
+    }
+
+    /// Seat a player moved from another table, keeping their ID and stack.
+    /// They get a random empty seat and are dealt in from the next hand.
+    pub fn seat_player(&mut self, mut player: Player, record: Option<LedgerRecord>) {
+        player.status = PlayerStatus::Waiting;
+        player.hole_cards = None;
+        player.current_bet = 0;
+        player.pending_seat = None;
+        player.seat = self
+            .pick_seat(None)
+            .unwrap_or_else(|_| self.players.values().map(|p| p.seat + 1).max().unwrap_or(0));
+        self.next_player_id = self.next_player_id.max(player.id + 1);
+        if let Some(record) = record {
+            self.ledger.insert(player.id, record);
+        }
+        self.player_order.push(player.id);
+        self.players.insert(player.id, player);
+    }
+
+    /// Whether a player still occupies their seat.  Busted tournament
+    /// players give it up once they can no longer rebuy.
+    fn holds_seat(&self, player: &Player) -> bool {
+        player.chips > 0
+            || player.pending_buy_in > 0
+            || !self.game_started
+            || self.game_mode.is_cash()
+            || self.rebuy_open()
+            || (self.hand_in_progress() && player.status == PlayerStatus::AllIn)
+    }
+
+    /// Whether `seat` is occupied or promised to a player changing seats.
+    pub fn seat_taken(&self, seat: u32) -> bool {
+        self.players
+            .values()
+            .any(|p| p.pending_seat == Some(seat) || (p.seat == seat && self.holds_seat(p)))
+    }
+
+    /// Pick a seat for a new player: the requested one if it is empty, or a
+    /// random empty seat.
+    pub fn pick_seat(&self, requested: Option<u32>) -> Result<u32, ProtocolError> {
+        if let Some(seat) = requested {
+            if seat >= self.table_size {
+                return Err(ProtocolError::NoSuchSeat { seat });
+            }
+            if self.seat_taken(seat) {
+                return Err(ProtocolError::SeatTaken { seat });
+            }
+            return Ok(seat);
+        }
+        let free: Vec<u32> = (0..self.table_size)
+            .filter(|&seat| !self.seat_taken(seat))
+            .collect();
+        free.choose(&mut rng())
+            .copied()
+            .ok_or(ProtocolError::RoomFull)
+    }
+
+    /// Move a player to an empty seat, or queue the move for the next hand
+    /// if they are involved in the current one.
+    ///
+    /// Returns `true` when the move is pending.
+    pub fn change_seat(&mut self, player_id: u32, seat: u32) -> Result<bool, ProtocolError> {
+        let current = self
+            .players
+            .get(&player_id)
+            .ok_or(ProtocolError::PlayerNotFound)?;
+        if current.seat == seat {
+            return Err(ProtocolError::not_allowed("You are already in that seat"));
+        }
+        self.pick_seat(Some(seat))?;
+        let in_hand = self.hand_in_progress();
+        let Some(player) = self.players.get_mut(&player_id) else {
+            return Err(ProtocolError::PlayerNotFound);
+        };
+        let pending = in_hand
+            && matches!(
+                player.status,
+                PlayerStatus::Active | PlayerStatus::AllIn | PlayerStatus::Folded
+            );
+        if pending {
+            player.pending_seat = Some(seat);
+        } else {
+            player.seat = seat;
+            player.pending_seat = None;
+        }
+        Ok(pending)
+    }
+
+    /// Deal out the seats at random (a multi-table tournament draws seats
+    /// when it starts).
+    pub fn reseat_randomly(&mut self) -> Vec<ServerMessage> {
+        let mut seats: Vec<u32> =
+            (0..self.table_size.max(self.player_order.len() as u32)).collect();
+        seats.shuffle(&mut rng());
+        let mut messages = Vec::new();
+        for (&id, seat) in self.player_order.iter().zip(seats) {
+            if let Some(player) = self.players.get_mut(&id) {
+                player.seat = seat;
+                player.pending_seat = None;
+                messages.push(ServerMessage::SeatChanged {
+                    player_id: id,
+                    seat,
+                    pending: false,
+                });
+            }
+        }
+        messages
+    }
+
+    /// Whether a hand is being played right now (blinds posted, not yet
+    /// resolved).
+    pub fn hand_in_progress(&self) -> bool {
+        self.game_started && !self.hand_complete && self.hand_number > 0
+    }
+
+    /// Cash games: buy `amount` chips for a player.
+    ///
+    /// The resulting stack must stay within the room's buy-in range (a
+    /// rebuy after busting must reach at least the minimum).  If the player
+    /// is involved in the current hand the chips are held until the next one.
+    ///
+    /// Returns `Ok(true)` when the buy-in is pending, `Ok(false)` when the
+    /// chips were added immediately.
+    pub fn buy_in(&mut self, player_id: u32, amount: u32) -> Result<bool, ProtocolError> {
+        let Some((min, max)) = self.game_mode.buy_in_range(self.big_blind) else {
+            return Err(ProtocolError::not_allowed(
+                "Buy-ins are only available in cash games",
+            ));
+        };
+        let player = self
+            .players
+            .get(&player_id)
+            .ok_or(ProtocolError::PlayerNotFound)?;
+        if player.leaving {
+            return Err(ProtocolError::not_allowed("You are leaving the table"));
+        }
+        let stack = player.chips + player.pending_buy_in;
+        if stack >= max {
+            return Err(ProtocolError::not_allowed(format!(
+                "Maximum stack is {max}"
+            )));
+        }
+        // The amount that takes the stack into the buy-in range.
+        let (least, most) = (min.saturating_sub(stack).max(1), max - stack);
+        if amount < least || amount > most {
+            return Err(ProtocolError::InvalidAmount {
+                min: least,
+                max: most,
+            });
+        }
+
+        Ok(self.credit_chips(player_id, amount))
+    }
+
+    /// Whether tournament rebuys are allowed right now.
+    pub fn rebuy_open(&self) -> bool {
+        self.rebuy_config.is_enabled() && self.game_started && !self.rebuy_period_over
+    }
+
+    /// Whether the rebuy period runs out with the blind increase due at the
+    /// start of the next hand.
+    pub fn rebuy_period_due(&self) -> bool {
+        self.rebuy_open()
+            && self.blind_config.is_enabled()
+            && self.blind_level >= self.rebuy_config.levels
+            && self.blind_clock.elapsed().as_secs() >= self.blind_config.interval_secs
+    }
+
+    /// Close the rebuy period.  If add-ons are offered the game is paused for
+    /// the add-on break; the caller must invalidate any running turn timer.
+    pub fn end_rebuy_period(&mut self) -> Vec<ServerMessage> {
+        if !self.rebuy_open() {
+            return Vec::new();
+        }
+        self.rebuy_period_over = true;
+        self.add_on_open = self.rebuy_config.add_on;
+        let mut messages = vec![ServerMessage::RebuyPeriodEnded {
+            add_on_open: self.add_on_open,
+        }];
+        if self.add_on_open && !self.paused {
+            self.freeze_turn_timer();
+            self.set_paused(true);
+            messages.push(ServerMessage::GamePaused);
+        }
+        messages
+    }
+
+    /// End the add-on break (called when the host resumes the game).
+    pub fn close_add_on(&mut self) -> Option<ServerMessage> {
+        if !self.add_on_open {
+            return None;
+        }
+        self.add_on_open = false;
+        Some(ServerMessage::AddOnClosed)
+    }
+
+    /// Tournaments: rebuy one starting stack for a player.
+    ///
+    /// Returns the amount and whether it is pending until the next hand.
+    pub fn rebuy(&mut self, player_id: u32) -> Result<(u32, bool), ProtocolError> {
+        if !self.rebuy_open() {
+            return Err(ProtocolError::not_allowed("Rebuys are not available"));
+        }
+        let amount = self.starting_chips;
+        let max_rebuys = self.rebuy_config.max_rebuys;
+        let player = self
+            .players
+            .get(&player_id)
+            .ok_or(ProtocolError::PlayerNotFound)?;
+        if player.chips + player.pending_buy_in > amount {
+            return Err(ProtocolError::not_allowed(
+                "You can only rebuy with a stack at or below the starting stack",
+            ));
+        }
+        let record = self
+            .ledger
+            .get_mut(&player_id)
+            .ok_or(ProtocolError::PlayerNotFound)?;
+        if max_rebuys > 0 && record.rebuys >= max_rebuys {
+            return Err(ProtocolError::not_allowed(format!(
+                "Rebuy limit reached ({max_rebuys})"
+            )));
+        }
+        record.rebuys += 1;
+        // Back in the tournament: forget the finishing place.
+        for group in &mut self.eliminations {
+            group.retain(|&id| id != player_id);
+        }
+        self.eliminations.retain(|group| !group.is_empty());
+        Ok((amount, self.credit_chips(player_id, amount)))
+    }
+
+    /// Tournaments: take the single add-on during the break.
+    ///
+    /// Returns the amount and whether it is pending until the next hand.
+    pub fn add_on(&mut self, player_id: u32) -> Result<(u32, bool), ProtocolError> {
+        if !self.add_on_open {
+            return Err(ProtocolError::not_allowed("The add-on is not available"));
+        }
+        let amount = self.starting_chips;
+        let player = self
+            .players
+            .get(&player_id)
+            .ok_or(ProtocolError::PlayerNotFound)?;
+        if player.chips + player.pending_buy_in == 0 {
+            return Err(ProtocolError::not_allowed(
+                "Eliminated players cannot take the add-on",
+            ));
+        }
+        let record = self
+            .ledger
+            .get_mut(&player_id)
+            .ok_or(ProtocolError::PlayerNotFound)?;
+        if record.add_on {
+            return Err(ProtocolError::not_allowed(
+                "You have already taken the add-on",
+            ));
+        }
+        record.add_on = true;
+        Ok((amount, self.credit_chips(player_id, amount)))
+    }
+
+    /// Add bought chips to a player's stack, or hold them until the next
+    /// hand if the player is involved in the current one.
+    ///
+    /// Returns `true` when the chips are pending.
+    fn credit_chips(&mut self, player_id: u32, amount: u32) -> bool {
+        let in_hand = self.hand_in_progress();
+        let Some(player) = self.players.get_mut(&player_id) else {
+            return false;
+        };
+        let pending =
+            in_hand && matches!(player.status, PlayerStatus::Active | PlayerStatus::AllIn);
+        if pending {
+            player.pending_buy_in += amount;
+        } else {
+            player.chips += amount;
+            if let Some(record) = self.ledger.get_mut(&player_id) {
+                record.bought_in += amount;
+            }
+            if !self.player_order.contains(&player_id) {
+                self.player_order.push(player_id);
+            }
+        }
+        pending
+    }
+
+    /// Add the chips a player bought during a hand to their stack, and to
+    /// the ledger.  Returns the new stack.
+    fn credit_pending_buy_in(&mut self, player_id: u32) -> u32 {
+        let Some(player) = self.players.get_mut(&player_id) else {
+            return 0;
+        };
+        let amount = std::mem::take(&mut player.pending_buy_in);
+        player.chips += amount;
+        if let Some(record) = self.ledger.get_mut(&player_id) {
+            record.bought_in += amount;
+        }
+        player.chips
+    }
+
+    /// Total chips paid into the game: entries, rebuys and add-ons
+    /// (including those still pending).
+    pub fn prize_pool(&self) -> u32 {
+        if let Some(pool) = self.shared_prize_pool {
+            return pool;
+        }
+        let pending: u32 = self.players.values().map(|p| p.pending_buy_in).sum();
+        self.ledger.values().map(|r| r.bought_in).sum::<u32>() + pending
+    }
+
+    /// Final tournament standings with `winners` sharing first place,
+    /// followed by the eliminated players, last out first.
+    pub fn final_standings(&self, winners: &[u32]) -> Vec<Standing> {
+        let pool = self.prize_pool();
+        let name = |id: u32| {
+            self.ledger
+                .get(&id)
+                .map(|r| r.name.clone())
+                .or_else(|| self.players.get(&id).map(|p| p.name.clone()))
+                .unwrap_or_else(|| format!("Player #{id}"))
+        };
+
+        let mut standings = Vec::new();
+        let mut place = 1;
+        for group in
+            std::iter::once(winners).chain(self.eliminations.iter().rev().map(Vec::as_slice))
+        {
+            let tied = group.len() as u32;
+            let payout = payout_for_place(pool, &self.payouts, place, tied);
+            for &player_id in group {
+                standings.push(Standing {
+                    place,
+                    player_id,
+                    name: name(player_id),
+                    payout,
+                });
+            }
+            place += tied;
+        }
+        standings
+    }
+
+    /// Snapshot of the session ledger, ordered by player ID.
+    pub fn ledger_entries(&self) -> Vec<LedgerEntry> {
+        let mut entries: Vec<LedgerEntry> = self
+            .ledger
+            .iter()
+            .map(|(&player_id, record)| LedgerEntry {
+                player_id,
+                name: record.name.clone(),
+                bought_in: record.bought_in,
+                cashed_out: record.cashed_out,
+                stack: self.players.get(&player_id).map(|p| p.chips).unwrap_or(0),
+                seated: record.seated,
+                rebuys: record.rebuys,
+                add_on: record.add_on,
+            })
+            .collect();
+        entries.sort_by_key(|e| e.player_id);
+        entries
+    }
+
+    /// Public info on every player, for `PlayerList` and snapshots.
+    pub fn player_infos(&self) -> Vec<PlayerInfo> {
+        self.players
+            .values()
+            .map(|p| PlayerInfo {
+                id: p.id,
+                name: p.name.clone(),
+                chips: p.chips,
+                seat: p.seat,
+            })
+            .collect()
+    }
+
+    /// Every dealt player's hole cards, for the spectators' streamer view.
+    pub fn dealt_hole_cards(&self) -> Vec<(u32, [CardInfo; 2])> {
+        self.players
+            .values()
+            .filter_map(|p| {
+                p.hole_cards
+                    .map(|(c1, c2)| (p.id, [card_to_info(&c1), card_to_info(&c2)]))
+            })
+            .collect()
     }
 
     pub fn player_count(&self) -> usize {
@@ -195,6 +793,249 @@ impl GameState {
         }
     }
 
+    /// Pause or resume the game (host command).
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+        self.sync_blind_clock();
+    }
+
+    /// Enter or leave the waiting-for-players state.
+    pub fn set_waiting_for_players(&mut self, waiting: bool) {
+        self.waiting_for_players = waiting;
+        self.sync_blind_clock();
+    }
+
+    /// Change the blinds and the increase schedule (host command).  Applied
+    /// at once outside a hand, otherwise held until the next hand starts.
+    ///
+    /// Returns `true` if the change is pending.
+    pub fn change_blinds(&mut self, small_blind: u32, big_blind: u32, config: BlindConfig) -> bool {
+        if self.hand_in_progress() {
+            self.pending_blinds = Some((small_blind, big_blind, config));
+            return true;
+        }
+        self.pending_blinds = None;
+        self.set_blinds(small_blind, big_blind, config);
+        false
+    }
+
+    /// Apply new blinds; the next increase is a full interval away.
+    fn set_blinds(&mut self, small_blind: u32, big_blind: u32, config: BlindConfig) {
+        self.small_blind = small_blind;
+        self.big_blind = big_blind;
+        self.blind_config = config;
+        self.blind_clock.restart();
+        self.sync_blind_clock();
+    }
+
+    /// End the game early (host command).
+    ///
+    /// A hand in progress is called off: everyone dealt in gets back the
+    /// stack they started it with.  Tournaments rank the players still in
+    /// by their stacks (equal stacks share a place) ahead of everyone
+    /// already out and pay out by place; cash games just report the final
+    /// ledger.
+    pub fn end_game(&mut self) -> Vec<ServerMessage> {
+        let mut messages = vec![ServerMessage::GameEnded];
+        let called_off = self.hand_in_progress();
+        for player in self.players.values_mut() {
+            if called_off
+                && matches!(
+                    player.status,
+                    PlayerStatus::Active | PlayerStatus::Folded | PlayerStatus::AllIn
+                )
+            {
+                player.chips = player.hand_start_chips;
+            }
+            player.status = PlayerStatus::Waiting;
+            player.hole_cards = None;
+            player.current_bet = 0;
+        }
+        let ids: Vec<u32> = self.players.keys().copied().collect();
+        for player_id in ids {
+            let chips = self.credit_pending_buy_in(player_id);
+            messages.push(ServerMessage::ChipUpdate { player_id, chips });
+        }
+        self.pot = 0;
+        self.current_bet = 0;
+        self.community_cards.clear();
+        self.hand_complete = true;
+        self.turn_started_at = None;
+        self.hand_started_at = None;
+        self.paused_turn_remaining = None;
+        self.pending_blinds = None;
+        self.game_started = false;
+        self.phase = GamePhase::Lobby;
+        self.paused = false;
+        self.waiting_for_players = false;
+        self.add_on_open = false;
+        self.sync_blind_clock();
+        self.remove_leaving_players(&mut messages);
+
+        if self.game_mode.is_cash() {
+            messages.push(ServerMessage::SessionLedger {
+                entries: self.ledger_entries(),
+            });
+            return messages;
+        }
+
+        // Smallest stacks first, like eliminations.
+        let mut stacks: Vec<(u32, u32)> = self
+            .players
+            .values()
+            .filter(|p| p.chips > 0)
+            .map(|p| (p.chips, p.id))
+            .collect();
+        stacks.sort();
+        let mut groups: Vec<Vec<u32>> = Vec::new();
+        let mut last_chips = None;
+        for &(chips, id) in &stacks {
+            match groups.last_mut() {
+                Some(group) if last_chips == Some(chips) => group.push(id),
+                _ => groups.push(vec![id]),
+            }
+            last_chips = Some(chips);
+        }
+        // Anyone level with the chip leader shares first place.
+        let Some(leaders) = groups.pop() else {
+            return messages;
+        };
+        self.eliminations.extend(groups);
+        let prize_pool = self.prize_pool();
+        messages.push(ServerMessage::PrizePool { total: prize_pool });
+        messages.push(ServerMessage::FinalStandings {
+            standings: self.final_standings(&leaders),
+            prize_pool,
+        });
+        messages
+    }
+
+    /// Players who asked to leave or were kicked mid-hand go once the hand
+    /// is over, with whatever they have left.
+    fn remove_leaving_players(&mut self, messages: &mut Vec<ServerMessage>) {
+        let leaving: Vec<u32> = self
+            .players
+            .values()
+            .filter(|p| p.leaving)
+            .map(|p| p.id)
+            .collect();
+        for player_id in leaving {
+            let mut outcome = Vec::new();
+            let amount = self.remove_player(player_id, &mut outcome);
+            if self.game_mode.is_cash() {
+                messages.push(ServerMessage::PlayerCashedOut { player_id, amount });
+            }
+            messages.push(ServerMessage::PlayerLeft { player_id });
+            messages.extend(outcome);
+        }
+    }
+
+    /// Number of players still in the game: those with chips, bought or
+    /// pending.
+    fn players_in(&self) -> u32 {
+        self.players
+            .values()
+            .filter(|p| p.chips + p.pending_buy_in > 0)
+            .count() as u32
+    }
+
+    /// Single-table tournaments: end the game once only one player has
+    /// chips left.
+    fn finish_if_last_standing(&mut self, messages: &mut Vec<ServerMessage>) {
+        if self.game_mode.is_cash()
+            || self.multi_table
+            || !self.game_started
+            || self.hand_in_progress()
+            || self.players_in() != 1
+        {
+            return;
+        }
+        let Some(winner) = self
+            .players
+            .values()
+            .find(|p| p.chips + p.pending_buy_in > 0)
+        else {
+            return;
+        };
+        let (winner_id, winner_name) = (winner.id, winner.name.clone());
+        let prize_pool = self.prize_pool();
+        messages.push(ServerMessage::PrizePool { total: prize_pool });
+        messages.push(ServerMessage::GameOver {
+            winner_id,
+            winner_name,
+        });
+        messages.push(ServerMessage::FinalStandings {
+            standings: self.final_standings(&[winner_id]),
+            prize_pool,
+        });
+        self.game_started = false;
+        self.phase = GamePhase::Lobby;
+        self.add_on_open = false;
+        self.sync_blind_clock();
+    }
+
+    /// Record how long the hand that just ended took.
+    fn record_hand_finished(&mut self) {
+        if let Some(started) = self.hand_started_at.take() {
+            METRICS.hand_duration.observe(started.elapsed());
+        }
+    }
+
+    /// Run the blind clock only while hands are actually being played.
+    fn sync_blind_clock(&mut self) {
+        if self.game_started && !self.paused && !self.waiting_for_players {
+            self.blind_clock.resume();
+        } else {
+            self.blind_clock.pause();
+        }
+    }
+
+    /// Record that a turn timer of `timeout_secs` has just started.
+    pub fn start_turn_timer(&mut self, timeout_secs: u32) {
+        self.turn_started_at = Some(Instant::now());
+        self.turn_timeout_secs = timeout_secs;
+    }
+
+    /// Count a betting action and how long the player took over it.
+    pub fn record_action(&self, action: PlayerAction) {
+        METRICS.action(action);
+        if let Some(started) = self.turn_started_at {
+            METRICS.time_to_act.observe(started.elapsed());
+        }
+    }
+
+    /// Freeze the running turn timer, remembering how many seconds were left.
+    pub fn freeze_turn_timer(&mut self) {
+        if let Some(started) = self.turn_started_at.take() {
+            let elapsed = started.elapsed().as_secs() as u32;
+            self.paused_turn_remaining =
+                Some(self.turn_timeout_secs.saturating_sub(elapsed).max(1));
+        }
+    }
+
+    /// Seconds left on the running (or frozen) turn timer.
+    pub fn turn_secs_left(&self) -> Option<u32> {
+        match self.turn_started_at {
+            Some(started) => Some(
+                self.turn_timeout_secs
+                    .saturating_sub(started.elapsed().as_secs() as u32),
+            ),
+            None => self.paused_turn_remaining,
+        }
+    }
+
+    /// The player who has to act in the hand being played, if any.
+    pub fn player_to_act(&self) -> Option<u32> {
+        if !self.hand_in_progress() {
+            return None;
+        }
+        self.current_player_id().filter(|id| {
+            self.players
+                .get(id)
+                .is_some_and(|p| p.status == PlayerStatus::Active)
+        })
+    }
+
     /// Check whether the current player is sitting out.
     pub fn is_current_player_sitting_out(&self) -> bool {
         self.current_player_id()
@@ -241,17 +1082,58 @@ impl GameState {
     pub fn start_new_hand(&mut self) -> Vec<ServerMessage> {
         let mut messages = Vec::new();
 
-        // Check if blinds should increase.
-        if self.blind_config.is_enabled() {
-            let should_increase = match self.last_blind_increase {
-                Some(last) => last.elapsed().as_secs() >= self.blind_config.interval_secs,
-                None => false, // first hand — initialised on game start
-            };
+        // Credit buy-ins, rebuys and add-ons made during the last hand.
+        let topped_up: Vec<u32> = self
+            .players
+            .values()
+            .filter(|p| p.pending_buy_in > 0)
+            .map(|p| p.id)
+            .collect();
+        for id in topped_up {
+            let chips = self.credit_pending_buy_in(id);
+            messages.push(ServerMessage::ChipUpdate {
+                player_id: id,
+                chips,
+            });
+            if !self.player_order.contains(&id) {
+                self.player_order.push(id);
+            }
+        }
+
+        // Carry out seat changes asked for during the last hand.
+        for player in self.players.values_mut() {
+            if let Some(seat) = player.pending_seat.take() {
+                player.seat = seat;
+                messages.push(ServerMessage::SeatChanged {
+                    player_id: player.id,
+                    seat,
+                    pending: false,
+                });
+            }
+        }
+
+        // Blinds set by the host replace the schedule; otherwise check if
+        // blinds should increase.
+        if let Some((small_blind, big_blind, blind_config)) = self.pending_blinds.take() {
+            self.set_blinds(small_blind, big_blind, blind_config);
+            messages.push(ServerMessage::BlindsChanged {
+                small_blind,
+                big_blind,
+                blind_config,
+                pending: false,
+            });
+        } else if self.blind_config.is_enabled() {
+            // The clock is only started on game start, so the first hand
+            // never sees an increase.
+            let should_increase =
+                self.blind_clock.elapsed().as_secs() >= self.blind_config.interval_secs;
             if should_increase {
                 let pct = self.blind_config.increase_percent;
                 self.small_blind = self.small_blind + (self.small_blind * pct).div_ceil(100);
                 self.big_blind = self.big_blind + (self.big_blind * pct).div_ceil(100);
-                self.last_blind_increase = Some(Instant::now());
+                self.blind_level += 1;
+                self.blind_clock.restart();
+                self.sync_blind_clock();
                 messages.push(ServerMessage::BlindsIncreased {
                     small_blind: self.small_blind,
                     big_blind: self.big_blind,
@@ -275,18 +1157,33 @@ impl GameState {
             }
             player.hole_cards = None;
             player.current_bet = 0;
+            player.hand_start_chips = player.chips;
         }
 
         // Remove eliminated players from order
         self.player_order
             .retain(|&id| self.players.get(&id).map(|p| p.chips > 0).unwrap_or(false));
+        let players = &self.players;
+        self.player_order.sort_by_key(|id| players[id].seat);
 
         if self.player_order.len() < 2 {
             return messages;
         }
 
-        // Move dealer button
-        self.dealer_index = (self.dealer_index + 1) % self.player_order.len();
+        self.hand_complete = false;
+        self.turn_started_at = None;
+        self.paused_turn_remaining = None;
+
+        // Move the button to the next occupied seat.
+        self.dealer_index = match self.dealer_seat {
+            Some(last) => self
+                .player_order
+                .iter()
+                .position(|id| self.players[id].seat > last)
+                .unwrap_or(0),
+            None => 1 % self.player_order.len(),
+        };
+        self.dealer_seat = Some(self.players[&self.player_order[self.dealer_index]].seat);
 
         // Determine blinds positions
         let sb_index = (self.dealer_index + 1) % self.player_order.len();
@@ -309,6 +1206,9 @@ impl GameState {
         self.first_actor_index = Some(self.current_player_index);
         self.has_acted_this_round = false;
 
+        self.hand_started_at = Some(Instant::now());
+        METRICS.hands_dealt.inc();
+
         messages.push(ServerMessage::NewHand {
             hand_number: self.hand_number,
             dealer_id,
@@ -546,6 +1446,8 @@ impl GameState {
         }
 
         if hands_to_show.is_empty() {
+            self.hand_complete = true;
+            self.record_hand_finished();
             return messages;
         }
 
@@ -623,26 +1525,60 @@ impl GameState {
             });
         }
 
-        for player in self.players.values() {
-            if player.chips == 0 && player.status != PlayerStatus::Out {
-                messages.push(ServerMessage::PlayerEliminated {
-                    player_id: player.id,
-                });
-            }
+        self.hand_complete = true;
+        self.turn_started_at = None;
+        self.record_hand_finished();
+        self.pot = 0;
+
+        if self.game_mode.is_cash() {
+            self.remove_leaving_players(&mut messages);
+            messages.push(ServerMessage::SessionLedger {
+                entries: self.ledger_entries(),
+            });
+            return messages;
         }
 
-        let remaining: Vec<&Player> = self.players.values().filter(|p| p.chips > 0).collect();
+        // A rebuy made during the hand keeps an all-in player alive.
+        let mut busted: Vec<(u32, u32)> = self
+            .players
+            .values()
+            .filter(|p| p.chips + p.pending_buy_in == 0 && p.status != PlayerStatus::Out)
+            .map(|p| (p.id, p.hand_start_chips))
+            .collect();
 
-        if remaining.len() == 1 {
-            messages.push(ServerMessage::GameOver {
-                winner_id: remaining[0].id,
-                winner_name: remaining[0].name.clone(),
-            });
-            self.game_started = false;
-            self.phase = GamePhase::Lobby;
+        // Players leaving with chips go after this hand's busts, so they
+        // still count as survivors here.
+        let survivors = self.players_in();
+
+        // Players busting in the same hand finish in order of their starting
+        // stacks (bigger stack, better place); equal stacks share a place.
+        busted.sort_by_key(|&(id, start)| (start, id));
+        let mut groups: Vec<Vec<u32>> = Vec::new();
+        let mut last_start = None;
+        for &(id, start) in &busted {
+            match groups.last_mut() {
+                Some(group) if last_start == Some(start) => group.push(id),
+                _ => groups.push(vec![id]),
+            }
+            last_start = Some(start);
+        }
+        if self.multi_table {
+            // Places depend on the other tables; the tournament settles them.
+            self.pending_busts.extend(groups);
+            self.remove_leaving_players(&mut messages);
+            return messages;
+        }
+        let mut place = survivors + 1;
+        for group in groups.iter().rev() {
+            for &player_id in group {
+                messages.push(ServerMessage::PlayerEliminated { player_id, place });
+            }
+            place += group.len() as u32;
         }
+        self.eliminations.extend(groups);
 
-        self.pot = 0;
+        self.remove_leaving_players(&mut messages);
+        self.finish_if_last_standing(&mut messages);
         messages
     }
 
@@ -695,3 +1631,342 @@ impl GameState {
         actions
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A table of `n` players in seats 0.., with the game started but no
+    /// hand dealt yet.
+    fn table(n: u32, game_mode: GameMode) -> GameState {
+        let mut gs = GameState {
+            game_mode,
+            ..GameState::default()
+        };
+        for seat in 0..n {
+            gs.add_player(format!("P{}", seat + 1), seat);
+        }
+        gs.starting_chips = gs.starting_bbs * gs.big_blind;
+        gs.game_started = true;
+        gs
+    }
+
+    fn fold(gs: &mut GameState, player_id: u32) {
+        assert_eq!(gs.current_player_id(), Some(player_id));
+        gs.players.get_mut(&player_id).unwrap().status = PlayerStatus::Folded;
+        gs.has_acted_this_round = true;
+        gs.next_player();
+    }
+
+    #[test]
+    fn folded_player_leaving_mid_hand_keeps_the_turn_order() {
+        let mut gs = table(4, cash());
+        gs.start_new_hand();
+        let first = gs.current_player_id().unwrap();
+        fold(&mut gs, first);
+        let next = gs.current_player_id().unwrap();
+        assert_ne!(next, first);
+
+        assert_eq!(gs.leave(first, &mut Vec::new()), None);
+        assert!(gs.players.contains_key(&first));
+        assert_eq!(gs.current_player_id(), Some(next));
+        assert_eq!(gs.player_to_act(), Some(next));
+
+        // Gone once the hand is over.
+        gs.phase = GamePhase::River;
+        let messages = gs.resolve_hand();
+        assert!(!gs.players.contains_key(&first));
+        assert!(!gs.player_order.contains(&first));
+        assert!(
+            messages.iter().any(
+                |m| matches!(m, ServerMessage::PlayerLeft { player_id } if *player_id == first)
+            )
+        );
+    }
+
+    fn standings(messages: &[ServerMessage]) -> Vec<(u32, u32, u32)> {
+        messages
+            .iter()
+            .find_map(|m| match m {
+                ServerMessage::FinalStandings { standings, .. } => Some(
+                    standings
+                        .iter()
+                        .map(|s| (s.place, s.player_id, s.payout))
+                        .collect(),
+                ),
+                _ => None,
+            })
+            .expect("final standings")
+    }
+
+    #[test]
+    fn tied_chip_leaders_share_first_place_when_the_game_is_ended() {
+        let mut gs = table(3, GameMode::Tournament);
+        gs.payouts = vec![50, 30, 20];
+        for (id, chips) in [(1, 500), (2, 500), (3, 200)] {
+            gs.players.get_mut(&id).unwrap().chips = chips;
+        }
+        // Pool of 3000: first and second place split between the leaders.
+        let messages = gs.end_game();
+        assert_eq!(
+            standings(&messages),
+            vec![(1, 1, 1200), (1, 2, 1200), (3, 3, 600)]
+        );
+    }
+
+    fn cash() -> GameMode {
+        GameMode::Cash {
+            min_buy_in_bbs: 20,
+            max_buy_in_bbs: 100,
+        }
+    }
+
+    /// Wind the hand up with player 1 the only one left in it.
+    fn bust(gs: &mut GameState, busted: &[(u32, u32)]) -> Vec<ServerMessage> {
+        for &(id, start) in busted {
+            let player = gs.players.get_mut(&id).unwrap();
+            player.chips = 0;
+            player.hand_start_chips = start;
+            player.status = PlayerStatus::Folded;
+        }
+        for player in gs.players.values_mut() {
+            if player.id != 1 && player.status == PlayerStatus::Active {
+                player.status = PlayerStatus::Folded;
+            }
+        }
+        gs.resolve_hand()
+    }
+
+    fn eliminated(messages: &[ServerMessage]) -> Vec<(u32, u32)> {
+        messages
+            .iter()
+            .filter_map(|m| match *m {
+                ServerMessage::PlayerEliminated { player_id, place } => Some((player_id, place)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn same_hand_busts_are_placed_by_starting_stack() {
+        let mut gs = table(6, GameMode::Tournament);
+        gs.start_new_hand();
+        // Players 1 and 6 survive, so the busts take places 3 to 6.
+        let messages = bust(&mut gs, &[(2, 300), (3, 100), (4, 300), (5, 700)]);
+        assert_eq!(eliminated(&messages), vec![(5, 3), (2, 4), (4, 4), (3, 6)]);
+        assert_eq!(gs.eliminations, vec![vec![3], vec![2, 4], vec![5]]);
+        assert!(gs.game_started);
+    }
+
+    #[test]
+    fn last_player_standing_wins_the_tournament() {
+        let mut gs = table(3, GameMode::Tournament);
+        gs.payouts = vec![70, 30];
+        gs.start_new_hand();
+        let messages = bust(&mut gs, &[(2, 500), (3, 500)]);
+        assert_eq!(eliminated(&messages), vec![(2, 2), (3, 2)]);
+        assert!(
+            messages
+                .iter()
+                .any(|m| matches!(m, ServerMessage::GameOver { winner_id, .. } if *winner_id == 1))
+        );
+        assert_eq!(
+            standings(&messages),
+            vec![(1, 1, 2100), (2, 2, 450), (2, 3, 450)]
+        );
+        assert!(!gs.game_started);
+    }
+
+    #[test]
+    fn pending_rebuy_keeps_an_all_in_player_alive() {
+        let mut gs = table(3, GameMode::Tournament);
+        gs.start_new_hand();
+        gs.players.get_mut(&2).unwrap().pending_buy_in = 1000;
+        let messages = bust(&mut gs, &[(2, 400), (3, 200)]);
+        assert_eq!(eliminated(&messages), vec![(3, 3)]);
+    }
+
+    #[test]
+    fn cash_buy_ins_stay_within_the_range() {
+        // 20 to 100 big blinds of 20: 400 to 2000 chips; everyone has 1000.
+        let mut gs = table(3, cash());
+        assert_eq!(
+            gs.buy_in(1, 1001),
+            Err(ProtocolError::InvalidAmount { min: 1, max: 1000 })
+        );
+        assert_eq!(gs.buy_in(1, 1000), Ok(false));
+        assert_eq!(gs.players[&1].chips, 2000);
+        assert!(matches!(
+            gs.buy_in(1, 1),
+            Err(ProtocolError::NotAllowed { .. })
+        ));
+
+        // A busted player has to get back to the minimum.
+        gs.players.get_mut(&2).unwrap().chips = 0;
+        assert_eq!(
+            gs.buy_in(2, 300),
+            Err(ProtocolError::InvalidAmount {
+                min: 400,
+                max: 2000
+            })
+        );
+        assert_eq!(gs.buy_in(2, 400), Ok(false));
+        assert_eq!(gs.ledger[&2].bought_in, 1400);
+
+        // Chips bought during a hand wait for the next one.
+        gs.start_new_hand();
+        assert_eq!(gs.buy_in(3, 500), Ok(true));
+        assert_eq!(gs.players[&3].pending_buy_in, 500);
+
+        let mut gs = table(2, GameMode::Tournament);
+        assert!(matches!(
+            gs.buy_in(1, 500),
+            Err(ProtocolError::NotAllowed { .. })
+        ));
+    }
+
+    #[test]
+    fn rebuys_need_a_short_stack_and_a_rebuy_left() {
+        let mut gs = table(3, GameMode::Tournament);
+        assert!(gs.rebuy(1).is_err());
+
+        gs.rebuy_config = RebuyConfig {
+            levels: 2,
+            max_rebuys: 1,
+            add_on: false,
+        };
+        gs.players.get_mut(&1).unwrap().chips = 1001;
+        assert!(gs.rebuy(1).is_err());
+        gs.players.get_mut(&1).unwrap().chips = 0;
+        gs.eliminations.push(vec![1]);
+        assert_eq!(gs.rebuy(1), Ok((1000, false)));
+        assert!(gs.eliminations.is_empty());
+        assert_eq!(gs.ledger[&1].rebuys, 1);
+        gs.players.get_mut(&1).unwrap().chips = 0;
+        assert!(gs.rebuy(1).is_err());
+
+        gs.end_rebuy_period();
+        assert!(gs.rebuy(2).is_err());
+    }
+
+    #[test]
+    fn one_add_on_for_players_still_in() {
+        let mut gs = table(3, GameMode::Tournament);
+        gs.rebuy_config = RebuyConfig {
+            levels: 1,
+            max_rebuys: 0,
+            add_on: true,
+        };
+        assert!(gs.add_on(1).is_err());
+
+        let messages = gs.end_rebuy_period();
+        assert!(gs.add_on_open && gs.paused);
+        assert!(matches!(
+            messages[0],
+            ServerMessage::RebuyPeriodEnded { add_on_open: true }
+        ));
+        assert_eq!(gs.add_on(1), Ok((1000, false)));
+        assert_eq!(gs.players[&1].chips, 2000);
+        assert!(gs.add_on(1).is_err());
+        gs.players.get_mut(&2).unwrap().chips = 0;
+        assert!(gs.add_on(2).is_err());
+
+        assert!(matches!(
+            gs.close_add_on(),
+            Some(ServerMessage::AddOnClosed)
+        ));
+        assert!(gs.add_on(3).is_err());
+    }
+
+    #[test]
+    fn prize_pool_counts_entries_rebuys_and_pending_chips() {
+        let mut gs = table(3, GameMode::Tournament);
+        assert_eq!(gs.prize_pool(), 3000);
+        gs.rebuy_config.levels = 1;
+        gs.players.get_mut(&1).unwrap().chips = 0;
+        gs.rebuy(1).unwrap();
+        assert_eq!(gs.prize_pool(), 4000);
+
+        // A rebuy during the hand counts before it is credited.
+        gs.start_new_hand();
+        gs.players.get_mut(&2).unwrap().chips = 0;
+        gs.rebuy(2).unwrap();
+        assert_eq!(gs.players[&2].pending_buy_in, 1000);
+        assert_eq!(gs.prize_pool(), 5000);
+
+        gs.shared_prize_pool = Some(12_000);
+        assert_eq!(gs.prize_pool(), 12_000);
+    }
+
+    #[test]
+    fn last_player_left_in_a_tournament_wins_it() {
+        let mut gs = table(2, GameMode::Tournament);
+        let mut messages = Vec::new();
+        assert_eq!(gs.remove_player(2, &mut messages), 1000);
+        assert_eq!(eliminated(&messages), vec![(2, 2)]);
+        assert!(
+            messages
+                .iter()
+                .any(|m| matches!(m, ServerMessage::GameOver { winner_id, .. } if *winner_id == 1))
+        );
+        assert_eq!(standings(&messages), vec![(1, 1, 2000), (2, 2, 0)]);
+        assert!(!gs.game_started);
+    }
+
+    #[test]
+    fn player_leaving_a_tournament_is_placed_behind_those_still_in() {
+        let mut gs = table(4, GameMode::Tournament);
+        let mut messages = Vec::new();
+        gs.remove_player(4, &mut messages);
+        assert_eq!(eliminated(&messages), vec![(4, 4)]);
+        assert_eq!(gs.eliminations, vec![vec![4]]);
+        assert!(gs.game_started);
+    }
+
+    #[test]
+    fn player_leaving_a_multi_table_tournament_is_left_to_the_tournament() {
+        let mut gs = table(3, GameMode::Tournament);
+        gs.multi_table = true;
+        let mut messages = Vec::new();
+        gs.remove_player(3, &mut messages);
+        assert!(messages.is_empty());
+        assert_eq!(gs.pending_busts, vec![vec![3]]);
+        assert!(gs.eliminations.is_empty());
+    }
+
+    #[test]
+    fn rebuy_pending_when_the_game_is_ended_counts() {
+        let mut gs = table(3, GameMode::Tournament);
+        gs.payouts = vec![100];
+        gs.rebuy_config.levels = 1;
+        gs.start_new_hand();
+        gs.players.get_mut(&3).unwrap().chips = 0;
+        assert_eq!(gs.rebuy(3), Ok((1000, true)));
+
+        // The hand is called off, so player 3 has their stack back and the
+        // rebuy on top; the pool has all four buy-ins.
+        let messages = gs.end_game();
+        assert_eq!(gs.players[&3].chips, 2000);
+        assert_eq!(gs.players[&3].pending_buy_in, 0);
+        assert_eq!(gs.ledger[&3].bought_in, 2000);
+        assert_eq!(gs.prize_pool(), 4000);
+        assert!(
+            messages
+                .iter()
+                .any(|m| matches!(m, ServerMessage::PrizePool { total: 4000 }))
+        );
+        assert_eq!(
+            standings(&messages),
+            vec![(1, 3, 4000), (2, 1, 0), (2, 2, 0)]
+        );
+    }
+
+    #[test]
+    fn player_leaving_between_hands_goes_at_once() {
+        let mut gs = table(3, cash());
+        let chips = gs.players[&2].chips;
+        assert_eq!(gs.leave(2, &mut Vec::new()), Some(chips));
+        assert!(!gs.players.contains_key(&2));
+        assert_eq!(gs.ledger[&2].cashed_out, chips);
+    }
+}
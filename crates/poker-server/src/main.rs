@@ -5,29 +5,52 @@
 //! | Method | Path            | Description                                |
 //! |--------|-----------------|------------------------------------------- |
 //! | `GET`  | `/ws`           | WebSocket upgrade for game connections     |
-//! | `GET`  | `/api/rooms`    | List active room IDs (JSON)                |
+//! | `GET`  | `/api/rooms`    | Summaries of the public rooms (JSON)       |
+//! | `GET`  | `/api/rooms/{id}` | Details of one room (JSON, 404 if none)  |
+//! | `GET`  | `/metrics`      | Prometheus metrics (text format)           |
+//! | `*`    | `/admin/*`      | Operator API, with an admin token (see [`admin`]) |
 //! | `GET`  | `/poker/*`      | Poker Dioxus SPA (fallback: poker/index.html) |
 //! | `GET`  | `/*`            | Main site static files (fallback: index.html) |
 //!
-//! Set `STATIC_DIR` to point at the combined static output (default: `./dist`).
+//! Run with `--help` for the command-line flags.  A TOML file given with
+//! `--config` sets everything else; see [`config`].  `PORT`, `STATIC_DIR`
+//! and `STATE_DIR` still work as environment variables, and `state_dir`
+//! keeps running games across restarts (see [`store`]).
+//!
+//! On SIGINT or SIGTERM the server stops accepting connections, sends every
+//! client a `ServerShutdown` notice, lets the hands being played finish and
+//! saves the rooms before exiting.
 
+mod actor;
+mod admin;
+mod config;
 mod game_logic;
+mod metrics;
+mod replay;
+mod requests;
 mod room;
+mod spectator;
+mod store;
+mod tournament;
 mod ws_handler;
 
-use std::net::SocketAddr;
 use std::sync::Arc;
 
-use axum::extract::State;
 use axum::extract::ws::WebSocketUpgrade;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
 use axum::response::IntoResponse;
 use axum::routing::get;
 use axum::{Json, Router};
+use clap::Parser;
 use tower_http::cors::CorsLayer;
 use tower_http::services::{ServeDir, ServeFile};
 use tracing_subscriber::EnvFilter;
 
+use config::{Cli, Config, LogFormat};
+use poker_core::protocol::{RoomDetails, RoomSummary};
 use room::RoomManager;
+use store::Store;
 
 /// Shared application state available to all handlers.
 #[derive(Clone)]
@@ -37,19 +60,51 @@ struct AppState {
 
 #[tokio::main]
 async fn main() {
-    // Initialise tracing (respects RUST_LOG env var).
-    tracing_subscriber::fmt()
-        .with_env_filter(
-            EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")),
-        )
-        .init();
+    let cli = Cli::parse();
+    let config = Config::load(&cli).unwrap_or_else(|e| {
+        eprintln!("poker-server: {e}");
+        std::process::exit(2);
+    });
+    if cli.print_config {
+        print!("{}", config.to_toml());
+        return;
+    }
 
+    // Initialise tracing (respects RUST_LOG env var).
+    let subscriber = tracing_subscriber::fmt().with_env_filter(
+        EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")),
+    );
+    match config.server.log_format {
+        LogFormat::Full => subscriber.init(),
+        LogFormat::Compact => subscriber.compact().init(),
+        LogFormat::Pretty => subscriber.pretty().init(),
+    }
+    tracing::info!("Effective configuration:\n{}", config.to_toml());
+
+    // Snapshot directory for running games, if enabled.
+    let store = config.server.state_dir.as_ref().map(|dir| {
+        Store::open(dir).unwrap_or_else(|e| {
+            eprintln!(
+                "poker-server: cannot use state directory {}: {e}",
+                dir.display()
+            );
+            std::process::exit(2);
+        })
+    });
+    let config = Arc::new(config);
+    let room_manager = RoomManager::new(Arc::clone(&config), store);
+    room_manager.restore_rooms();
+
+    let room_manager = Arc::new(room_manager);
     let state = AppState {
-        room_manager: Arc::new(RoomManager::new()),
+        room_manager: Arc::clone(&room_manager),
     };
 
     // Static file directory for the combined site output.
-    let static_dir = std::env::var("STATIC_DIR").unwrap_or_else(|_| "./dist".to_string());
+    let static_dir = config.server.static_dir.display().to_string();
+    if !config.server.static_dir.is_dir() {
+        tracing::warn!("Static directory {static_dir} does not exist; only the API is served");
+    }
 
     // Poker SPA: /poker/* routes, fallback to /poker/index.html for client-side routing.
     let poker_spa = ServeDir::new(format!("{static_dir}/poker"))
@@ -59,25 +114,75 @@ async fn main() {
     let main_site = ServeDir::new(&static_dir)
         .not_found_service(ServeFile::new(format!("{static_dir}/index.html")));
 
-    let app = Router::new()
+    let mut api = Router::new()
         .route("/ws", get(ws_handler))
         .route("/api/rooms", get(rooms_handler))
+        .route("/api/rooms/{id}", get(room_handler))
+        .route("/metrics", get(metrics_handler));
+    if !config.server.admin_token.is_empty() {
+        api = api.nest("/admin", admin::router(&config.server.admin_token));
+        tracing::info!("Admin API enabled at /admin");
+    }
+
+    let app = api
         .layer(CorsLayer::permissive())
         .with_state(state)
         .nest_service("/poker", poker_spa)
         .fallback_service(main_site);
 
-    let port: u16 = std::env::var("PORT")
-        .ok()
-        .and_then(|p| p.parse().ok())
-        .unwrap_or(8080);
-
-    let addr = SocketAddr::from(([0, 0, 0, 0], port));
+    let addr = config.server.bind;
+    let listener = tokio::net::TcpListener::bind(addr)
+        .await
+        .unwrap_or_else(|e| {
+            eprintln!("poker-server: cannot listen on {addr}: {e}");
+            std::process::exit(1);
+        });
     tracing::info!("Poker server listening on {addr}");
     tracing::info!("Serving static files from {static_dir}");
 
-    let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
-    axum::serve(listener, app).await.unwrap();
+    tokio::select! {
+        result = axum::serve(listener, app) => result.unwrap(),
+        // Dropping the server stops new connections; the open ones keep
+        // playing until the rooms are wound down.
+        () = shutdown_signal() => {
+            tracing::info!("Shutting down");
+            let reason = if room_manager.persistent() {
+                "The server is restarting"
+            } else {
+                "The server is shutting down"
+            };
+            room_manager
+                .shutdown(
+                    reason,
+                    config.timing.reconnect_after_secs,
+                    config.timing.shutdown_timeout(),
+                )
+                .await;
+            tracing::info!("Shutdown complete");
+        }
+    }
+}
+
+/// Resolve on Ctrl-C or, on Unix, SIGTERM.
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("failed to listen for Ctrl-C");
+    };
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("failed to listen for SIGTERM")
+            .recv()
+            .await;
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+    tokio::select! {
+        () = ctrl_c => {}
+        () = terminate => {}
+    }
 }
 
 /// `GET /ws` — upgrade to WebSocket and hand off to [`ws_handler::handle_socket`].
@@ -85,7 +190,34 @@ async fn ws_handler(ws: WebSocketUpgrade, State(state): State<AppState>) -> impl
     ws.on_upgrade(move |socket| ws_handler::handle_socket(socket, state.room_manager))
 }
 
-/// `GET /api/rooms` — return a JSON array of active room IDs.
-async fn rooms_handler(State(state): State<AppState>) -> Json<Vec<String>> {
+/// `GET /api/rooms` — return a JSON array of room summaries, leaving out
+/// unlisted rooms.
+async fn rooms_handler(State(state): State<AppState>) -> Json<Vec<RoomSummary>> {
     Json(state.room_manager.list_rooms().await)
 }
+
+/// `GET /api/rooms/{id}` — return the details of one room.
+async fn room_handler(
+    Path(room_id): Path<String>,
+    State(state): State<AppState>,
+) -> Result<Json<RoomDetails>, StatusCode> {
+    state
+        .room_manager
+        .room_details(&room_id)
+        .await
+        .map(Json)
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
+/// `GET /metrics` — counters, gauges and histograms in the Prometheus text
+/// format.
+async fn metrics_handler(State(state): State<AppState>) -> impl IntoResponse {
+    let gauges = state.room_manager.gauges().await;
+    (
+        [(
+            axum::http::header::CONTENT_TYPE,
+            "text/plain; version=0.0.4; charset=utf-8",
+        )],
+        metrics::METRICS.render(&gauges),
+    )
+}
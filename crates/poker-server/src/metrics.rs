@@ -0,0 +1,250 @@
+//! Prometheus metrics, served as text on `GET /metrics`.
+//!
+//! Counters and histograms live in the process-wide [`METRICS`] and are
+//! bumped where things happen: the room manager, the WebSocket handler and
+//! the game logic.  Gauges (rooms, players) are read off the
+//! [`RoomManager`](crate::room::RoomManager) when the endpoint is scraped.
+
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use poker_core::protocol::PlayerAction;
+
+/// Process-wide metrics.
+pub static METRICS: Metrics = Metrics::new();
+
+/// A monotonically increasing count.
+pub struct Counter(AtomicU64);
+
+impl Counter {
+    const fn new() -> Self {
+        Self(AtomicU64::new(0))
+    }
+
+    pub fn inc(&self) {
+        self.0.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn get(&self) -> u64 {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Durations sorted into buckets with upper bounds in seconds.
+pub struct Histogram<const N: usize> {
+    bounds: [f64; N],
+    /// Observations per bucket, not cumulative.  Anything above the last
+    /// bound goes in `overflow`.
+    buckets: [AtomicU64; N],
+    overflow: AtomicU64,
+    sum_micros: AtomicU64,
+}
+
+impl<const N: usize> Histogram<N> {
+    const fn new(bounds: [f64; N]) -> Self {
+        Self {
+            bounds,
+            buckets: [const { AtomicU64::new(0) }; N],
+            overflow: AtomicU64::new(0),
+            sum_micros: AtomicU64::new(0),
+        }
+    }
+
+    pub fn observe(&self, duration: Duration) {
+        let secs = duration.as_secs_f64();
+        match self.bounds.iter().position(|&bound| secs <= bound) {
+            Some(i) => self.buckets[i].fetch_add(1, Ordering::Relaxed),
+            None => self.overflow.fetch_add(1, Ordering::Relaxed),
+        };
+        self.sum_micros
+            .fetch_add(duration.as_micros() as u64, Ordering::Relaxed);
+    }
+
+    fn render(&self, out: &mut String, name: &str, help: &str) {
+        header(out, name, help, "histogram");
+        let mut count = 0;
+        for (bound, bucket) in self.bounds.iter().zip(&self.buckets) {
+            count += bucket.load(Ordering::Relaxed);
+            let _ = writeln!(out, "{name}_bucket{{le=\"{bound}\"}} {count}");
+        }
+        count += self.overflow.load(Ordering::Relaxed);
+        let sum = self.sum_micros.load(Ordering::Relaxed) as f64 / 1_000_000.0;
+        let _ = writeln!(out, "{name}_bucket{{le=\"+Inf\"}} {count}");
+        let _ = writeln!(out, "{name}_sum {sum}");
+        let _ = writeln!(out, "{name}_count {count}");
+    }
+}
+
+/// Everything counted since the server started.
+pub struct Metrics {
+    pub rooms_created: Counter,
+    pub hands_dealt: Counter,
+    /// Actions taken, indexed like [`ACTION_LABELS`].
+    actions: [Counter; 5],
+    pub turn_timeouts: Counter,
+    pub reconnects: Counter,
+    pub reconnect_failures: Counter,
+    pub invalid_messages: Counter,
+    pub send_failures: Counter,
+    pub hand_duration: Histogram<9>,
+    pub time_to_act: Histogram<9>,
+}
+
+/// The `action` label of each [`PlayerAction`].
+const ACTION_LABELS: [&str; 5] = ["fold", "check", "call", "raise", "allin"];
+
+impl Metrics {
+    const fn new() -> Self {
+        Self {
+            rooms_created: Counter::new(),
+            hands_dealt: Counter::new(),
+            actions: [const { Counter::new() }; 5],
+            turn_timeouts: Counter::new(),
+            reconnects: Counter::new(),
+            reconnect_failures: Counter::new(),
+            invalid_messages: Counter::new(),
+            send_failures: Counter::new(),
+            hand_duration: Histogram::new([
+                10.0, 20.0, 30.0, 45.0, 60.0, 90.0, 120.0, 180.0, 300.0,
+            ]),
+            time_to_act: Histogram::new([0.5, 1.0, 2.0, 3.0, 5.0, 10.0, 15.0, 20.0, 30.0]),
+        }
+    }
+
+    /// Count an action a player took.
+    pub fn action(&self, action: PlayerAction) {
+        let i = match action {
+            PlayerAction::Fold => 0,
+            PlayerAction::Check => 1,
+            PlayerAction::Call => 2,
+            PlayerAction::Raise => 3,
+            PlayerAction::AllIn => 4,
+        };
+        self.actions[i].inc();
+    }
+
+    /// Render the counters and histograms, after the given gauges, in the
+    /// Prometheus text format.
+    pub fn render(&self, gauges: &Gauges) -> String {
+        let mut out = String::new();
+        gauge(
+            &mut out,
+            "poker_rooms",
+            "Rooms currently open.",
+            gauges.rooms,
+        );
+        gauge(
+            &mut out,
+            "poker_players_connected",
+            "Players seated with a live connection.",
+            gauges.connected_players,
+        );
+        gauge(
+            &mut out,
+            "poker_players_disconnected",
+            "Players whose seat is held while they reconnect.",
+            gauges.disconnected_players,
+        );
+        gauge(
+            &mut out,
+            "poker_spectators",
+            "Spectators watching a room.",
+            gauges.spectators,
+        );
+        counter(
+            &mut out,
+            "poker_rooms_created_total",
+            "Rooms created by clients.",
+            self.rooms_created.get(),
+        );
+        counter(
+            &mut out,
+            "poker_hands_dealt_total",
+            "Hands dealt.",
+            self.hands_dealt.get(),
+        );
+        header(
+            &mut out,
+            "poker_actions_total",
+            "Betting actions taken, by type.",
+            "counter",
+        );
+        for (label, count) in ACTION_LABELS.iter().zip(&self.actions) {
+            let _ = writeln!(
+                out,
+                "poker_actions_total{{action=\"{label}\"}} {}",
+                count.get()
+            );
+        }
+        counter(
+            &mut out,
+            "poker_turn_timeouts_total",
+            "Turns that ran out of time.",
+            self.turn_timeouts.get(),
+        );
+        header(
+            &mut out,
+            "poker_reconnects_total",
+            "Attempts to rejoin a room with a session token.",
+            "counter",
+        );
+        let _ = writeln!(
+            out,
+            "poker_reconnects_total{{result=\"success\"}} {}",
+            self.reconnects.get()
+        );
+        let _ = writeln!(
+            out,
+            "poker_reconnects_total{{result=\"failure\"}} {}",
+            self.reconnect_failures.get()
+        );
+        counter(
+            &mut out,
+            "poker_invalid_messages_total",
+            "Client messages that could not be decoded.",
+            self.invalid_messages.get(),
+        );
+        counter(
+            &mut out,
+            "poker_ws_send_failures_total",
+            "WebSocket frames that could not be sent.",
+            self.send_failures.get(),
+        );
+        self.hand_duration.render(
+            &mut out,
+            "poker_hand_duration_seconds",
+            "Time from the deal to the end of a hand.",
+        );
+        self.time_to_act.render(
+            &mut out,
+            "poker_time_to_act_seconds",
+            "Time a player took to act on their turn.",
+        );
+        out
+    }
+}
+
+/// Current values read off the room manager at scrape time.
+#[derive(Debug, Default)]
+pub struct Gauges {
+    pub rooms: usize,
+    pub connected_players: usize,
+    pub disconnected_players: usize,
+    pub spectators: usize,
+}
+
+fn header(out: &mut String, name: &str, help: &str, kind: &str) {
+    let _ = writeln!(out, "# HELP {name} {help}");
+    let _ = writeln!(out, "# TYPE {name} {kind}");
+}
+
+fn gauge(out: &mut String, name: &str, help: &str, value: usize) {
+    header(out, name, help, "gauge");
+    let _ = writeln!(out, "{name} {value}");
+}
+
+fn counter(out: &mut String, name: &str, help: &str, value: u64) {
+    header(out, name, help, "counter");
+    let _ = writeln!(out, "{name} {value}");
+}
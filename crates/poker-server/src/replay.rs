@@ -0,0 +1,196 @@
+//! Room event sequence numbers and the replay buffer.
+//!
+//! Every room event sent to players is numbered, in order, and the latest
+//! ones are kept in a bounded buffer together with who they were for.  A
+//! player who rejoins with the number of the last event they saw is sent
+//! the events they missed, including those sent while they were away.  Once
+//! the buffer has moved past that number they get a snapshot instead.
+//!
+//! Replies (errors, pongs, lists) are neither numbered nor kept: they answer
+//! one request and say nothing about the table.
+
+use std::collections::VecDeque;
+
+use poker_core::protocol::{Envelope, ServerMessage};
+
+/// Which players an event was for.
+#[derive(Debug, Clone, Copy)]
+pub enum Audience {
+    All,
+    Except(u32),
+    Only(u32),
+}
+
+impl Audience {
+    fn includes(self, player_id: u32) -> bool {
+        match self {
+            Audience::All => true,
+            Audience::Except(id) => id != player_id,
+            Audience::Only(id) => id == player_id,
+        }
+    }
+}
+
+struct Recorded {
+    seq: u64,
+    audience: Audience,
+    msg: ServerMessage,
+}
+
+/// The latest events of one room.
+pub struct ReplayBuffer {
+    events: VecDeque<Recorded>,
+    /// Number of the last event, 0 before the first.
+    last_seq: u64,
+    capacity: usize,
+}
+
+impl ReplayBuffer {
+    /// Keep up to `capacity` events, numbering on from `last_seq`.
+    pub fn new(capacity: usize, last_seq: u64) -> Self {
+        Self {
+            events: VecDeque::with_capacity(capacity.min(64)),
+            last_seq,
+            capacity,
+        }
+    }
+
+    /// Carry on numbering after a restart.  Clients may have seen events
+    /// after `last_seq` was saved, of a hand that is now void, so the
+    /// numbering jumps well past them and nobody can resume from before
+    /// the restart.
+    pub fn after_restart(capacity: usize, last_seq: u64) -> Self {
+        Self::new(capacity, last_seq.saturating_add(1 << 32))
+    }
+
+    pub fn last_seq(&self) -> u64 {
+        self.last_seq
+    }
+
+    /// Number and keep a message.  Replies get no number.
+    pub fn record(&mut self, audience: Audience, msg: &ServerMessage) -> Option<u64> {
+        if !is_event(msg) {
+            return None;
+        }
+        self.last_seq += 1;
+        if self.capacity > 0 {
+            if self.events.len() == self.capacity {
+                self.events.pop_front();
+            }
+            self.events.push_back(Recorded {
+                seq: self.last_seq,
+                audience,
+                msg: msg.clone(),
+            });
+        }
+        Some(self.last_seq)
+    }
+
+    /// The events for `player_id` after `seq`, or `None` if some of them
+    /// are no longer kept (or `seq` was never handed out).
+    pub fn since(&self, seq: u64, player_id: u32) -> Option<Vec<Envelope>> {
+        let oldest = self.events.front().map_or(self.last_seq + 1, |e| e.seq);
+        if seq > self.last_seq || seq + 1 < oldest {
+            return None;
+        }
+        Some(
+            self.events
+                .iter()
+                .filter(|e| e.seq > seq && e.audience.includes(player_id))
+                .map(|e| Envelope {
+                    seq: Some(e.seq),
+                    msg: e.msg.clone(),
+                })
+                .collect(),
+        )
+    }
+}
+
+/// Whether a message tells the player about the table, as opposed to
+/// answering a request.
+fn is_event(msg: &ServerMessage) -> bool {
+    !matches!(
+        msg,
+        ServerMessage::ServerHello { .. }
+            | ServerMessage::UpgradeRequired { .. }
+            | ServerMessage::Resumed { .. }
+            | ServerMessage::PlayerList { .. }
+            | ServerMessage::RoomList { .. }
+            | ServerMessage::RoomError { .. }
+            | ServerMessage::ActionAck { .. }
+            | ServerMessage::ActionRejected { .. }
+            | ServerMessage::Ok
+            | ServerMessage::Pong
+            | ServerMessage::Error { .. }
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pot(pot: u32) -> ServerMessage {
+        ServerMessage::PotUpdate { pot }
+    }
+
+    fn seqs(events: Option<Vec<Envelope>>) -> Option<Vec<u64>> {
+        events.map(|events| events.into_iter().filter_map(|e| e.seq).collect())
+    }
+
+    #[test]
+    fn replies_are_not_numbered() {
+        let mut buffer = ReplayBuffer::new(8, 0);
+        assert_eq!(buffer.record(Audience::All, &ServerMessage::Pong), None);
+        assert_eq!(buffer.record(Audience::All, &pot(10)), Some(1));
+        assert_eq!(buffer.last_seq(), 1);
+    }
+
+    #[test]
+    fn events_older_than_the_buffer_are_a_gap() {
+        let mut buffer = ReplayBuffer::new(3, 0);
+        for i in 1..=5 {
+            buffer.record(Audience::All, &pot(i));
+        }
+        // Events 3 to 5 are kept.
+        assert_eq!(seqs(buffer.since(1, 1)), None);
+        assert_eq!(seqs(buffer.since(0, 1)), None);
+        assert_eq!(seqs(buffer.since(2, 1)), Some(vec![3, 4, 5]));
+        assert_eq!(seqs(buffer.since(4, 1)), Some(vec![5]));
+        assert_eq!(seqs(buffer.since(5, 1)), Some(vec![]));
+        // Never handed out.
+        assert_eq!(seqs(buffer.since(6, 1)), None);
+    }
+
+    #[test]
+    fn empty_buffer_only_resumes_from_the_last_event() {
+        let mut buffer = ReplayBuffer::new(0, 0);
+        buffer.record(Audience::All, &pot(10));
+        assert_eq!(seqs(buffer.since(1, 1)), Some(vec![]));
+        assert_eq!(seqs(buffer.since(0, 1)), None);
+    }
+
+    #[test]
+    fn players_only_get_their_own_events() {
+        let mut buffer = ReplayBuffer::new(8, 0);
+        buffer.record(Audience::All, &pot(10));
+        buffer.record(Audience::Only(1), &pot(20));
+        buffer.record(Audience::Except(1), &pot(30));
+        buffer.record(Audience::Only(2), &pot(40));
+        assert_eq!(seqs(buffer.since(0, 1)), Some(vec![1, 2]));
+        assert_eq!(seqs(buffer.since(0, 2)), Some(vec![1, 3, 4]));
+        assert_eq!(seqs(buffer.since(0, 3)), Some(vec![1, 3]));
+    }
+
+    #[test]
+    fn numbering_jumps_after_a_restart() {
+        let mut buffer = ReplayBuffer::after_restart(8, 40);
+        assert_eq!(buffer.last_seq(), 40 + (1 << 32));
+        // Nothing from before the restart can be resumed from.
+        assert_eq!(seqs(buffer.since(40, 1)), None);
+        assert_eq!(seqs(buffer.since(45, 1)), None);
+        let seq = buffer.record(Audience::All, &pot(10)).unwrap();
+        assert_eq!(seq, 41 + (1 << 32));
+        assert_eq!(seqs(buffer.since(seq - 1, 1)), Some(vec![seq]));
+        assert_eq!(seqs(buffer.since(40, 1)), None);
+    }
+}
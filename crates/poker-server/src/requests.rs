@@ -0,0 +1,123 @@
+//! Answers to the latest numbered betting actions of each player.
+//!
+//! Clients may give their betting actions a request ID, counting up.  The
+//! answer to each one is kept for a while, so a client that sends an action
+//! again because it never saw the answer gets the same answer, and the
+//! action is not taken twice.  An ID at or below the highest one answered
+//! whose answer is no longer kept is refused as a duplicate.
+//!
+//! The answers follow a player who is moved to another table of a
+//! multi-table tournament (see [`Mover`](crate::tournament::Mover)).
+
+use std::collections::{HashMap, VecDeque};
+
+use poker_core::protocol::ProtocolError;
+
+/// Answers kept per player.
+const KEPT_PER_PLAYER: usize = 16;
+
+type Answer = Result<(), ProtocolError>;
+
+/// The numbered actions of one player.
+#[derive(Debug, Clone, Default)]
+pub struct PlayerRequests {
+    /// Highest request ID answered.
+    highest: u64,
+    /// The latest answers, oldest first.
+    answers: VecDeque<(u64, Answer)>,
+}
+
+#[derive(Default)]
+pub struct RequestLog {
+    players: HashMap<u32, PlayerRequests>,
+}
+
+impl RequestLog {
+    /// The answer to a request that was answered before: the same answer
+    /// if it is still kept, otherwise a
+    /// [`DuplicateRequest`](ProtocolError::DuplicateRequest) error.
+    /// `None` for a new request.
+    pub fn answer(&self, player_id: u32, request_id: u64) -> Option<Answer> {
+        let requests = self.players.get(&player_id)?;
+        if request_id > requests.highest {
+            return None;
+        }
+        let kept = requests
+            .answers
+            .iter()
+            .find(|(id, _)| *id == request_id)
+            .map(|(_, answer)| answer.clone());
+        Some(kept.unwrap_or(Err(ProtocolError::DuplicateRequest)))
+    }
+
+    pub fn record(&mut self, player_id: u32, request_id: u64, answer: Answer) {
+        let requests = self.players.entry(player_id).or_default();
+        requests.highest = requests.highest.max(request_id);
+        if requests.answers.len() == KEPT_PER_PLAYER {
+            requests.answers.pop_front();
+        }
+        requests.answers.push_back((request_id, answer));
+    }
+
+    /// A player's answers, to take along to another table.
+    pub fn of(&self, player_id: u32) -> Option<PlayerRequests> {
+        self.players.get(&player_id).cloned()
+    }
+
+    /// Take over the answers of a player moved from another table.
+    pub fn restore(&mut self, player_id: u32, requests: PlayerRequests) {
+        self.players.insert(player_id, requests);
+    }
+
+    /// Drop the answers of a player who has left.
+    pub fn forget(&mut self, player_id: u32) {
+        self.players.remove(&player_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repeated_requests_get_the_same_answer() {
+        let mut log = RequestLog::default();
+        assert_eq!(log.answer(1, 1), None);
+        log.record(1, 1, Ok(()));
+        log.record(1, 2, Err(ProtocolError::NotYourTurn));
+        assert_eq!(log.answer(1, 1), Some(Ok(())));
+        assert_eq!(log.answer(1, 2), Some(Err(ProtocolError::NotYourTurn)));
+        assert_eq!(log.answer(1, 3), None);
+        assert_eq!(log.answer(2, 1), None);
+    }
+
+    #[test]
+    fn old_requests_no_longer_kept_are_duplicates() {
+        let mut log = RequestLog::default();
+        for id in 1..=KEPT_PER_PLAYER as u64 + 4 {
+            log.record(1, id, Ok(()));
+        }
+        assert_eq!(log.answer(1, 1), Some(Err(ProtocolError::DuplicateRequest)));
+        assert_eq!(log.answer(1, 4), Some(Err(ProtocolError::DuplicateRequest)));
+        assert_eq!(log.answer(1, 5), Some(Ok(())));
+        // An ID skipped by the client counts as used too.
+        log.record(1, 30, Ok(()));
+        assert_eq!(
+            log.answer(1, 25),
+            Some(Err(ProtocolError::DuplicateRequest))
+        );
+        assert_eq!(log.answer(1, 31), None);
+    }
+
+    #[test]
+    fn answers_move_with_the_player() {
+        let mut from = RequestLog::default();
+        from.record(1, 7, Ok(()));
+        let mut to = RequestLog::default();
+        to.restore(1, from.of(1).unwrap());
+        from.forget(1);
+        assert_eq!(from.answer(1, 7), None);
+        assert_eq!(to.answer(1, 7), Some(Ok(())));
+        assert_eq!(to.answer(1, 6), Some(Err(ProtocolError::DuplicateRequest)));
+    }
+}
@@ -2,88 +2,470 @@
 //!
 //! Each room contains an independent [`GameState`] and a set of connected
 //! players, each with their own [`mpsc`] sender for targeted message delivery
-//! (no broadcast fan-out of private data).
+//! (no broadcast fan-out of private data).  A room runs as its own task
+//! (see [`crate::actor`]); the manager only keeps a [`RoomHandle`] to each.
 
-use std::collections::HashMap;
-use std::sync::Arc;
-use std::sync::atomic::AtomicU64;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, PoisonError, RwLock};
 use std::time::{Duration, Instant};
 
+use crate::actor::{RoomActor, RoomHandle};
+use crate::config::Config;
 use crate::game_logic::{GamePhase, GameState, PlayerStatus};
+use crate::metrics::{Gauges, METRICS};
+use crate::replay::{Audience, ReplayBuffer};
+use crate::requests::RequestLog;
+use crate::spectator::Spectator;
+use crate::store::{RoomSnapshot, SnapshotFile, Store};
+use crate::tournament::{TableLink, Tournament};
 use poker_core::protocol::{
-    BlindConfig, CardInfo, PlayerInfo, ServerMessage, card_to_info, validate_room_id,
+    BlindConfig, CardInfo, Envelope, GameMode, PlayerBet, ProtocolError, RebuyConfig, RoomDetails,
+    RoomSummary, ServerMessage, SpectatorDelay, card_to_info, validate_game_mode,
+    validate_multi_table, validate_payouts, validate_rebuy_config, validate_room_id,
+    validate_room_password, validate_spectator_delay, validate_table_size,
 };
-use tokio::sync::{Mutex, RwLock, mpsc};
-
-/// How long a disconnected player's seat is held before permanent removal.
-const SESSION_GRACE_PERIOD: Duration = Duration::from_secs(5 * 60); // 5 minutes
+use tokio::sync::{mpsc, watch};
 
 /// Handle to a per-player outbound channel.
 ///
 /// The WebSocket write loop drains this receiver and forwards messages as
 /// text frames.
-pub type PlayerTx = mpsc::UnboundedSender<ServerMessage>;
-pub type PlayerRx = mpsc::UnboundedReceiver<ServerMessage>;
+pub type PlayerTx = mpsc::UnboundedSender<Envelope>;
+pub type PlayerRx = mpsc::UnboundedReceiver<Envelope>;
+
+/// All rooms keyed by room ID.
+///
+/// The lock is only held to look a handle up or change the map, never
+/// across an `await`.
+#[derive(Clone, Default)]
+pub struct RoomMap(Arc<RwLock<HashMap<String, RoomHandle>>>);
+
+impl RoomMap {
+    pub fn get(&self, room_id: &str) -> Option<RoomHandle> {
+        self.read().get(room_id).cloned()
+    }
+
+    pub fn insert(&self, room_id: &str, room: RoomHandle) {
+        self.write().insert(room_id.to_string(), room);
+    }
+
+    pub fn remove(&self, room_id: &str) {
+        self.write().remove(room_id);
+    }
+
+    /// Every room with its ID, sorted by ID.
+    pub fn all(&self) -> Vec<(String, RoomHandle)> {
+        let mut rooms: Vec<(String, RoomHandle)> = self
+            .read()
+            .iter()
+            .map(|(room_id, room)| (room_id.clone(), room.clone()))
+            .collect();
+        rooms.sort_by(|a, b| a.0.cmp(&b.0));
+        rooms
+    }
+
+    /// Change the map under a single write lock.
+    pub fn update<T>(&self, f: impl FnOnce(&mut HashMap<String, RoomHandle>) -> T) -> T {
+        f(&mut self.write())
+    }
+
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, HashMap<String, RoomHandle>> {
+        self.0.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn write(&self) -> std::sync::RwLockWriteGuard<'_, HashMap<String, RoomHandle>> {
+        self.0.write().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Tells a connection that its player now sits in another room (a
+/// multi-table tournament moved them).  The connection switches its read
+/// loop to `room` and its write loop to `rx` without reconnecting.
+pub struct Relocation {
+    pub room_id: String,
+    pub room: RoomHandle,
+    pub rx: PlayerRx,
+}
+
+/// Handle used to relocate a player's connection.
+pub type RelocationTx = mpsc::UnboundedSender<Relocation>;
+
+/// Settings chosen when a room is created.
+#[derive(Debug, Clone)]
+pub struct RoomSettings {
+    pub blind_config: BlindConfig,
+    pub starting_bbs: u32,
+    pub game_mode: GameMode,
+    pub rebuy_config: RebuyConfig,
+    pub payouts: Vec<u32>,
+    pub multi_table: bool,
+    pub table_size: u32,
+    pub spectator_delay: SpectatorDelay,
+    pub password: String,
+    pub unlisted: bool,
+}
+
+/// What a newcomer shows to get into a room.
+#[derive(Debug, Clone)]
+pub enum Admission {
+    /// The password and invite sent with `JoinRoom` or `Spectate`.
+    Credentials { password: String, invite: String },
+    /// Already let in, e.g. a spectator taking a seat.
+    Admitted,
+}
+
+/// An invite handed out by the host.
+#[derive(Debug, Clone, Copy)]
+pub struct Invite {
+    pub single_use: bool,
+    pub expires_at: Option<Instant>,
+}
+
+impl Invite {
+    fn is_live(&self) -> bool {
+        self.expires_at.is_none_or(|at| Instant::now() < at)
+    }
+}
 
 /// A single poker room.
 pub struct Room {
-    /// Server-side game state (deck, hands, betting, etc.).
-    pub game_state: Arc<Mutex<GameState>>,
     /// Per-player outbound senders keyed by player ID.
     pub player_senders: HashMap<u32, PlayerTx>,
-    /// Blind increase configuration for this room.
-    pub blind_config: BlindConfig,
     /// Monotonically increasing counter incremented every time a new turn
-    /// starts.  Used to invalidate stale turn-timer tasks.
-    pub turn_counter: Arc<AtomicU64>,
+    /// starts.  Used to invalidate stale turn timers.
+    pub turn_counter: u64,
     /// Maps session tokens to player IDs for reconnection.
     pub sessions: HashMap<String, u32>,
     /// Maps player IDs to session tokens (reverse lookup).
     pub player_sessions: HashMap<u32, String>,
     /// Tracks when disconnected players should be permanently removed.
     pub disconnected_at: HashMap<u32, Instant>,
+    /// Per-connection relocation handles keyed by player ID.
+    pub relocators: HashMap<u32, RelocationTx>,
+    /// Set when this room is a table of a multi-table tournament.
+    pub tournament: Option<TableLink>,
+    /// Connections watching the room, keyed by spectator ID.
+    pub spectators: HashMap<u32, Spectator>,
+    pub next_spectator_id: u32,
+    /// Whether spectators may chat (toggled by host).
+    pub allow_spectator_chat: bool,
+    /// How far spectators lag behind the table.
+    pub spectator_delay: SpectatorDelay,
+    /// The hand spectator feeds stamp their messages with, taken from the
+    /// `NewHand` messages sent to spectators.
+    pub spectator_hand: watch::Sender<u32>,
+    /// Names (lowercase) and session tokens the host has banned.
+    pub banned_names: HashSet<String>,
+    pub banned_sessions: HashSet<String>,
+    /// Password newcomers must give, empty for none.
+    pub password: String,
+    /// Hidden from the public room list.
+    pub unlisted: bool,
+    /// Outstanding invites keyed by token.
+    pub invites: HashMap<String, Invite>,
+    /// Where the game is saved between hands, if the server keeps state.
+    pub snapshot_file: Option<SnapshotFile>,
+    /// The server is shutting down: no new hands are dealt.
+    pub closing: bool,
+    /// Numbers the events sent to players and keeps the latest for players
+    /// who rejoin.  Sending only needs a shared borrow of the room.
+    pub replay: RefCell<ReplayBuffer>,
+    /// Answers to the players' numbered betting actions.
+    pub requests: RequestLog,
+    /// Server configuration: timers, limits and room defaults.
+    pub config: Arc<Config>,
 }
 
 impl Room {
-    fn new(blind_config: BlindConfig, starting_bbs: u32) -> Self {
+    /// Create a room and the game state it starts with.
+    pub fn new(settings: RoomSettings, config: Arc<Config>) -> (Self, GameState) {
+        let RoomSettings {
+            blind_config,
+            starting_bbs,
+            game_mode,
+            rebuy_config,
+            payouts,
+            table_size,
+            spectator_delay,
+            password,
+            unlisted,
+            ..
+        } = settings;
+        let (blind_config, starting_bbs) = match game_mode {
+            // Cash games never raise the blinds; the default buy-in must
+            // fall inside the allowed range.
+            GameMode::Cash {
+                min_buy_in_bbs,
+                max_buy_in_bbs,
+            } => (
+                BlindConfig::default(),
+                starting_bbs.clamp(min_buy_in_bbs, max_buy_in_bbs),
+            ),
+            GameMode::Tournament => (blind_config, starting_bbs),
+        };
         let mut gs = GameState::new();
         gs.blind_config = blind_config;
         gs.starting_bbs = starting_bbs;
-        Self {
-            game_state: Arc::new(Mutex::new(gs)),
+        gs.game_mode = game_mode;
+        gs.rebuy_config = rebuy_config;
+        gs.payouts = payouts;
+        gs.table_size = table_size;
+        gs.small_blind = config.room.small_blind;
+        gs.big_blind = config.room.big_blind;
+        gs.allow_late_entry = config.room.allow_late_entry;
+        let room = Self {
             player_senders: HashMap::new(),
-            blind_config,
-            turn_counter: Arc::new(AtomicU64::new(0)),
+            turn_counter: 0,
             sessions: HashMap::new(),
             player_sessions: HashMap::new(),
             disconnected_at: HashMap::new(),
-        }
+            relocators: HashMap::new(),
+            tournament: None,
+            spectators: HashMap::new(),
+            next_spectator_id: 1,
+            allow_spectator_chat: config.room.allow_spectator_chat,
+            spectator_delay,
+            spectator_hand: watch::Sender::new(0),
+            banned_names: HashSet::new(),
+            banned_sessions: HashSet::new(),
+            password,
+            unlisted,
+            invites: HashMap::new(),
+            snapshot_file: None,
+            closing: false,
+            replay: RefCell::new(ReplayBuffer::new(config.limits.replay_events, 0)),
+            requests: RequestLog::default(),
+            config,
+        };
+        (room, gs)
     }
 
-    /// Send a message to a specific player.
+    /// Send a message to a specific player.  Events are numbered and kept
+    /// for replay even while the player is disconnected.
     pub fn send_to_player(&self, player_id: u32, msg: &ServerMessage) {
+        let seq = self.record(Audience::Only(player_id), msg);
         if let Some(tx) = self.player_senders.get(&player_id) {
             // Ignore send failure — the player may have just disconnected.
-            let _ = tx.send(msg.clone());
+            let _ = tx.send(Envelope {
+                seq,
+                msg: msg.clone(),
+            });
         }
     }
 
-    /// Broadcast a message to **all** connected players in this room.
+    /// Send a reply to a specific spectator, bypassing the delay.
+    pub fn send_to_spectator(&self, spectator_id: u32, msg: &ServerMessage) {
+        if let Some(spectator) = self.spectators.get(&spectator_id) {
+            let _ = spectator.tx.send(msg.clone().into());
+        }
+    }
+
+    /// Broadcast a message to **all** connected players and spectators in
+    /// this room.
     pub fn broadcast(&self, msg: &ServerMessage) {
+        let seq = self.record(Audience::All, msg);
         for tx in self.player_senders.values() {
-            let _ = tx.send(msg.clone());
+            let _ = tx.send(Envelope {
+                seq,
+                msg: msg.clone(),
+            });
         }
+        self.send_to_spectators(msg);
     }
 
-    /// Broadcast a message to all connected players **except** `exclude_id`.
+    /// Broadcast a message to all connected players **except** `exclude_id`,
+    /// and to all spectators.
     pub fn broadcast_except(&self, msg: &ServerMessage, exclude_id: u32) {
+        let seq = self.record(Audience::Except(exclude_id), msg);
         for (&pid, tx) in &self.player_senders {
             if pid != exclude_id {
-                let _ = tx.send(msg.clone());
+                let _ = tx.send(Envelope {
+                    seq,
+                    msg: msg.clone(),
+                });
+            }
+        }
+        self.send_to_spectators(msg);
+    }
+
+    /// Number and keep an event for the players in `audience`.
+    fn record(&self, audience: Audience, msg: &ServerMessage) -> Option<u64> {
+        self.replay.borrow_mut().record(audience, msg)
+    }
+
+    /// Queue a public message for the spectators.  Hole cards and turn
+    /// prompts are never sent to them.
+    fn send_to_spectators(&self, msg: &ServerMessage) {
+        match msg {
+            ServerMessage::HoleCards { .. } | ServerMessage::YourTurn { .. } => return,
+            ServerMessage::NewHand { hand_number, .. } => {
+                self.spectator_hand.send_replace(*hand_number);
+            }
+            // Nothing is left to hide once the game is over.
+            ServerMessage::GameOver { .. } | ServerMessage::GameEnded => {
+                self.spectator_hand.send_replace(u32::MAX);
+            }
+            _ => {}
+        }
+        for spectator in self.spectators.values() {
+            spectator.feed.send(msg.clone());
+        }
+    }
+
+    /// Streamer view: show spectators every dealt player's hole cards.
+    pub fn send_spectator_hole_cards(&self, gs: &GameState) {
+        if !self.spectator_delay.hole_cards {
+            return;
+        }
+        let hands = gs.dealt_hole_cards();
+        if !hands.is_empty() {
+            self.send_to_spectators(&ServerMessage::SpectatorHoleCards { hands });
+        }
+    }
+
+    /// How many spectators are watching and whether they may chat.
+    pub fn spectator_status(&self) -> ServerMessage {
+        ServerMessage::Spectators {
+            count: self.spectators.len() as u32,
+            chat_allowed: self.allow_spectator_chat,
+        }
+    }
+
+    /// Lobby summary of the room.
+    pub fn summary(&self, room_id: &str, gs: &GameState) -> RoomSummary {
+        RoomSummary {
+            room_id: room_id.to_string(),
+            players: gs.player_count() as u32,
+            table_size: gs.table_size,
+            spectators: self.spectators.len() as u32,
+            small_blind: gs.small_blind,
+            big_blind: gs.big_blind,
+            blind_config: gs.blind_config,
+            game_mode: gs.game_mode,
+            multi_table: self.tournament.is_some(),
+            started: gs.game_started,
+            allow_late_entry: gs.allow_late_entry,
+            password: !self.password.is_empty(),
+        }
+    }
+
+    /// Full public description of the room.  Who is playing in a
+    /// password-protected room is left out.
+    pub fn details(&self, room_id: &str, gs: &GameState) -> RoomDetails {
+        let players = if self.password.is_empty() {
+            let mut players = gs.player_infos();
+            players.sort_by_key(|p| p.seat);
+            players
+        } else {
+            Vec::new()
+        };
+        RoomDetails {
+            summary: self.summary(room_id, gs),
+            host: gs.players.get(&gs.host_id).map(|p| p.name.clone()),
+            starting_bbs: gs.starting_bbs,
+            rebuy_config: gs.rebuy_config,
+            payouts: gs.payouts.clone(),
+            hand_number: gs.hand_number,
+            spectator_delay: self.spectator_delay,
+            players,
+        }
+    }
+
+    /// Save the game between hands while it runs; drop the snapshot once
+    /// it is over.  A no-op for rooms that are not persisted.
+    pub fn save_snapshot(&self, gs: &GameState) {
+        let Some(file) = &self.snapshot_file else {
+            return;
+        };
+        if gs.game_started {
+            file.save(&RoomSnapshot::capture(&file.room_id, self, gs));
+        } else {
+            file.remove();
+        }
+    }
+
+    /// Send a message to every player and spectator at once, bypassing the
+    /// spectator delay.
+    pub fn notify_all(&self, msg: &ServerMessage) {
+        let seq = self.record(Audience::All, msg);
+        for tx in self.player_senders.values() {
+            let _ = tx.send(Envelope {
+                seq,
+                msg: msg.clone(),
+            });
+        }
+        for spectator in self.spectators.values() {
+            let _ = spectator.tx.send(msg.clone().into());
+        }
+    }
+
+    /// Whether the host has banned this name.
+    pub fn is_banned(&self, name: &str) -> bool {
+        self.banned_names.contains(&name.trim().to_lowercase())
+    }
+
+    /// Check that a newcomer may come in: with a live invite, or with the
+    /// password if the room has one.  Expired invites are dropped.
+    pub fn check_admission(&mut self, admission: &Admission) -> Result<(), ProtocolError> {
+        let Admission::Credentials { password, invite } = admission else {
+            return Ok(());
+        };
+        self.invites.retain(|_, invite| invite.is_live());
+        if !invite.is_empty() {
+            if !self.invites.contains_key(invite) {
+                return Err(ProtocolError::InvalidInvite);
             }
+            return Ok(());
+        }
+        if self.password.is_empty() || *password == self.password {
+            return Ok(());
+        }
+        if password.is_empty() {
+            Err(ProtocolError::PasswordRequired)
+        } else {
+            Err(ProtocolError::WrongPassword)
+        }
+    }
+
+    /// Use up a single-use invite once its holder is in.
+    pub fn use_invite(&mut self, admission: &Admission) {
+        if let Admission::Credentials { invite, .. } = admission
+            && self.invites.get(invite).is_some_and(|i| i.single_use)
+        {
+            self.invites.remove(invite);
         }
     }
 
+    /// Hand out a new invite and return its token.
+    pub fn create_invite(&mut self, single_use: bool, expires_in_secs: u32) -> String {
+        let token = generate_session_token();
+        let expires_at = (expires_in_secs > 0)
+            .then(|| Instant::now() + Duration::from_secs(u64::from(expires_in_secs)));
+        self.invites.insert(
+            token.clone(),
+            Invite {
+                single_use,
+                expires_at,
+            },
+        );
+        token
+    }
+
+    /// Forget a player's connection and session.  Unless a table move has
+    /// handed their relocation handle to another room, this ends their
+    /// connection.  Returns their session token.
+    pub fn detach_player(&mut self, player_id: u32) -> Option<String> {
+        self.player_senders.remove(&player_id);
+        self.relocators.remove(&player_id);
+        self.disconnected_at.remove(&player_id);
+        self.requests.forget(player_id);
+        let token = self.player_sessions.remove(&player_id)?;
+        self.sessions.remove(&token);
+        Some(token)
+    }
+
     /// Register a session token for a player.
     pub fn register_session(&mut self, player_id: u32, token: String) {
         self.sessions.insert(token.clone(), player_id);
@@ -99,15 +481,7 @@ impl Room {
         player_id: u32,
         session_token: &str,
     ) -> ServerMessage {
-        let players: Vec<PlayerInfo> = gs
-            .players
-            .values()
-            .map(|p| PlayerInfo {
-                id: p.id,
-                name: p.name.clone(),
-                chips: p.chips,
-            })
-            .collect();
+        let players = gs.player_infos();
 
         let sitting_out: Vec<u32> = gs
             .players
@@ -154,6 +528,24 @@ impl Room {
             (0, 0, 0)
         };
 
+        let bets: Vec<PlayerBet> = if gs.hand_in_progress() {
+            gs.player_order
+                .iter()
+                .filter_map(|id| gs.players.get(id))
+                .map(|p| PlayerBet {
+                    player_id: p.id,
+                    bet: p.current_bet,
+                    committed: p.hand_start_chips.saturating_sub(p.chips),
+                })
+                .filter(|b| b.committed > 0)
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        let to_act = gs.player_to_act();
+        let turn_secs_left = to_act.and_then(|_| gs.turn_secs_left()).unwrap_or(0);
+
         ServerMessage::Rejoined {
             room_id: room_id.to_string(),
             player_id,
@@ -168,287 +560,465 @@ impl Room {
             players,
             sitting_out,
             folded,
-            blind_config: self.blind_config,
+            blind_config: gs.blind_config,
             allow_late_entry: gs.allow_late_entry,
-            is_host: gs.host_id == player_id,
+            is_host: player_id != 0 && gs.host_id == player_id,
+            paused: gs.paused,
+            game_mode: gs.game_mode,
+            rebuy_config: gs.rebuy_config,
+            rebuy_open: gs.rebuy_open(),
+            add_on_open: gs.add_on_open,
+            prize_pool: gs.prize_pool(),
+            table_size: gs.table_size,
             dealer_id,
             small_blind_id: sb_id,
             big_blind_id: bb_id,
             small_blind: gs.small_blind,
             big_blind: gs.big_blind,
+            bets,
+            current_bet: gs.current_bet,
+            min_raise: gs.min_raise,
+            to_act: to_act.unwrap_or(0),
+            turn_secs_left,
         }
     }
 }
 
+/// A player who got a seat: where, under which ID, and the receiver for
+/// their messages.
+pub struct Seated {
+    pub room_id: String,
+    pub room: RoomHandle,
+    pub player_id: u32,
+    pub rx: PlayerRx,
+}
+
+/// A spectator who is watching a room.
+pub struct Watching {
+    pub room_id: String,
+    pub room: RoomHandle,
+    pub spectator_id: u32,
+    pub rx: PlayerRx,
+}
+
 /// Manages all active rooms.
 ///
-/// Thread-safe: the outer `RwLock` allows concurrent reads (e.g. looking up
-/// rooms) while writes (create / remove) take exclusive access.  Each room
-/// is individually `Mutex`-protected so independent rooms never contend.
+/// The manager keeps a handle to each room's task and asks the room for
+/// anything it needs, so independent rooms never contend and no room's
+/// state is shared.
 pub struct RoomManager {
-    rooms: RwLock<HashMap<String, Arc<Mutex<Room>>>>,
+    rooms: RoomMap,
+    /// Snapshot directory, if running games are kept across restarts.
+    store: Option<Store>,
+    /// Set once the server starts shutting down; no rooms can be created
+    /// or joined after that.
+    closing: AtomicBool,
+    config: Arc<Config>,
 }
 
 impl RoomManager {
-    pub fn new() -> Self {
+    pub fn new(config: Arc<Config>, store: Option<Store>) -> Self {
         Self {
-            rooms: RwLock::new(HashMap::new()),
+            rooms: RoomMap::default(),
+            store,
+            closing: AtomicBool::new(false),
+            config,
         }
     }
 
-    /// Create a new room with the given ID.
-    ///
-    /// Returns an error string if the room ID is invalid or already taken.
-    pub async fn create_room(
-        &self,
-        room_id: &str,
-        blind_config: BlindConfig,
-        starting_bbs: u32,
-    ) -> Result<(), String> {
-        validate_room_id(room_id)?;
-
-        let mut rooms = self.rooms.write().await;
-        if rooms.contains_key(room_id) {
-            return Err(format!("Room '{}' already exists", room_id));
-        }
-        rooms.insert(
-            room_id.to_string(),
-            Arc::new(Mutex::new(Room::new(blind_config, starting_bbs))),
-        );
-        Ok(())
+    /// Whether running games are kept across restarts.
+    pub fn persistent(&self) -> bool {
+        self.store.is_some()
     }
 
-    /// Look up a room by ID.
-    pub async fn get_room(&self, room_id: &str) -> Option<Arc<Mutex<Room>>> {
-        let rooms = self.rooms.read().await;
-        rooms.get(room_id).cloned()
+    /// Wind the server down: tell everyone, stop dealing, give the hands
+    /// being played up to `hand_timeout` to finish and save the rooms.
+    pub async fn shutdown(&self, reason: &str, reconnect_after: u32, hand_timeout: Duration) {
+        self.closing.store(true, Ordering::SeqCst);
+        let rooms = self.all_rooms();
+
+        let notice = ServerMessage::ServerShutdown {
+            reason: reason.to_string(),
+            reconnect_after,
+        };
+        for (_, room) in &rooms {
+            let notice = notice.clone();
+            room.run(move |a| {
+                a.room.closing = true;
+                a.room.notify_all(&notice);
+            });
+        }
+
+        // Let the hands being played finish.
+        let deadline = Instant::now() + hand_timeout;
+        loop {
+            let mut playing = Vec::new();
+            for (room_id, room) in &rooms {
+                if room
+                    .call(|a| a.gs.hand_in_progress())
+                    .await
+                    .unwrap_or(false)
+                {
+                    playing.push(room_id.as_str());
+                }
+            }
+            if playing.is_empty() {
+                break;
+            }
+            if Instant::now() >= deadline {
+                tracing::warn!(rooms = ?playing, "Shutting down with hands unfinished");
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(250)).await;
+        }
+
+        // A hand cut short keeps the snapshot taken before it was dealt.
+        let mut writes = Vec::new();
+        for (_, room) in &rooms {
+            let write = room
+                .call(|a| {
+                    if !a.gs.hand_in_progress() {
+                        a.room.save_snapshot(&a.gs);
+                    }
+                    a.room.snapshot_file.as_ref().and_then(SnapshotFile::flush)
+                })
+                .await
+                .flatten();
+            writes.extend(write);
+        }
+        for write in writes {
+            let _ = write.await;
+        }
     }
 
-    /// Add a player to a room.
-    ///
-    /// Returns `(player_id, session_token, PlayerRx)` on success so the caller
-    /// can wire up the WebSocket write loop.
-    pub async fn join_room(
-        &self,
-        room_id: &str,
-        player_name: &str,
-    ) -> Result<(u32, String, usize, PlayerRx, Arc<Mutex<Room>>), String> {
-        let room_arc = self
-            .get_room(room_id)
-            .await
-            .ok_or_else(|| format!("Room '{}' not found", room_id))?;
+    /// Bring back the games saved before the last shutdown.  Every player
+    /// starts out disconnected, with the usual grace period to rejoin.
+    pub fn restore_rooms(&self) {
+        let Some(store) = &self.store else {
+            return;
+        };
+        for snapshot in store.load() {
+            let room_id = snapshot.room_id.clone();
+            if validate_room_id(&room_id).is_err() || self.rooms.get(&room_id).is_some() {
+                continue;
+            }
+            let player_ids: Vec<u32> = snapshot.players.iter().map(|p| p.id).collect();
+            let (mut room, gs) = snapshot.restore(Arc::clone(&self.config));
+            room.snapshot_file = Some(store.file(&room_id));
+            let (mut actor, handle) = RoomActor::new(room_id.clone(), room, gs, self.rooms.clone());
+            let now = Instant::now();
+            for player_id in player_ids {
+                actor.hold_seat(player_id, now);
+            }
+            actor.spawn();
+            tracing::info!(room = %room_id, "Restored room from snapshot");
+            self.rooms.insert(&room_id, handle);
+        }
+    }
 
-        let mut room = room_arc.lock().await;
+    /// Create a new room with the given ID and start its task.
+    ///
+    /// A multi-table tournament room is where entrants register; it becomes
+    /// the first table once the tournament starts.
+    ///
+    /// Returns an error if the settings are invalid or the room ID is taken.
+    pub fn create_room(&self, room_id: &str, settings: RoomSettings) -> Result<(), ProtocolError> {
+        if self.closing.load(Ordering::SeqCst) {
+            return Err(ProtocolError::ShuttingDown);
+        }
+        validate_settings(room_id, &settings).map_err(ProtocolError::invalid)?;
 
-        // Lock game_state, validate, add player, then drop before
-        // mutating player_senders to avoid overlapping borrows.
-        let (player_id, player_count) = {
-            let mut game_state = room.game_state.lock().await;
-            if game_state.game_started && !game_state.allow_late_entry {
-                return Err("Game already in progress".to_string());
+        self.rooms.update(|rooms| {
+            if rooms.contains_key(room_id) {
+                return Err(ProtocolError::RoomExists {
+                    room_id: room_id.to_string(),
+                });
+            }
+            let max_rooms = self.config.limits.max_rooms;
+            if max_rooms > 0 && rooms.len() >= max_rooms {
+                return Err(ProtocolError::TooManyRooms {
+                    max: max_rooms as u32,
+                });
             }
-            let player = if game_state.game_started {
-                // Late entry: give the frozen starting chip amount.
-                let chips = game_state.starting_chips;
-                let p = game_state.add_player_with_chips(player_name.to_string(), Some(chips));
-                // Late-joiners sit out until the next hand.
-                game_state.set_sitting_out(p.id);
-                p
+            let (mut room, gs) = Room::new(settings.clone(), Arc::clone(&self.config));
+            if settings.multi_table {
+                let tournament = Tournament::new(room_id, settings, self.rooms.clone());
+                room.tournament = Some(TableLink::new(tournament, room_id));
             } else {
-                game_state.add_player(player_name.to_string())
-            };
-            // First player to join becomes the host.
-            if game_state.host_id == 0 {
-                game_state.host_id = player.id;
+                room.snapshot_file = self.store.as_ref().map(|store| store.file(room_id));
             }
-            (player.id, game_state.player_count())
-        };
+            let (actor, handle) = RoomActor::new(room_id.to_string(), room, gs, self.rooms.clone());
+            actor.spawn();
+            rooms.insert(room_id.to_string(), handle);
+            METRICS.rooms_created.inc();
+            Ok(())
+        })
+    }
 
-        let session_token = generate_session_token();
-        room.register_session(player_id, session_token.clone());
+    /// Every room with its ID, sorted by ID.
+    pub fn all_rooms(&self) -> Vec<(String, RoomHandle)> {
+        self.rooms.all()
+    }
 
-        let (tx, rx) = mpsc::unbounded_channel();
-        room.player_senders.insert(player_id, tx);
+    /// Shut a room down at once: end its game, tell everyone in it why and
+    /// drop their connections.  Tables of a multi-table tournament cannot
+    /// be closed on their own.
+    pub async fn close_room(&self, room_id: &str, reason: &str) -> Result<(), String> {
+        let room = self.find_room(room_id).map_err(|e| e.to_string())?;
+        let reason = reason.to_string();
+        room.call(move |a| a.close(&reason))
+            .await
+            .unwrap_or_else(|| Err(format!("Room '{}' not found", room_id)))
+    }
 
-        // Notify existing players about the new player.
-        let join_msg = ServerMessage::PlayerJoined {
-            player_id,
-            name: player_name.to_string(),
+    /// Send an announcement to one room, or to every room when `room_id` is
+    /// `None`.  Returns how many rooms it went to.
+    pub fn announce(&self, message: &str, room_id: Option<&str>) -> Result<usize, String> {
+        let targets = match room_id {
+            Some(room_id) => vec![self.find_room(room_id).map_err(|e| e.to_string())?],
+            None => self.all_rooms().into_iter().map(|(_, room)| room).collect(),
+        };
+        let announcement = ServerMessage::Announcement {
+            message: message.to_string(),
         };
-        room.broadcast_except(&join_msg, player_id);
+        for room in &targets {
+            let announcement = announcement.clone();
+            room.run(move |a| a.room.notify_all(&announcement));
+        }
+        Ok(targets.len())
+    }
 
-        drop(room);
+    /// Look up a room by ID.
+    pub fn get_room(&self, room_id: &str) -> Option<RoomHandle> {
+        self.rooms.get(room_id)
+    }
 
-        Ok((player_id, session_token, player_count, rx, room_arc))
+    /// Look up a room by ID, or explain that there is none.
+    fn find_room(&self, room_id: &str) -> Result<RoomHandle, ProtocolError> {
+        self.get_room(room_id)
+            .ok_or_else(|| room_not_found(room_id))
     }
 
-    /// Reconnect a previously-disconnected player using their session token.
+    /// Find the room an invite belongs to when no room ID was given.
+    pub async fn resolve_room(&self, room_id: &str, invite: &str) -> Result<String, ProtocolError> {
+        if !room_id.is_empty() || invite.is_empty() {
+            return Ok(room_id.to_string());
+        }
+        for (room_id, room) in self.all_rooms() {
+            let invite = invite.to_string();
+            if room
+                .call(move |a| a.room.invites.contains_key(&invite))
+                .await
+                .unwrap_or(false)
+            {
+                return Ok(room_id);
+            }
+        }
+        Err(ProtocolError::InvalidInvite)
+    }
+
+    /// Add a player to a room, in the requested seat or a random empty one.
+    /// Entrants of a multi-table tournament are seated when it starts.
     ///
-    /// Returns the player_id and a new `PlayerRx` on success.
-    pub async fn rejoin_room(
+    /// `relocator` lets a multi-table tournament move the connection to
+    /// another table later.
+    pub async fn join_room(
         &self,
         room_id: &str,
-        session_token: &str,
-    ) -> Result<(u32, PlayerRx, Arc<Mutex<Room>>), String> {
-        let room_arc = self
-            .get_room(room_id)
+        player_name: &str,
+        seat: Option<u32>,
+        admission: Admission,
+        relocator: RelocationTx,
+    ) -> Result<Seated, ProtocolError> {
+        let room = self.find_room(room_id)?;
+        let player_name = player_name.to_string();
+        let (player_id, rx) = room
+            .call(move |a| a.join(&player_name, seat, &admission, relocator))
             .await
-            .ok_or_else(|| format!("Room '{}' not found", room_id))?;
-
-        let mut room = room_arc.lock().await;
+            .unwrap_or_else(|| Err(room_not_found(room_id)))?;
+        Ok(Seated {
+            room_id: room_id.to_string(),
+            room,
+            player_id,
+            rx,
+        })
+    }
 
-        let player_id = *room
-            .sessions
-            .get(session_token)
-            .ok_or_else(|| "Invalid or expired session token".to_string())?;
+    /// Start watching a room as a spectator.
+    pub async fn spectate(
+        &self,
+        room_id: &str,
+        name: &str,
+        admission: Admission,
+    ) -> Result<Watching, ProtocolError> {
+        let room = self.find_room(room_id)?;
+        let name = name.to_string();
+        let (spectator_id, rx) = room
+            .call(move |a| a.spectate(&name, &admission))
+            .await
+            .unwrap_or_else(|| Err(room_not_found(room_id)))?;
+        Ok(Watching {
+            room_id: room_id.to_string(),
+            room,
+            spectator_id,
+            rx,
+        })
+    }
 
-        // Verify the player still exists in game state.
-        let player_exists = {
-            let gs = room.game_state.lock().await;
-            gs.players.contains_key(&player_id)
-        };
-        if !player_exists {
-            // Token was valid but player was already fully removed.
-            room.sessions.remove(session_token);
-            room.player_sessions.remove(&player_id);
-            return Err("Session expired — player was removed".to_string());
+    /// Reconnect a previously-disconnected player using their session token.
+    ///
+    /// A multi-table tournament may have moved the player since they last
+    /// saw `room_id`, so the token is looked up in every room if the given
+    /// one does not know it.  `last_seq` numbers an event of `room_id`, so
+    /// another room sends a snapshot instead of replaying.
+    pub async fn rejoin_room(
+        &self,
+        room_id: &str,
+        session_token: &str,
+        last_seq: u64,
+        relocator: RelocationTx,
+    ) -> Result<Seated, ProtocolError> {
+        let result = self
+            .try_rejoin(room_id, session_token, last_seq, relocator)
+            .await;
+        match result {
+            Ok(_) => METRICS.reconnects.inc(),
+            Err(_) => METRICS.reconnect_failures.inc(),
         }
-
-        // Clear the disconnected-at timestamp (cancel grace period).
-        room.disconnected_at.remove(&player_id);
-
-        // Replace the sender channel.
-        let (tx, rx) = mpsc::unbounded_channel();
-        room.player_senders.insert(player_id, tx);
-
-        drop(room);
-        Ok((player_id, rx, room_arc))
+        result
     }
 
-    /// Soft-disconnect a player during a game: mark them as sitting out and
-    /// start a grace period.  Their game state is preserved.
-    pub async fn disconnect_player(&self, room_id: &str, player_id: u32) {
-        let rooms = self.rooms.read().await;
-        let Some(room_arc) = rooms.get(room_id) else {
-            return;
+    async fn try_rejoin(
+        &self,
+        room_id: &str,
+        session_token: &str,
+        last_seq: u64,
+        relocator: RelocationTx,
+    ) -> Result<Seated, ProtocolError> {
+        let requested = self.get_room(room_id);
+        let known = match &requested {
+            Some(room) => knows_session(room, session_token).await,
+            None => false,
         };
-
-        let mut room = room_arc.lock().await;
-        room.player_senders.remove(&player_id);
-
-        let game_in_progress = {
-            let mut gs = room.game_state.lock().await;
-            if gs.game_started && gs.players.contains_key(&player_id) {
-                // Sit the player out so auto-check/fold kicks in.
-                if !gs
-                    .players
-                    .get(&player_id)
-                    .map(|p| p.sitting_out)
-                    .unwrap_or(true)
-                {
-                    gs.set_sitting_out(player_id);
-                    room.broadcast(&ServerMessage::PlayerSatOut { player_id });
+        let (room_id, room) = match requested {
+            Some(room) if known => (room_id.to_string(), room),
+            requested => match self.find_session(session_token).await {
+                Some(found) => found,
+                None => {
+                    let room = requested.ok_or_else(|| room_not_found(room_id))?;
+                    (room_id.to_string(), room)
                 }
-                true
-            } else {
-                false
-            }
+            },
         };
+        let token = session_token.to_string();
+        let last_seq = if known { last_seq } else { 0 };
+        let (player_id, rx) = room
+            .call(move |a| a.rejoin(&token, last_seq, relocator))
+            .await
+            .unwrap_or_else(|| Err(room_not_found(&room_id)))?;
+        Ok(Seated {
+            room_id,
+            room,
+            player_id,
+            rx,
+        })
+    }
 
-        if game_in_progress {
-            // Keep the player in game state; start the grace-period countdown.
-            room.disconnected_at.insert(player_id, Instant::now());
-            tracing::info!(
-                room = room_id,
-                player = player_id,
-                "Player disconnected — seat held for {:?}",
-                SESSION_GRACE_PERIOD,
-            );
-
-            // Spawn a task that will permanently remove the player if they
-            // don't reconnect within the grace period.
-            let rm = self_ref(room_id, &self.rooms).await;
-            let rid = room_id.to_string();
-            let grace = SESSION_GRACE_PERIOD;
-            drop(room);
-            drop(rooms);
-
-            if let Some(rm) = rm {
-                tokio::spawn(async move {
-                    tokio::time::sleep(grace).await;
-                    let mut room = rm.lock().await;
-                    // Only remove if they're still marked as disconnected.
-                    if room
-                        .disconnected_at
-                        .get(&player_id)
-                        .is_some_and(|t| t.elapsed() >= grace)
-                    {
-                        room.disconnected_at.remove(&player_id);
-                        if let Some(token) = room.player_sessions.remove(&player_id) {
-                            room.sessions.remove(&token);
-                        }
-                        {
-                            let mut gs = room.game_state.lock().await;
-                            gs.remove_player(player_id);
-                        }
-                        room.broadcast(&ServerMessage::PlayerLeft { player_id });
-                        tracing::info!(
-                            room = %rid,
-                            player = player_id,
-                            "Grace period expired — player permanently removed"
-                        );
-                    }
-                });
-            }
-        } else {
-            // Game hasn't started — remove immediately.
-            if let Some(token) = room.player_sessions.remove(&player_id) {
-                room.sessions.remove(&token);
-            }
-            {
-                let mut gs = room.game_state.lock().await;
-                gs.remove_player(player_id);
+    /// Find the room holding a session token.
+    async fn find_session(&self, session_token: &str) -> Option<(String, RoomHandle)> {
+        for (room_id, room) in self.all_rooms() {
+            if knows_session(&room, session_token).await {
+                return Some((room_id, room));
             }
-            room.broadcast(&ServerMessage::PlayerLeft { player_id });
-
-            let is_empty = room.player_senders.is_empty();
-            drop(room);
-            drop(rooms);
-
-            if is_empty {
-                let mut rooms = self.rooms.write().await;
-                if let Some(room_arc) = rooms.get(room_id) {
-                    let room = room_arc.lock().await;
-                    if room.player_senders.is_empty() {
-                        drop(room);
-                        rooms.remove(room_id);
-                        tracing::info!(room_id, "Removed empty room");
-                    }
-                }
+        }
+        None
+    }
+
+    /// Summaries of the active rooms that are not unlisted, sorted by ID.
+    pub async fn list_rooms(&self) -> Vec<RoomSummary> {
+        let mut listed = Vec::new();
+        for (_, room) in self.all_rooms() {
+            let summary = room
+                .call(|a| (!a.room.unlisted).then(|| a.room.summary(&a.room_id, &a.gs)))
+                .await;
+            listed.extend(summary.flatten());
+        }
+        listed
+    }
+
+    /// Current room, player and spectator counts for `/metrics`.
+    pub async fn gauges(&self) -> Gauges {
+        let rooms = self.all_rooms();
+        let mut gauges = Gauges {
+            rooms: rooms.len(),
+            ..Gauges::default()
+        };
+        for (_, room) in rooms {
+            let counts = room
+                .call(|a| {
+                    (
+                        a.room.player_senders.len(),
+                        a.room.disconnected_at.len(),
+                        a.room.spectators.len(),
+                    )
+                })
+                .await;
+            if let Some((connected, disconnected, spectators)) = counts {
+                gauges.connected_players += connected;
+                gauges.disconnected_players += disconnected;
+                gauges.spectators += spectators;
             }
         }
+        gauges
+    }
+
+    /// Details of one room.  Unlisted rooms are found by ID too.
+    pub async fn room_details(&self, room_id: &str) -> Option<RoomDetails> {
+        let room = self.get_room(room_id)?;
+        room.call(|a| a.room.details(&a.room_id, &a.gs)).await
+    }
+}
+
+/// Check the settings of a room to be created.
+fn validate_settings(room_id: &str, settings: &RoomSettings) -> Result<(), String> {
+    validate_room_id(room_id)?;
+    validate_game_mode(&settings.game_mode)?;
+    validate_rebuy_config(&settings.rebuy_config, &settings.game_mode)?;
+    validate_table_size(settings.table_size)?;
+    validate_spectator_delay(&settings.spectator_delay)?;
+    validate_room_password(&settings.password)?;
+    if !settings.game_mode.is_cash() {
+        validate_payouts(&settings.payouts)?;
+    }
+    if settings.multi_table {
+        validate_multi_table(
+            room_id,
+            settings.table_size,
+            &settings.game_mode,
+            &settings.rebuy_config,
+        )?;
     }
+    Ok(())
+}
 
-    /// List active room IDs (for debugging / future API).
-    pub async fn list_rooms(&self) -> Vec<String> {
-        let rooms = self.rooms.read().await;
-        rooms.keys().cloned().collect()
+fn room_not_found(room_id: &str) -> ProtocolError {
+    ProtocolError::RoomNotFound {
+        room_id: room_id.to_string(),
     }
 }
 
+/// Whether a room has a session with this token.
+async fn knows_session(room: &RoomHandle, session_token: &str) -> bool {
+    let token = session_token.to_string();
+    room.call(move |a| a.room.sessions.contains_key(&token))
+        .await
+        .unwrap_or(false)
+}
+
 /// Generate a random session token (32-char hex string).
-fn generate_session_token() -> String {
+pub fn generate_session_token() -> String {
     use rand::RngExt;
     let mut rng = rand::rng();
     let bytes: [u8; 16] = rng.random();
     bytes.iter().map(|b| format!("{b:02x}")).collect()
 }
-
-/// Helper: get an `Arc<Mutex<Room>>` reference from `rooms` RwLock.
-async fn self_ref(
-    room_id: &str,
-    rooms: &RwLock<HashMap<String, Arc<Mutex<Room>>>>,
-) -> Option<Arc<Mutex<Room>>> {
-    let rooms = rooms.read().await;
-    rooms.get(room_id).cloned()
-}
@@ -0,0 +1,80 @@
+//! Spectator connections and their delayed outbound queues.
+//!
+//! Public room events reach spectators through a [`SpectatorFeed`]: each
+//! message is stamped with the time and hand it was sent in and held in a
+//! per-spectator queue until the room's [`SpectatorDelay`] has passed.  With
+//! no delay the queue forwards messages as soon as they arrive.
+//!
+//! The hand count is taken from the `NewHand` messages that pass through
+//! the room's spectator fan-out, so the feed needs no access to the game
+//! state.
+
+use std::time::{Duration, Instant};
+
+use poker_core::protocol::{ServerMessage, SpectatorDelay};
+use tokio::sync::{mpsc, watch};
+
+use crate::room::PlayerTx;
+
+/// A public message waiting to be shown to a spectator.
+struct Queued {
+    sent_at: Instant,
+    hand: u32,
+    msg: ServerMessage,
+}
+
+/// A connection watching a room without a seat.
+pub struct Spectator {
+    /// Name shown next to the spectator's chat messages.
+    pub name: String,
+    /// Direct line to the connection, for replies that are never delayed
+    /// (errors, pongs).
+    pub tx: PlayerTx,
+    /// Delayed line for everything that happens at the table.
+    pub feed: SpectatorFeed,
+}
+
+/// Sending half of one spectator's delayed queue.
+pub struct SpectatorFeed {
+    queue: mpsc::UnboundedSender<Queued>,
+    hand: watch::Receiver<u32>,
+}
+
+impl SpectatorFeed {
+    /// Start a queue that forwards into `tx` once `delay` has passed.
+    /// `hand` follows the number of the hand being played.
+    pub fn spawn(tx: PlayerTx, delay: SpectatorDelay, hand: watch::Receiver<u32>) -> Self {
+        let (queue, rx) = mpsc::unbounded_channel();
+        tokio::spawn(release(rx, tx, delay, hand.clone()));
+        Self { queue, hand }
+    }
+
+    /// Queue a message, stamped with the current time and hand.
+    pub fn send(&self, msg: ServerMessage) {
+        let _ = self.queue.send(Queued {
+            sent_at: Instant::now(),
+            hand: *self.hand.borrow(),
+            msg,
+        });
+    }
+}
+
+/// Forward queued messages in order, each once it is `delay` old.  Ends when
+/// the spectator leaves (the feed is dropped) or disconnects.
+async fn release(
+    mut rx: mpsc::UnboundedReceiver<Queued>,
+    tx: PlayerTx,
+    delay: SpectatorDelay,
+    mut hand: watch::Receiver<u32>,
+) {
+    let secs = Duration::from_secs(u64::from(delay.secs));
+    while let Some(queued) = rx.recv().await {
+        tokio::time::sleep_until((queued.sent_at + secs).into()).await;
+        let due = queued.hand.saturating_add(delay.hands);
+        // An error means the room is gone; release what is left at once.
+        let _ = hand.wait_for(|&current| current >= due).await;
+        if tx.send(queued.msg.into()).is_err() {
+            return;
+        }
+    }
+}
@@ -0,0 +1,359 @@
+//! Room snapshots on disk, so running games survive a server restart.
+//!
+//! Each room with a game in progress is written to `<dir>/<room_id>.json`
+//! between hands: players and stacks, the button, the blind level, the
+//! ledger and the session tokens.  On startup the snapshots are loaded back
+//! with every player disconnected, so clients can `Rejoin` with the tokens
+//! they already hold.  A hand that was being played when the server stopped
+//! is void — stacks are as they were before it was dealt.
+//!
+//! Multi-table tournaments span several rooms and are not persisted.
+
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use poker_core::protocol::{BlindConfig, GameMode, RebuyConfig, SpectatorDelay};
+use serde::{Deserialize, Serialize};
+use tokio::task::JoinHandle;
+
+use crate::config::Config;
+use crate::game_logic::{GamePhase, GameState, LedgerRecord, PausableClock, Player, PlayerStatus};
+use crate::replay::ReplayBuffer;
+use crate::room::{Room, RoomSettings};
+
+/// A directory of room snapshots.
+pub struct Store {
+    dir: PathBuf,
+}
+
+impl Store {
+    /// Use `dir` for snapshots, creating it if needed.
+    pub fn open(dir: impl Into<PathBuf>) -> std::io::Result<Self> {
+        let dir = dir.into();
+        std::fs::create_dir_all(&dir)?;
+        Ok(Self { dir })
+    }
+
+    /// The snapshot file of a room.
+    pub fn file(&self, room_id: &str) -> SnapshotFile {
+        SnapshotFile {
+            room_id: room_id.to_string(),
+            path: self.dir.join(format!("{room_id}.json")),
+            last_write: RefCell::new(None),
+        }
+    }
+
+    /// Read every snapshot in the directory.  Unreadable files are logged
+    /// and skipped.
+    pub fn load(&self) -> Vec<RoomSnapshot> {
+        let Ok(entries) = std::fs::read_dir(&self.dir) else {
+            return Vec::new();
+        };
+        let mut snapshots = Vec::new();
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().is_none_or(|ext| ext != "json") {
+                continue;
+            }
+            let snapshot = std::fs::read_to_string(&path)
+                .map_err(|e| e.to_string())
+                .and_then(|json| serde_json::from_str(&json).map_err(|e| e.to_string()));
+            match snapshot {
+                Ok(snapshot) => snapshots.push(snapshot),
+                Err(e) => tracing::warn!(path = %path.display(), "Skipping room snapshot: {e}"),
+            }
+        }
+        snapshots
+    }
+}
+
+/// Where one room's snapshot is kept.
+///
+/// Writes happen in the background, one after another in the order they
+/// were asked for, so a late save can never bring back a snapshot that was
+/// removed after it.
+pub struct SnapshotFile {
+    pub room_id: String,
+    path: PathBuf,
+    /// The latest write; the next one waits for it to finish.
+    last_write: RefCell<Option<JoinHandle<()>>>,
+}
+
+impl SnapshotFile {
+    /// Write the snapshot.  The file is replaced in one step, so a crash
+    /// never leaves half a snapshot behind.
+    pub fn save(&self, snapshot: &RoomSnapshot) {
+        let json = serde_json::to_string(snapshot);
+        let room_id = self.room_id.clone();
+        let path = self.path.clone();
+        self.write(move || {
+            let json = match json {
+                Ok(json) => json,
+                Err(e) => {
+                    tracing::warn!(room = %room_id, "Could not encode room snapshot: {e}");
+                    return;
+                }
+            };
+            let tmp = path.with_extension("json.tmp");
+            if let Err(e) = std::fs::write(&tmp, json).and_then(|()| std::fs::rename(&tmp, &path)) {
+                tracing::warn!(path = %path.display(), "Could not write room snapshot: {e}");
+            }
+        });
+    }
+
+    /// Delete the snapshot, if there is one.
+    pub fn remove(&self) {
+        let path = self.path.clone();
+        self.write(move || {
+            let _ = std::fs::remove_file(path);
+        });
+    }
+
+    /// The writes still running, to wait for at shutdown.
+    pub fn flush(&self) -> Option<JoinHandle<()>> {
+        self.last_write.take()
+    }
+
+    /// Run `write` on the blocking pool once the previous write is done.
+    fn write(&self, write: impl FnOnce() + Send + 'static) {
+        let previous = self.last_write.take();
+        let task = tokio::spawn(async move {
+            if let Some(previous) = previous {
+                let _ = previous.await;
+            }
+            let _ = tokio::task::spawn_blocking(write).await;
+        });
+        *self.last_write.borrow_mut() = Some(task);
+    }
+}
+
+/// A player as kept in a snapshot.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayerSnapshot {
+    pub id: u32,
+    pub name: String,
+    pub chips: u32,
+    /// Knocked out of the tournament.
+    pub out: bool,
+    pub pending_buy_in: u32,
+    pub seat: u32,
+    pub pending_seat: Option<u32>,
+}
+
+/// Everything needed to bring a room back between hands.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoomSnapshot {
+    pub room_id: String,
+    pub password: String,
+    pub unlisted: bool,
+    pub spectator_delay: SpectatorDelay,
+    pub allow_spectator_chat: bool,
+    pub banned_names: HashSet<String>,
+    pub banned_sessions: HashSet<String>,
+    /// Session tokens keyed to player IDs.
+    pub sessions: HashMap<String, u32>,
+    pub players: Vec<PlayerSnapshot>,
+    pub player_order: Vec<u32>,
+    pub next_player_id: u32,
+    pub host_id: u32,
+    pub hand_number: u32,
+    pub dealer_seat: Option<u32>,
+    pub table_size: u32,
+    pub small_blind: u32,
+    pub big_blind: u32,
+    pub blind_config: BlindConfig,
+    pub blind_level: u32,
+    /// Time on the blind clock, in seconds.
+    pub blind_clock_secs: u64,
+    pub pending_blinds: Option<(u32, u32, BlindConfig)>,
+    pub starting_bbs: u32,
+    pub starting_chips: u32,
+    pub allow_late_entry: bool,
+    pub game_mode: GameMode,
+    pub rebuy_config: RebuyConfig,
+    pub rebuy_period_over: bool,
+    pub payouts: Vec<u32>,
+    pub eliminations: Vec<Vec<u32>>,
+    pub ledger: HashMap<u32, LedgerRecord>,
+    /// Number of the room's last event, so that numbering carries on
+    /// after a restart.
+    #[serde(default)]
+    pub last_seq: u64,
+}
+
+impl RoomSnapshot {
+    /// Take a snapshot of a room between hands.
+    pub fn capture(room_id: &str, room: &Room, gs: &GameState) -> Self {
+        let players = gs
+            .players
+            .values()
+            .map(|p| PlayerSnapshot {
+                id: p.id,
+                name: p.name.clone(),
+                chips: p.chips,
+                out: p.status == PlayerStatus::Out,
+                pending_buy_in: p.pending_buy_in,
+                seat: p.seat,
+                pending_seat: p.pending_seat,
+            })
+            .collect();
+        Self {
+            room_id: room_id.to_string(),
+            password: room.password.clone(),
+            unlisted: room.unlisted,
+            spectator_delay: room.spectator_delay,
+            allow_spectator_chat: room.allow_spectator_chat,
+            banned_names: room.banned_names.clone(),
+            banned_sessions: room.banned_sessions.clone(),
+            sessions: room.sessions.clone(),
+            players,
+            player_order: gs.player_order.clone(),
+            next_player_id: gs.next_player_id,
+            host_id: gs.host_id,
+            hand_number: gs.hand_number,
+            dealer_seat: gs.dealer_seat,
+            table_size: gs.table_size,
+            small_blind: gs.small_blind,
+            big_blind: gs.big_blind,
+            blind_config: gs.blind_config,
+            blind_level: gs.blind_level,
+            blind_clock_secs: gs.blind_clock.elapsed().as_secs(),
+            pending_blinds: gs.pending_blinds,
+            starting_bbs: gs.starting_bbs,
+            starting_chips: gs.starting_chips,
+            allow_late_entry: gs.allow_late_entry,
+            game_mode: gs.game_mode,
+            rebuy_config: gs.rebuy_config,
+            rebuy_period_over: gs.rebuy_period_over,
+            payouts: gs.payouts.clone(),
+            eliminations: gs.eliminations.clone(),
+            ledger: gs.ledger.clone(),
+            last_seq: room.replay.borrow().last_seq(),
+        }
+    }
+
+    /// Rebuild the room.  Every player is sitting out, as if they had just
+    /// disconnected, and the game waits for them to sit back in.
+    pub fn restore(self, config: Arc<Config>) -> (Room, GameState) {
+        let (mut room, mut gs) = Room::new(
+            RoomSettings {
+                blind_config: self.blind_config,
+                starting_bbs: self.starting_bbs,
+                game_mode: self.game_mode,
+                rebuy_config: self.rebuy_config,
+                payouts: self.payouts,
+                multi_table: false,
+                table_size: self.table_size,
+                spectator_delay: self.spectator_delay,
+                password: self.password,
+                unlisted: self.unlisted,
+            },
+            config,
+        );
+        room.allow_spectator_chat = self.allow_spectator_chat;
+        room.banned_names = self.banned_names;
+        room.banned_sessions = self.banned_sessions;
+        room.player_sessions = self
+            .sessions
+            .iter()
+            .map(|(token, &id)| (id, token.clone()))
+            .collect();
+        room.sessions = self.sessions;
+        room.spectator_hand.send_replace(self.hand_number);
+        room.replay = RefCell::new(ReplayBuffer::after_restart(
+            room.config.limits.replay_events,
+            self.last_seq,
+        ));
+
+        gs.players = self
+            .players
+            .into_iter()
+            .map(|p| {
+                let player = Player {
+                    id: p.id,
+                    name: p.name,
+                    chips: p.chips,
+                    status: if p.out {
+                        PlayerStatus::Out
+                    } else {
+                        PlayerStatus::Waiting
+                    },
+                    hole_cards: None,
+                    current_bet: 0,
+                    sitting_out: true,
+                    pending_buy_in: p.pending_buy_in,
+                    leaving: false,
+                    hand_start_chips: p.chips,
+                    seat: p.seat,
+                    pending_seat: p.pending_seat,
+                };
+                (p.id, player)
+            })
+            .collect();
+        gs.player_order = self.player_order;
+        gs.next_player_id = self.next_player_id;
+        gs.host_id = self.host_id;
+        gs.hand_number = self.hand_number;
+        gs.dealer_seat = self.dealer_seat;
+        gs.small_blind = self.small_blind;
+        gs.big_blind = self.big_blind;
+        gs.blind_level = self.blind_level;
+        gs.blind_clock = PausableClock::stopped_at(Duration::from_secs(self.blind_clock_secs));
+        gs.pending_blinds = self.pending_blinds;
+        gs.starting_chips = self.starting_chips;
+        gs.allow_late_entry = self.allow_late_entry;
+        gs.rebuy_period_over = self.rebuy_period_over;
+        gs.eliminations = self.eliminations;
+        gs.ledger = self.ledger;
+        gs.game_started = true;
+        gs.phase = GamePhase::Lobby;
+        gs.hand_complete = true;
+        gs.set_waiting_for_players(true);
+        (room, gs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn writes_finish_in_the_order_they_were_made() {
+        let dir = std::env::temp_dir().join(format!("poker-store-test-{}", std::process::id()));
+        let store = Store::open(&dir).unwrap();
+        let file = store.file("friday");
+        let (room, gs) = Room::new(
+            RoomSettings {
+                blind_config: BlindConfig::default(),
+                starting_bbs: 50,
+                game_mode: GameMode::Tournament,
+                rebuy_config: RebuyConfig::default(),
+                payouts: vec![100],
+                multi_table: false,
+                table_size: 9,
+                spectator_delay: SpectatorDelay::default(),
+                password: String::new(),
+                unlisted: false,
+            },
+            Arc::new(Config::default()),
+        );
+        let snapshot = RoomSnapshot::capture("friday", &room, &gs);
+
+        for _ in 0..20 {
+            file.save(&snapshot);
+            file.save(&snapshot);
+            file.remove();
+        }
+        file.flush().unwrap().await.unwrap();
+        assert!(!dir.join("friday.json").exists());
+
+        file.remove();
+        file.save(&snapshot);
+        file.flush().unwrap().await.unwrap();
+        assert_eq!(store.load().len(), 1);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,780 @@
+//! Multi-table tournaments.
+//!
+//! A [`Tournament`] owns the tables of one event: the room the entrants
+//! registered in plus the extra tables opened when it starts.  Each table
+//! plays its own hands; between hands it reports its busted players to the
+//! tournament, picks up the shared blind level and asks whether any of its
+//! players should move to keep the tables balanced.  As the field shrinks
+//! the smallest tables are broken until everyone left sits at the final
+//! table.
+//!
+//! Moving a player does not need a reconnect: their connection receives a
+//! [`Relocation`] and switches to the new room in place.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::Instant;
+
+use poker_core::protocol::{BlindConfig, ServerMessage, Standing, payout_for_place};
+use rand::rng;
+use rand::seq::SliceRandom;
+use tokio::sync::mpsc;
+
+use crate::actor::{Command, RoomActor, RoomHandle, WeakRoomHandle};
+use crate::game_logic::{GameState, LedgerRecord, PausableClock, Player};
+use crate::requests::PlayerRequests;
+use crate::room::{PlayerTx, Relocation, RelocationTx, Room, RoomMap, RoomSettings};
+
+/// Ties a room to the tournament it is a table of.
+///
+/// The tables share the tournament behind a plain mutex: it is only held
+/// for bookkeeping, never across an `await` or while waiting on a table.
+#[derive(Clone)]
+pub struct TableLink {
+    pub tournament: Arc<Mutex<Tournament>>,
+    /// ID of the room this link belongs to.
+    pub room_id: String,
+}
+
+impl TableLink {
+    pub fn new(tournament: Tournament, room_id: &str) -> Self {
+        Self {
+            tournament: Arc::new(Mutex::new(tournament)),
+            room_id: room_id.to_string(),
+        }
+    }
+
+    pub fn lock(&self) -> MutexGuard<'_, Tournament> {
+        self.tournament
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// A table still in play.
+struct Table {
+    room_id: String,
+    room: WeakRoomHandle,
+    /// Players with chips, including those on their way to this table.
+    players: usize,
+    /// Players moved here who have not been seated yet.
+    incoming: usize,
+    /// True while the table cannot deal for lack of active players.
+    idle: bool,
+}
+
+/// A player on their way from one table to another.
+pub struct Mover {
+    pub player: Player,
+    pub record: Option<LedgerRecord>,
+    pub session_token: Option<String>,
+    /// Outbound channel of the player's connection, if they are connected.
+    pub tx: Option<PlayerTx>,
+    /// Relocation handle of that connection, for later moves.
+    pub relocator: Option<RelocationTx>,
+    pub disconnected_at: Option<Instant>,
+    /// Answers to the player's numbered actions, so retries stay safe.
+    pub requests: Option<PlayerRequests>,
+}
+
+/// Players leaving one table for another between hands.
+pub struct Transfer {
+    pub room_id: String,
+    pub room: RoomHandle,
+    pub movers: Vec<Mover>,
+}
+
+/// What a table has to do after reporting to the tournament.
+pub enum BetweenHands {
+    /// Keep playing after sending these players to other tables.
+    Continue(Vec<Transfer>),
+    /// The tournament is over.
+    Finished,
+}
+
+/// State shared by all tables of a multi-table tournament.
+pub struct Tournament {
+    id: String,
+    settings: RoomSettings,
+    table_size: usize,
+    rooms: RoomMap,
+    tables: Vec<Table>,
+    /// Every table ever opened.  Busted players stay in the room they
+    /// busted in, so the final results go to broken tables too.
+    all_tables: Vec<(String, WeakRoomHandle)>,
+    next_table: u32,
+    started: bool,
+    finished: bool,
+    names: HashMap<u32, String>,
+    players_remaining: usize,
+    /// Eliminations across all tables, first out first.
+    eliminations: Vec<Vec<u32>>,
+    prize_pool: u32,
+    blind_config: BlindConfig,
+    small_blind: u32,
+    big_blind: u32,
+    blind_level: u32,
+    blind_clock: PausableClock,
+}
+
+impl Tournament {
+    pub fn new(id: &str, settings: RoomSettings, rooms: RoomMap) -> Self {
+        Self {
+            id: id.to_string(),
+            table_size: settings.table_size as usize,
+            blind_config: settings.blind_config,
+            settings,
+            rooms,
+            tables: Vec::new(),
+            all_tables: Vec::new(),
+            next_table: 2,
+            started: false,
+            finished: false,
+            names: HashMap::new(),
+            players_remaining: 0,
+            eliminations: Vec::new(),
+            prize_pool: 0,
+            small_blind: 0,
+            big_blind: 0,
+            blind_level: 1,
+            blind_clock: PausableClock::default(),
+        }
+    }
+
+    /// Whether the tournament has been started (it runs only once).
+    pub fn started(&self) -> bool {
+        self.started
+    }
+
+    /// Whether the tournament has started and is not over yet.
+    pub fn in_play(&self) -> bool {
+        self.started && !self.finished
+    }
+
+    /// Progress message for the tables' players.
+    pub fn progress(&self) -> ServerMessage {
+        ServerMessage::TournamentUpdate {
+            entrants: self.names.len() as u32,
+            players_remaining: self.players_remaining as u32,
+            tables: self.tables.len() as u32,
+        }
+    }
+
+    /// Current blinds and level of the shared clock, raising them if the
+    /// level is up.
+    pub fn current_blinds(&mut self) -> (u32, u32, u32) {
+        if self.blind_config.is_enabled()
+            && self.blind_clock.elapsed().as_secs() >= self.blind_config.interval_secs
+        {
+            let pct = self.blind_config.increase_percent;
+            self.small_blind += (self.small_blind * pct).div_ceil(100);
+            self.big_blind += (self.big_blind * pct).div_ceil(100);
+            self.blind_level += 1;
+            self.blind_clock.restart();
+        }
+        (self.small_blind, self.big_blind, self.blind_level)
+    }
+
+    /// Place a table's busted players.  Groups are first out first; players
+    /// within a group tie.
+    fn record_busts(&mut self, groups: Vec<Vec<u32>>) -> Vec<ServerMessage> {
+        let busted: usize = groups.iter().map(Vec::len).sum();
+        self.players_remaining = self.players_remaining.saturating_sub(busted);
+        let mut messages = Vec::new();
+        let mut place = self.players_remaining as u32 + 1;
+        for group in groups.iter().rev() {
+            for &player_id in group {
+                messages.push(ServerMessage::PlayerEliminated { player_id, place });
+            }
+            place += group.len() as u32;
+        }
+        self.eliminations.extend(groups);
+        messages
+    }
+
+    /// Final standings with `winner_id` first.
+    fn final_standings(&self, winner_id: u32) -> Vec<Standing> {
+        let name = |id: u32| {
+            self.names
+                .get(&id)
+                .cloned()
+                .unwrap_or_else(|| format!("Player #{id}"))
+        };
+        let payouts = &self.settings.payouts;
+        let mut standings = vec![Standing {
+            place: 1,
+            player_id: winner_id,
+            name: name(winner_id),
+            payout: payout_for_place(self.prize_pool, payouts, 1, 1),
+        }];
+        let mut place = 2;
+        for group in self.eliminations.iter().rev() {
+            let tied = group.len() as u32;
+            let payout = payout_for_place(self.prize_pool, payouts, place, tied);
+            for &player_id in group {
+                standings.push(Standing {
+                    place,
+                    player_id,
+                    name: name(player_id),
+                    payout,
+                });
+            }
+            place += tied;
+        }
+        standings
+    }
+
+    /// Messages announcing the winner and the final standings.
+    fn results(&self, winner_id: u32) -> Vec<ServerMessage> {
+        vec![
+            ServerMessage::PrizePool {
+                total: self.prize_pool,
+            },
+            ServerMessage::GameOver {
+                winner_id,
+                winner_name: self.names.get(&winner_id).cloned().unwrap_or_default(),
+            },
+            ServerMessage::FinalStandings {
+                standings: self.final_standings(winner_id),
+                prize_pool: self.prize_pool,
+            },
+        ]
+    }
+
+    /// Decide how many of a table's `alive` players leave it, and where to.
+    ///
+    /// The smallest table is broken once the others have enough seats for
+    /// everyone (an idle table does not count as smallest, so a lone player
+    /// left waiting is merged into rather than stranded).  Otherwise a table
+    /// with two or more players more than the smallest one gives up a seat.
+    fn plan_moves(
+        &mut self,
+        room_id: &str,
+        alive: usize,
+        idle: bool,
+    ) -> Vec<(String, WeakRoomHandle)> {
+        let Some(idx) = self.tables.iter().position(|t| t.room_id == room_id) else {
+            return Vec::new();
+        };
+        self.tables[idx].players = alive + self.tables[idx].incoming;
+        self.tables[idx].idle = idle;
+        if self.tables.len() < 2 || self.tables[idx].incoming > 0 {
+            return Vec::new();
+        }
+
+        let count = self.tables[idx].players;
+        let smallest_active = self
+            .tables
+            .iter()
+            .enumerate()
+            .filter(|&(i, t)| i != idx && !t.idle)
+            .map(|(_, t)| t.players)
+            .min()
+            .unwrap_or(usize::MAX);
+        let needed = self.players_remaining.div_ceil(self.table_size).max(1);
+
+        let breaking = self.tables.len() > needed && count <= smallest_active;
+        let moves = if breaking {
+            count
+        } else {
+            let smallest = self
+                .tables
+                .iter()
+                .enumerate()
+                .filter(|&(i, _)| i != idx)
+                .map(|(_, t)| t.players)
+                .min()
+                .unwrap_or(count);
+            if count >= smallest + 2 { 1 } else { 0 }
+        };
+
+        // Pick a destination for each mover, smallest table first.
+        let mut seats: Vec<usize> = self.tables.iter().map(|t| t.players).collect();
+        let mut destinations = Vec::new();
+        for _ in 0..moves {
+            let dest = (0..self.tables.len())
+                .filter(|&i| i != idx && seats[i] < self.table_size)
+                .min_by_key(|&i| seats[i]);
+            let Some(dest) = dest else {
+                // Stale counts from other tables; try again next hand.
+                return Vec::new();
+            };
+            seats[dest] += 1;
+            destinations.push(dest);
+        }
+        for &dest in &destinations {
+            self.tables[dest].players += 1;
+            self.tables[dest].incoming += 1;
+        }
+        self.tables[idx].players -= moves;
+        let destinations = destinations
+            .into_iter()
+            .map(|i| (self.tables[i].room_id.clone(), self.tables[i].room.clone()))
+            .collect();
+        if breaking {
+            let table = self.tables.remove(idx);
+            tracing::info!(tournament = %self.id, table = %table.room_id, "Table broken");
+        }
+        destinations
+    }
+
+    /// A table finished seating players moved to it.
+    pub fn seated(&mut self, room_id: &str, count: usize) {
+        if let Some(table) = self.tables.iter_mut().find(|t| t.room_id == room_id) {
+            table.incoming = table.incoming.saturating_sub(count);
+        }
+    }
+}
+
+/// Start a multi-table tournament from its registration room.
+///
+/// Seats the entrants at random, opening as many extra tables as needed.
+/// The registration room's game state (`gs`) must already be marked as
+/// started; `own` is its handle.  Returns the tasks of the extra tables,
+/// which still need their first hand dealt and to be spawned.
+pub fn start(
+    gs: &mut GameState,
+    room: &mut Room,
+    own: &WeakRoomHandle,
+    link: &TableLink,
+) -> Vec<RoomActor> {
+    let mut t = link.lock();
+    t.started = true;
+    t.names = gs
+        .players
+        .values()
+        .map(|p| (p.id, p.name.clone()))
+        .collect();
+    t.players_remaining = gs.players.len();
+    t.prize_pool = gs.starting_chips * gs.players.len() as u32;
+    t.small_blind = gs.small_blind;
+    t.big_blind = gs.big_blind;
+    t.blind_level = 1;
+    if t.blind_config.is_enabled() {
+        t.blind_clock.restart();
+    }
+    join_tournament(gs, &t);
+
+    let mut ids = gs.player_order.clone();
+    ids.shuffle(&mut rng());
+    let table_count = ids.len().div_ceil(t.table_size).max(1);
+    let mut seating: Vec<Vec<u32>> = vec![Vec::new(); table_count];
+    for (i, id) in ids.into_iter().enumerate() {
+        seating[i % table_count].push(id);
+    }
+    let mut seating = seating.into_iter();
+    gs.player_order = seating.next().unwrap_or_default();
+
+    t.tables.push(Table {
+        room_id: link.room_id.clone(),
+        room: own.clone(),
+        players: gs.player_order.len(),
+        incoming: 0,
+        idle: false,
+    });
+    t.all_tables.push((link.room_id.clone(), own.clone()));
+
+    let mut new_tables = Vec::new();
+    let mut moved = Vec::new();
+    for ids in seating {
+        let table_id = format!("{}t{}", t.id, t.next_table);
+        t.next_table += 1;
+
+        let (mut table, mut table_gs) = Room::new(t.settings.clone(), Arc::clone(&room.config));
+        table.tournament = Some(TableLink {
+            tournament: Arc::clone(&link.tournament),
+            room_id: table_id.clone(),
+        });
+        table_gs.game_started = true;
+        table_gs.starting_chips = gs.starting_chips;
+        join_tournament(&mut table_gs, &t);
+        let (mut table, handle) =
+            RoomActor::new(table_id.clone(), table, table_gs, t.rooms.clone());
+
+        let movers: Vec<Mover> = ids
+            .iter()
+            .filter_map(|&id| unseat(gs, room, id, &table_id, &handle))
+            .collect();
+        t.tables.push(Table {
+            room_id: table_id.clone(),
+            room: handle.downgrade(),
+            players: movers.len(),
+            incoming: 0,
+            idle: movers.len() < 2,
+        });
+        t.all_tables.push((table_id.clone(), handle.downgrade()));
+        moved.extend(table.seat_movers(movers));
+
+        // Register the table for lookups.
+        t.rooms.insert(&table_id, handle);
+        new_tables.push(table);
+    }
+
+    // The registration room lets go of the players seated elsewhere.
+    forget_movers(room, &moved);
+
+    // Entrants left at the registration table draw their seats too.
+    for msg in gs.reseat_randomly() {
+        room.broadcast(&msg);
+    }
+
+    let progress = t.progress();
+    room.broadcast(&progress);
+    for table in &new_tables {
+        table.room.broadcast(&progress);
+    }
+    tracing::info!(
+        tournament = %t.id,
+        entrants = t.names.len(),
+        tables = t.tables.len(),
+        "Multi-table tournament started"
+    );
+    new_tables
+}
+
+/// Copy the tournament-wide settings onto one of its tables.
+fn join_tournament(gs: &mut GameState, t: &Tournament) {
+    gs.multi_table = true;
+    gs.shared_prize_pool = Some(t.prize_pool);
+    // The tournament runs the blind clock for all tables.
+    gs.blind_config = BlindConfig::default();
+    gs.small_blind = t.small_blind;
+    gs.big_blind = t.big_blind;
+    gs.blind_level = t.blind_level;
+}
+
+/// Take a player off `room` and point their connection at `dest`.
+fn unseat(
+    gs: &mut GameState,
+    room: &Room,
+    player_id: u32,
+    dest_id: &str,
+    dest: &RoomHandle,
+) -> Option<Mover> {
+    let (player, record) = gs.unseat_player(player_id)?;
+    let relocator = room.relocators.get(&player_id).cloned();
+    let tx = relocator.as_ref().map(|relocator| {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let _ = relocator.send(Relocation {
+            room_id: dest_id.to_string(),
+            room: dest.clone(),
+            rx,
+        });
+        tx
+    });
+    room.broadcast_except(&ServerMessage::PlayerLeft { player_id }, player_id);
+    Some(Mover {
+        player,
+        record,
+        session_token: room.player_sessions.get(&player_id).cloned(),
+        tx,
+        relocator,
+        disconnected_at: room.disconnected_at.get(&player_id).copied(),
+        requests: room.requests.of(player_id),
+    })
+}
+
+/// Seat players moved to `table` and send each of them a snapshot of it.
+pub fn seat_movers(table: &mut Room, gs: &mut GameState, table_id: &str, movers: Vec<Mover>) {
+    let mut seated = Vec::new();
+    for mover in movers {
+        let player_id = mover.player.id;
+        let name = mover.player.name.clone();
+        gs.seat_player(mover.player, mover.record);
+        let seat = gs.players.get(&player_id).map_or(0, |p| p.seat);
+        if let Some(token) = mover.session_token {
+            table.register_session(player_id, token);
+        }
+        if let Some(since) = mover.disconnected_at {
+            table.disconnected_at.insert(player_id, since);
+        }
+        if let Some(requests) = mover.requests {
+            table.requests.restore(player_id, requests);
+        }
+        table.broadcast(&ServerMessage::PlayerJoined {
+            player_id,
+            name,
+            seat,
+        });
+        if let Some(tx) = mover.tx {
+            table.player_senders.insert(player_id, tx);
+        }
+        if let Some(relocator) = mover.relocator {
+            table.relocators.insert(player_id, relocator);
+        }
+        seated.push(player_id);
+    }
+    for player_id in seated {
+        let token = table
+            .player_sessions
+            .get(&player_id)
+            .cloned()
+            .unwrap_or_default();
+        table.send_to_player(
+            player_id,
+            &ServerMessage::TableMoved {
+                room_id: table_id.to_string(),
+            },
+        );
+        let snapshot = table.build_rejoin_snapshot(gs, table_id, player_id, &token);
+        table.send_to_player(player_id, &snapshot);
+    }
+}
+
+/// Forget the connections and sessions of players who moved away from
+/// `room`.
+pub fn forget_movers(room: &mut Room, player_ids: &[u32]) {
+    for &player_id in player_ids {
+        room.detach_player(player_id);
+    }
+}
+
+/// Report a finished hand at one table to the tournament.
+///
+/// Announces the table's busted players with their places, then either
+/// ends the tournament or works out which players leave this table to
+/// balance the field.  Movers are taken off `gs` and their connections are
+/// pointed at the new table; the returned transfers still need to seat
+/// them there.
+pub fn between_hands(gs: &mut GameState, room: &Room, link: &TableLink) -> BetweenHands {
+    let mut t = link.lock();
+    if t.finished {
+        return BetweenHands::Finished;
+    }
+
+    let busts = std::mem::take(&mut gs.pending_busts);
+    for m in &t.record_busts(busts) {
+        room.broadcast(m);
+    }
+
+    let alive: Vec<u32> = gs
+        .player_order
+        .iter()
+        .copied()
+        .filter(|id| gs.players.get(id).is_some_and(|p| p.chips > 0))
+        .collect();
+
+    if t.players_remaining <= 1 {
+        t.finished = true;
+        let winner_id = alive.first().copied().unwrap_or(0);
+        let results = t.results(winner_id);
+        for m in &results {
+            room.broadcast(m);
+        }
+        // Players who busted at other tables get the results too.
+        for (_, table) in t
+            .all_tables
+            .iter()
+            .filter(|(room_id, _)| *room_id != link.room_id)
+        {
+            if let Some(table) = table.upgrade() {
+                table.send(Command::Broadcast(results.clone()));
+            }
+        }
+        tracing::info!(tournament = %t.id, winner = winner_id, "Multi-table tournament finished");
+        return BetweenHands::Finished;
+    }
+
+    // Only players out of the hand can be moved.
+    if gs.hand_in_progress() {
+        return BetweenHands::Continue(Vec::new());
+    }
+
+    let active = alive
+        .iter()
+        .filter(|id| gs.players.get(id).is_some_and(|p| !p.sitting_out))
+        .count();
+    let destinations = t.plan_moves(&link.room_id, alive.len(), active < 2);
+    room.broadcast(&t.progress());
+    drop(t);
+
+    let mut picks = alive;
+    picks.shuffle(&mut rng());
+    let mut transfers: Vec<Transfer> = Vec::new();
+    for ((dest_id, dest), player_id) in destinations.into_iter().zip(picks) {
+        let Some(dest) = dest.upgrade() else {
+            continue;
+        };
+        let Some(mover) = unseat(gs, room, player_id, &dest_id, &dest) else {
+            continue;
+        };
+        match transfers.iter_mut().find(|tr| tr.room_id == dest_id) {
+            Some(transfer) => transfer.movers.push(mover),
+            None => transfers.push(Transfer {
+                room_id: dest_id,
+                room: dest,
+                movers: vec![mover],
+            }),
+        }
+    }
+    BetweenHands::Continue(transfers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use poker_core::protocol::{GameMode, RebuyConfig, SpectatorDelay};
+
+    fn settings(table_size: u32) -> RoomSettings {
+        RoomSettings {
+            blind_config: BlindConfig::default(),
+            starting_bbs: 50,
+            game_mode: GameMode::Tournament,
+            rebuy_config: RebuyConfig::default(),
+            payouts: vec![100],
+            multi_table: true,
+            table_size,
+            spectator_delay: SpectatorDelay::default(),
+            password: String::new(),
+            unlisted: false,
+        }
+    }
+
+    /// A room that is never spawned; its handle stays usable for as long
+    /// as the actor is kept.
+    fn room(table_size: u32) -> (RoomActor, RoomHandle) {
+        let (room, gs) = Room::new(settings(table_size), Arc::new(Config::default()));
+        RoomActor::new("t".to_string(), room, gs, RoomMap::default())
+    }
+
+    /// A started tournament with tables `t1`, `t2`, ... of `counts`
+    /// players.
+    fn tournament(table_size: u32, counts: &[usize]) -> (Tournament, Vec<(RoomActor, RoomHandle)>) {
+        let mut t = Tournament::new("t", settings(table_size), RoomMap::default());
+        t.started = true;
+        t.players_remaining = counts.iter().sum();
+        let rooms: Vec<_> = counts.iter().map(|_| room(table_size)).collect();
+        for (i, (&players, (_, handle))) in counts.iter().zip(&rooms).enumerate() {
+            t.tables.push(Table {
+                room_id: format!("t{}", i + 1),
+                room: handle.downgrade(),
+                players,
+                incoming: 0,
+                idle: false,
+            });
+        }
+        (t, rooms)
+    }
+
+    /// Let every table report in turn until nobody moves, carrying out
+    /// the planned moves.  Returns the final player count of each table.
+    fn settle(t: &mut Tournament) -> Vec<(String, usize)> {
+        let mut alive: HashMap<String, usize> = t
+            .tables
+            .iter()
+            .map(|table| (table.room_id.clone(), table.players))
+            .collect();
+        for _ in 0..20 {
+            let mut moved = false;
+            let ids: Vec<String> = t.tables.iter().map(|t| t.room_id.clone()).collect();
+            for id in ids {
+                let destinations = t.plan_moves(&id, alive[&id], false);
+                moved |= !destinations.is_empty();
+                *alive.get_mut(&id).unwrap() -= destinations.len();
+                for (dest, _) in destinations {
+                    *alive.get_mut(&dest).unwrap() += 1;
+                    t.seated(&dest, 1);
+                }
+            }
+            if !moved {
+                break;
+            }
+        }
+        t.tables
+            .iter()
+            .map(|table| (table.room_id.clone(), alive[&table.room_id]))
+            .collect()
+    }
+
+    #[test]
+    fn tables_are_balanced_to_within_one_player() {
+        let (mut t, _rooms) = tournament(9, &[9, 9, 4]);
+        let tables = settle(&mut t);
+        let counts: Vec<usize> = tables.iter().map(|&(_, n)| n).collect();
+        assert_eq!(counts.len(), 3);
+        assert_eq!(counts.iter().sum::<usize>(), 22);
+        let (min, max) = (counts.iter().min().unwrap(), counts.iter().max().unwrap());
+        assert!(max - min <= 1, "unbalanced tables: {counts:?}");
+    }
+
+    #[test]
+    fn smallest_table_is_broken_once_the_others_have_room() {
+        let (mut t, _rooms) = tournament(9, &[5, 4, 3]);
+        let destinations = t.plan_moves("t3", 3, false);
+        assert_eq!(destinations.len(), 3);
+        assert!(destinations.iter().all(|(dest, _)| dest != "t3"));
+        let tables: Vec<(&str, usize)> = t
+            .tables
+            .iter()
+            .map(|table| (table.room_id.as_str(), table.players))
+            .collect();
+        assert_eq!(tables, vec![("t1", 6), ("t2", 6)]);
+    }
+
+    #[test]
+    fn table_is_kept_while_the_others_are_too_full() {
+        let (mut t, _rooms) = tournament(9, &[9, 9, 8]);
+        assert!(t.plan_moves("t3", 8, false).is_empty());
+        assert_eq!(t.tables.len(), 3);
+    }
+
+    #[test]
+    fn registration_room_lets_go_of_players_seated_elsewhere() {
+        let link = TableLink::new(Tournament::new("t", settings(6), RoomMap::default()), "t");
+        let (mut room, mut gs) = Room::new(settings(6), Arc::new(Config::default()));
+        let (_actor, own) = self::room(6);
+        for seat in 0..12 {
+            let id = gs.add_player(format!("P{}", seat + 1), seat).id;
+            room.register_session(id, format!("token{id}"));
+            room.player_senders.insert(id, mpsc::unbounded_channel().0);
+            room.relocators.insert(id, mpsc::unbounded_channel().0);
+            room.requests.record(id, 1, Ok(()));
+        }
+        gs.game_started = true;
+        gs.starting_chips = 1000;
+
+        let tables = start(&mut gs, &mut room, &own.downgrade(), &link);
+        assert_eq!(tables.len(), 1);
+        assert_eq!(gs.player_count(), 6);
+        for id in 1..=12 {
+            let seated = gs.players.contains_key(&id);
+            assert_eq!(room.player_senders.contains_key(&id), seated);
+            assert_eq!(room.player_sessions.contains_key(&id), seated);
+            assert_eq!(room.relocators.contains_key(&id), seated);
+            assert_eq!(room.requests.of(id).is_some(), seated);
+            assert_eq!(room.sessions.contains_key(&format!("token{id}")), seated);
+        }
+        assert_eq!(room.sessions.len(), 6);
+    }
+
+    #[test]
+    fn players_in_a_hand_are_never_moved() {
+        let (t, rooms) = tournament(9, &[9, 5]);
+        let link = TableLink::new(t, "t1");
+        let (room, mut gs) = Room::new(settings(9), Arc::new(Config::default()));
+        gs.multi_table = true;
+        gs.game_started = true;
+        for seat in 0..9 {
+            gs.add_player(format!("P{}", seat + 1), seat);
+        }
+
+        gs.start_new_hand();
+        let BetweenHands::Continue(transfers) = between_hands(&mut gs, &room, &link) else {
+            panic!("the tournament is not over");
+        };
+        assert!(transfers.is_empty());
+        assert_eq!(gs.player_count(), 9);
+
+        // Once the hand is over one player goes to the short table.
+        gs.hand_complete = true;
+        let BetweenHands::Continue(transfers) = between_hands(&mut gs, &room, &link) else {
+            panic!("the tournament is not over");
+        };
+        assert_eq!(transfers.len(), 1);
+        assert_eq!(transfers[0].room_id, "t2");
+        assert_eq!(transfers[0].movers.len(), 1);
+        assert_eq!(gs.player_count(), 8);
+        drop(rooms);
+    }
+}
@@ -6,18 +6,56 @@
 //!
 //! This module is specific to the TUI binary.
 
-use crate::tui::{Tui, UserIntent};
+use crate::tui::{PickerIntent, Tui, UserIntent};
 use poker_client::client_controller::{ClientController, PollResult};
-use poker_core::protocol::{BlindConfig, ClientMessage};
+use poker_client::game_state::LogCategory;
+use poker_client::session::{self, MAX_RECONNECT_ATTEMPTS};
+use poker_core::protocol::{
+    BlindConfig, ClientMessage, Encoding, GameMode, RebuyConfig, SpectatorDelay,
+};
+
+/// Settings for a room created from the command line.
+pub struct RoomOptions {
+    pub game_mode: GameMode,
+    pub rebuy_config: RebuyConfig,
+    pub payouts: Vec<u32>,
+    /// Spread a tournament over several tables.
+    pub multi_table: bool,
+    /// Seats at the table (per table in a multi-table tournament).
+    pub table_size: u32,
+    /// How far spectators lag behind the table.
+    pub spectator_delay: SpectatorDelay,
+    /// Hide the room from the public room list.
+    pub unlisted: bool,
+}
+
+/// What gets us into a private room.
+#[derive(Default)]
+pub struct Credentials {
+    /// The room password; when creating, the password to set.
+    pub password: String,
+    /// An invite token, which also stands in for the room ID.
+    pub invite: String,
+}
 
 /// Start the poker client, connecting via WebSocket to the given server/room.
 ///
-/// If `create` is true, sends `CreateRoom` before `JoinRoom`.
+/// If `create` is given, sends `CreateRoom` with those settings before
+/// `JoinRoom`.  `seat` picks a seat (0-based); otherwise the server picks a
+/// random empty one.  With `spectate` the client watches the room instead of
+/// joining it.  `credentials` get us into a password-protected room.  With
+/// neither a room ID nor an invite the user picks a room from the list.
+/// `encoding` is asked for in the handshake.
+#[allow(clippy::too_many_arguments)]
 pub async fn start_client(
     server_url: &str,
     room_id: &str,
     name: &str,
-    create: bool,
+    create: Option<RoomOptions>,
+    seat: Option<u32>,
+    spectate: bool,
+    credentials: Credentials,
+    encoding: Encoding,
 ) -> Result<(), Box<dyn std::error::Error>> {
     // Build the WS URL (append /ws if the user didn't already).
     let ws_url = if server_url.ends_with("/ws") {
@@ -26,33 +64,66 @@ pub async fn start_client(
         format!("{}/ws", server_url.trim_end_matches('/'))
     };
 
-    let mut ctrl = ClientController::connect_ws(&ws_url, name).await?;
+    let mut ctrl = ClientController::connect_ws(&ws_url, name, encoding).await?;
+
+    let room_id = if room_id.is_empty() && credentials.invite.is_empty() {
+        let mut tui = Tui::setup()?;
+        let picked = pick_room(&mut tui, &mut ctrl).await;
+        tui.teardown()?;
+        match picked? {
+            Some(room_id) => room_id,
+            None => return Ok(()),
+        }
+    } else {
+        room_id.to_string()
+    };
 
-    // Send CreateRoom (if requested) then JoinRoom.
-    if create {
+    // Send CreateRoom (if requested) then JoinRoom, or Spectate to watch.
+    if let Some(options) = create {
         ctrl.send(ClientMessage::CreateRoom {
-            room_id: room_id.to_string(),
+            room_id: room_id.clone(),
             blind_config: BlindConfig::default(),
             starting_bbs: 50,
+            game_mode: options.game_mode,
+            rebuy_config: options.rebuy_config,
+            payouts: options.payouts,
+            multi_table: options.multi_table,
+            table_size: options.table_size,
+            spectator_delay: options.spectator_delay,
+            password: credentials.password.clone(),
+            unlisted: options.unlisted,
+        });
+    }
+    let Credentials { password, invite } = credentials;
+    if spectate {
+        ctrl.send(ClientMessage::Spectate {
+            room_id: room_id.clone(),
+            name: name.to_string(),
+            password,
+            invite,
+        });
+    } else {
+        ctrl.send(ClientMessage::JoinRoom {
+            room_id: room_id.clone(),
+            name: name.to_string(),
+            seat,
+            password,
+            invite,
         });
     }
-    ctrl.send(ClientMessage::JoinRoom {
-        room_id: room_id.to_string(),
-        name: name.to_string(),
-    });
 
     // Wait for room confirmation before entering the TUI.
     loop {
         match ctrl.recv().await {
             PollResult::Updated(changed) => {
-                if (changed.phase || changed.players) && ctrl.state.our_player_id != 0 {
+                if (changed.phase || changed.players)
+                    && (ctrl.state.our_player_id != 0 || ctrl.state.spectating)
+                {
                     break; // Successfully joined.
                 }
-                // Check for room errors surfaced as events.
-                if let Some(last) = ctrl.state.events.back()
-                    && let poker_client::game_state::GameEvent::ServerError { message } = last
-                {
-                    return Err(message.clone().into());
+                if changed.error {
+                    let error = ctrl.state.last_error.clone().unwrap_or_default();
+                    return Err(error.to_string().into());
                 }
             }
             PollResult::Disconnected => {
@@ -64,11 +135,51 @@ pub async fn start_client(
 
     // Launch TUI and run the main event loop.
     let mut tui = Tui::setup()?;
-    let result = run_event_loop(&mut tui, &mut ctrl).await;
+    let result = run_event_loop(&mut tui, &mut ctrl, &ws_url, name).await;
     tui.teardown()?;
     result
 }
 
+// ---------------------------------------------------------------------------
+// Room picker
+// ---------------------------------------------------------------------------
+
+/// How often the room picker asks the server for a fresh room list.
+const ROOM_LIST_REFRESH: tokio::time::Duration = tokio::time::Duration::from_secs(2);
+
+/// List the server's rooms until the user picks one (`None` if they quit).
+async fn pick_room(
+    tui: &mut Tui,
+    ctrl: &mut ClientController,
+) -> Result<Option<String>, Box<dyn std::error::Error>> {
+    let mut refresh = tokio::time::interval(ROOM_LIST_REFRESH);
+    loop {
+        tui.render_room_picker(&ctrl.state)?;
+
+        let timeout = tokio::time::Duration::from_millis(50);
+
+        tokio::select! {
+            _ = refresh.tick() => {
+                ctrl.send(ClientMessage::ListRooms);
+            }
+
+            poll = ctrl.recv() => {
+                if let PollResult::Disconnected = poll {
+                    return Err("Disconnected while listing rooms".into());
+                }
+            }
+
+            _ = tokio::time::sleep(timeout) => {
+                match tui.poll_room_picker_input(&ctrl.state)? {
+                    PickerIntent::Quit => return Ok(None),
+                    PickerIntent::Pick(room_id) => return Ok(Some(room_id)),
+                    PickerIntent::None => {}
+                }
+            }
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Event loop
 // ---------------------------------------------------------------------------
@@ -76,6 +187,8 @@ pub async fn start_client(
 async fn run_event_loop(
     tui: &mut Tui,
     ctrl: &mut ClientController,
+    ws_url: &str,
+    name: &str,
 ) -> Result<(), Box<dyn std::error::Error>> {
     loop {
         tui.render(&ctrl.state)?;
@@ -92,6 +205,11 @@ async fn run_event_loop(
                     }
                     PollResult::Disconnected => {
                         tui.render(&ctrl.state)?;
+                        if ctrl.state.reconnect_after.is_some()
+                            && rejoin_after_shutdown(tui, ctrl, ws_url, name).await?
+                        {
+                            continue;
+                        }
                         tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
                         break;
                     }
@@ -116,3 +234,61 @@ async fn run_event_loop(
 
     Ok(())
 }
+
+/// Rejoin once the server that announced a shutdown is back, waiting as
+/// long as it asked and then backing off.  Returns whether we are back in
+/// the room; the user can quit while waiting.
+async fn rejoin_after_shutdown(
+    tui: &mut Tui,
+    ctrl: &mut ClientController,
+    ws_url: &str,
+    name: &str,
+) -> Result<bool, Box<dyn std::error::Error>> {
+    let session_token = ctrl.state.session_token.clone();
+    if session_token.is_empty() {
+        return Ok(false);
+    }
+    let sit_back_in = session::sit_back_in_after_rejoin(&ctrl.state);
+    for attempt in 0..MAX_RECONNECT_ATTEMPTS {
+        let delay = session::reconnect_delay_ms(&ctrl.state, attempt);
+        let deadline = tokio::time::Instant::now() + tokio::time::Duration::from_millis(delay);
+        while tokio::time::Instant::now() < deadline {
+            if let UserIntent::Quit = tui.poll_and_handle_input(&ctrl.state)? {
+                return Ok(false);
+            }
+            tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+        }
+
+        ctrl.add_message(
+            format!(
+                "Reconnection attempt {} of {MAX_RECONNECT_ATTEMPTS}…",
+                attempt + 1
+            ),
+            LogCategory::System,
+        );
+        tui.render(&ctrl.state)?;
+        let room_id = ctrl.state.room_id.clone();
+        let rejoined = session::try_rejoin(
+            ws_url,
+            &room_id,
+            name,
+            &session_token,
+            Some(&ctrl.state),
+            ctrl.state.encoding,
+        )
+        .await;
+        if let Ok(new_ctrl) = rejoined {
+            *ctrl = new_ctrl;
+            if sit_back_in {
+                ctrl.send(ClientMessage::SitIn);
+            }
+            return Ok(true);
+        }
+    }
+    ctrl.add_message(
+        "Could not reconnect. Session may have expired.".to_string(),
+        LogCategory::Error,
+    );
+    tui.render(&ctrl.state)?;
+    Ok(false)
+}
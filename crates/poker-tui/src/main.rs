@@ -1,4 +1,5 @@
 use clap::Parser;
+use poker_core::protocol::{Encoding, GameMode, RebuyConfig, SpectatorDelay};
 
 mod client;
 mod tui;
@@ -11,30 +12,173 @@ struct Cli {
     #[arg(short, long, default_value = "ws://127.0.0.1:8080")]
     server: String,
 
-    /// Room ID to create or join
+    /// Room ID to create or join; without it (or --invite) pick one from a list
     #[arg(short, long)]
-    room: String,
+    room: Option<String>,
 
     /// Player name
     #[arg(short, long)]
     name: String,
 
     /// Create the room (instead of joining an existing one)
-    #[arg(short, long)]
+    #[arg(short, long, requires = "room")]
     create: bool,
+
+    /// When creating: make the room a cash game instead of a tournament
+    #[arg(long, requires = "create")]
+    cash: bool,
+
+    /// Cash game minimum buy-in, in big blinds
+    #[arg(long, default_value_t = 40)]
+    min_buy_in: u32,
+
+    /// Cash game maximum buy-in, in big blinds
+    #[arg(long, default_value_t = 100)]
+    max_buy_in: u32,
+
+    /// Tournament: allow rebuys during the first N blind levels
+    #[arg(long, default_value_t = 0, conflicts_with = "cash")]
+    rebuy_levels: u32,
+
+    /// Tournament: maximum rebuys per player (0 = unlimited)
+    #[arg(long, default_value_t = 0)]
+    max_rebuys: u32,
+
+    /// Tournament: offer a single add-on at the break after the rebuy period
+    #[arg(long, requires = "rebuy_levels")]
+    add_on: bool,
+
+    /// Tournament: prize pool percentage per place, best first (e.g. 50,30,20)
+    #[arg(long, value_delimiter = ',', default_value = "100")]
+    payouts: Vec<u32>,
+
+    /// When creating: number of seats at the table (2-10)
+    #[arg(long, default_value_t = 9)]
+    table_size: u32,
+
+    /// Tournament: spread the entrants over several tables of `--table-size` seats
+    #[arg(long, requires = "create", conflicts_with_all = ["cash", "rebuy_levels"])]
+    multi_table: bool,
+
+    /// Sit in this seat (1 to the table size) instead of a random empty one
+    #[arg(long, value_parser = clap::value_parser!(u32).range(1..))]
+    seat: Option<u32>,
+
+    /// When creating: hold back everything spectators see by this many seconds
+    #[arg(long, default_value_t = 0, requires = "create")]
+    spectator_delay_secs: u32,
+
+    /// When creating: hold back everything spectators see by this many hands
+    #[arg(long, default_value_t = 0, requires = "create")]
+    spectator_delay_hands: u32,
+
+    /// When creating: show spectators every player's hole cards after the delay
+    #[arg(long, requires = "create")]
+    streamer_view: bool,
+
+    /// Watch the room as a spectator (press S to take a seat later)
+    #[arg(long, conflicts_with_all = ["create", "seat"])]
+    spectate: bool,
+
+    /// Password of a private room; when creating, makes the room private
+    #[arg(long, default_value = "")]
+    password: String,
+
+    /// When creating: hide the room from the public room list
+    #[arg(long, requires = "create")]
+    unlisted: bool,
+
+    /// Join (or with --spectate, watch) with an invite token; --room is then optional
+    #[arg(long, conflicts_with = "create")]
+    invite: Option<String>,
+
+    /// Wire encoding to ask the server for: json, msgpack or cbor
+    #[arg(long, default_value = "json", value_parser = parse_encoding)]
+    encoding: Encoding,
+}
+
+fn parse_encoding(name: &str) -> Result<Encoding, String> {
+    match name {
+        "json" => Ok(Encoding::Json),
+        "msgpack" => Ok(Encoding::MessagePack),
+        "cbor" => Ok(Encoding::Cbor),
+        _ => Err("expected json, msgpack or cbor".to_string()),
+    }
 }
 
 #[tokio::main]
 async fn main() {
     let cli = Cli::parse();
 
-    let action = if cli.create { "Creating" } else { "Joining" };
-    println!(
-        "{} room '{}' on {} as '{}'...",
-        action, cli.room, cli.server, cli.name
-    );
+    let action = if cli.create {
+        "Creating"
+    } else if cli.spectate {
+        "Watching"
+    } else {
+        "Joining"
+    };
+    let room = cli.room.unwrap_or_default();
+    if room.is_empty() && cli.invite.is_none() {
+        println!("Listing rooms on {} as '{}'...", cli.server, cli.name);
+    } else if room.is_empty() {
+        println!(
+            "{} with an invite on {} as '{}'...",
+            action, cli.server, cli.name
+        );
+    } else {
+        println!(
+            "{} room '{}' on {} as '{}'...",
+            action, room, cli.server, cli.name
+        );
+    }
+
+    let game_mode = if cli.cash {
+        GameMode::Cash {
+            min_buy_in_bbs: cli.min_buy_in,
+            max_buy_in_bbs: cli.max_buy_in,
+        }
+    } else {
+        GameMode::Tournament
+    };
+
+    let rebuy_config = RebuyConfig {
+        levels: cli.rebuy_levels,
+        max_rebuys: cli.max_rebuys,
+        add_on: cli.add_on,
+    };
+
+    let create = cli.create.then_some(client::RoomOptions {
+        game_mode,
+        rebuy_config,
+        payouts: cli.payouts,
+        multi_table: cli.multi_table,
+        table_size: cli.table_size,
+        spectator_delay: SpectatorDelay {
+            secs: cli.spectator_delay_secs,
+            hands: cli.spectator_delay_hands,
+            hole_cards: cli.streamer_view,
+        },
+        unlisted: cli.unlisted,
+    });
+    let seat = cli.seat.map(|seat| seat - 1);
+
+    let credentials = client::Credentials {
+        password: cli.password,
+        invite: cli.invite.unwrap_or_default(),
+    };
 
-    if let Err(e) = client::start_client(&cli.server, &cli.room, &cli.name, cli.create).await {
+    if let Err(e) = client::start_client(
+        &cli.server,
+        &room,
+        &cli.name,
+        create,
+        seat,
+        cli.spectate,
+        credentials,
+        cli.encoding,
+    )
+    .await
+    {
         eprintln!("Error: {}", e);
     }
 }
@@ -22,7 +22,7 @@ use ratatui::{
 use std::io::{self, Stdout};
 
 use poker_client::game_state::{
-    ClientGameState, GameEvent, LogCategory, RAISE_PRESETS, RaisePreset,
+    ClientGameState, GameEvent, LogCategory, RAISE_PRESETS, RaisePreset, invite_terms, ordinal,
 };
 use poker_core::protocol::{CardInfo, ClientMessage, PlayerAction, PlayerInfo};
 
@@ -44,6 +44,17 @@ pub enum UserIntent {
     Feedback(String, LogCategory),
 }
 
+/// The result of a key press in the room picker.
+#[derive(Debug)]
+pub enum PickerIntent {
+    /// Keep browsing.
+    None,
+    /// The user wants to quit without joining.
+    Quit,
+    /// Join (or watch) the room with this ID.
+    Pick(String),
+}
+
 // ---------------------------------------------------------------------------
 // TUI-only state
 // ---------------------------------------------------------------------------
@@ -63,6 +74,14 @@ struct TuiState {
     pending_all_in: bool,
     /// Show help popup
     show_help: bool,
+    /// Hand number at which the results popup was closed (so the next
+    /// tournament's results show up again).
+    results_dismissed_at: Option<u32>,
+    /// Host menu, with the index of the selected player (see
+    /// [`moderated_players`]).
+    host_menu: Option<usize>,
+    /// Index of the selected room in the room picker.
+    picked_room: usize,
 }
 
 impl TuiState {
@@ -362,6 +381,45 @@ impl Tui {
         Ok(self.handle_key_event(key, gs))
     }
 
+    /// Draw the room picker over the room list in `gs.rooms`.
+    pub fn render_room_picker(&mut self, gs: &ClientGameState) -> io::Result<()> {
+        let selected = self.state.picked_room;
+        self.terminal
+            .draw(|f| render_room_picker(f, gs, selected))?;
+        Ok(())
+    }
+
+    /// Poll for a keyboard event in the room picker.  Never blocks.
+    pub fn poll_room_picker_input(&mut self, gs: &ClientGameState) -> io::Result<PickerIntent> {
+        if !event::poll(std::time::Duration::from_millis(0))? {
+            return Ok(PickerIntent::None);
+        }
+        let Event::Key(key) = event::read()? else {
+            return Ok(PickerIntent::None);
+        };
+        if key.kind != KeyEventKind::Press {
+            return Ok(PickerIntent::None);
+        }
+        let tui = &mut self.state;
+        tui.picked_room = tui.picked_room.min(gs.rooms.len().saturating_sub(1));
+        Ok(match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => PickerIntent::Quit,
+            KeyCode::Up => {
+                tui.picked_room = tui.picked_room.saturating_sub(1);
+                PickerIntent::None
+            }
+            KeyCode::Down => {
+                tui.picked_room = (tui.picked_room + 1).min(gs.rooms.len().saturating_sub(1));
+                PickerIntent::None
+            }
+            KeyCode::Enter => match gs.rooms.get(tui.picked_room) {
+                Some(room) => PickerIntent::Pick(room.room_id.clone()),
+                None => PickerIntent::None,
+            },
+            _ => PickerIntent::None,
+        })
+    }
+
     /// Notify the UI that the set of available game actions changed.
     ///
     /// Re-clamps the selected button and, if a new turn started, resets the
@@ -379,11 +437,17 @@ impl Tui {
 
     fn handle_key_event(&mut self, key: KeyEvent, gs: &ClientGameState) -> UserIntent {
         let tui = &mut self.state;
+        if let Some(selected) = tui.host_menu {
+            return handle_host_menu_key(tui, gs, key.code, selected);
+        }
         match key.code {
             KeyCode::Esc => {
                 if tui.show_help {
                     tui.show_help = false;
                     UserIntent::None
+                } else if results_visible(tui, gs) {
+                    tui.results_dismissed_at = Some(gs.hand_number);
+                    UserIntent::None
                 } else {
                     UserIntent::Quit
                 }
@@ -403,6 +467,89 @@ impl Tui {
                 }
                 UserIntent::None
             }
+            KeyCode::Char('p') | KeyCode::Char('P') => {
+                if tui.show_help || !gs.is_host || !gs.game_started {
+                    return UserIntent::None;
+                }
+                if gs.paused {
+                    UserIntent::Send(ClientMessage::ResumeGame)
+                } else {
+                    UserIntent::Send(ClientMessage::PauseGame)
+                }
+            }
+            KeyCode::Char('b') | KeyCode::Char('B') => {
+                if tui.show_help {
+                    return UserIntent::None;
+                }
+                if gs.can_rebuy() {
+                    return UserIntent::Send(ClientMessage::Rebuy);
+                }
+                if !gs.is_cash_game() {
+                    return UserIntent::None;
+                }
+                match gs.top_up() {
+                    Some(msg) => UserIntent::Send(msg),
+                    None => UserIntent::Feedback(
+                        "Your stack is already at the maximum buy-in".to_string(),
+                        LogCategory::Error,
+                    ),
+                }
+            }
+            KeyCode::Char('a') | KeyCode::Char('A') => {
+                if tui.show_help || !gs.can_add_on() {
+                    return UserIntent::None;
+                }
+                UserIntent::Send(ClientMessage::AddOn)
+            }
+            KeyCode::Char('e') | KeyCode::Char('E') => {
+                if tui.show_help || !gs.is_host || !gs.rebuy_open {
+                    return UserIntent::None;
+                }
+                UserIntent::Send(ClientMessage::EndRebuyPeriod)
+            }
+            KeyCode::Char('v') | KeyCode::Char('V') => {
+                if tui.show_help || !gs.is_host {
+                    return UserIntent::None;
+                }
+                UserIntent::Send(ClientMessage::ToggleSpectatorChat)
+            }
+            KeyCode::Char('i') | KeyCode::Char('I') => {
+                if tui.show_help || !gs.is_host {
+                    return UserIntent::None;
+                }
+                UserIntent::Send(ClientMessage::CreateInvite {
+                    single_use: true,
+                    expires_in_secs: 0,
+                })
+            }
+            KeyCode::Char('m') | KeyCode::Char('M') => {
+                if tui.show_help || !can_moderate(gs) {
+                    return UserIntent::None;
+                }
+                tui.host_menu = Some(0);
+                UserIntent::None
+            }
+            KeyCode::Char('l') | KeyCode::Char('L') => {
+                if tui.show_help || !gs.is_cash_game() {
+                    return UserIntent::None;
+                }
+                UserIntent::Send(ClientMessage::LeaveTable)
+            }
+            KeyCode::Char('s') | KeyCode::Char('S') => {
+                if tui.show_help {
+                    return UserIntent::None;
+                }
+                if gs.spectating {
+                    return UserIntent::Send(ClientMessage::TakeSeat { seat: None });
+                }
+                match gs.next_empty_seat() {
+                    Some(seat) => UserIntent::Send(ClientMessage::ChangeSeat { seat }),
+                    None => UserIntent::Feedback(
+                        "There is no empty seat to move to".to_string(),
+                        LogCategory::Error,
+                    ),
+                }
+            }
             KeyCode::Char(c) => {
                 if !tui.show_help && c.is_ascii_digit() {
                     tui.enter_char(c);
@@ -476,6 +623,73 @@ impl Tui {
     }
 }
 
+/// Whether we may kick players, hand over hosting, end the game or change
+/// the blinds.  Multi-table tournaments are run by the tournament itself.
+fn can_moderate(gs: &ClientGameState) -> bool {
+    gs.is_host && !gs.spectating && gs.tournament.is_none()
+}
+
+/// The players the host menu acts on: everyone but us, in seat order.
+fn moderated_players(gs: &ClientGameState) -> Vec<&PlayerInfo> {
+    let mut players: Vec<&PlayerInfo> = gs
+        .players
+        .iter()
+        .filter(|p| p.id != gs.our_player_id)
+        .collect();
+    players.sort_by_key(|p| p.seat);
+    players
+}
+
+fn handle_host_menu_key(
+    tui: &mut TuiState,
+    gs: &ClientGameState,
+    code: KeyCode,
+    selected: usize,
+) -> UserIntent {
+    let players = moderated_players(gs);
+    let selected = selected.min(players.len().saturating_sub(1));
+    let player_id = players.get(selected).map(|p| p.id);
+    // Blind changes keep the current schedule and are relative to the
+    // current big blind.
+    let blinds = |big_blind: u32| ClientMessage::ChangeBlinds {
+        small_blind: (big_blind / 2).max(1),
+        big_blind: big_blind.max(1),
+        blind_config: gs.blind_config,
+    };
+    let intent = match code {
+        KeyCode::Up => {
+            tui.host_menu = Some(selected.saturating_sub(1));
+            return UserIntent::None;
+        }
+        KeyCode::Down => {
+            tui.host_menu = Some((selected + 1).min(players.len().saturating_sub(1)));
+            return UserIntent::None;
+        }
+        KeyCode::Char('k') | KeyCode::Char('K') => player_id.map(|player_id| {
+            UserIntent::Send(ClientMessage::KickPlayer {
+                player_id,
+                ban: false,
+            })
+        }),
+        KeyCode::Char('x') | KeyCode::Char('X') => player_id.map(|player_id| {
+            UserIntent::Send(ClientMessage::KickPlayer {
+                player_id,
+                ban: true,
+            })
+        }),
+        KeyCode::Char('t') | KeyCode::Char('T') => {
+            player_id.map(|player_id| UserIntent::Send(ClientMessage::TransferHost { player_id }))
+        }
+        KeyCode::Char('g') | KeyCode::Char('G') => Some(UserIntent::Send(ClientMessage::EndGame)),
+        KeyCode::Char('+') => Some(UserIntent::Send(blinds(gs.big_blind.saturating_mul(2)))),
+        KeyCode::Char('-') => Some(UserIntent::Send(blinds(gs.big_blind / 2))),
+        KeyCode::Esc | KeyCode::Char('m') | KeyCode::Char('M') => None,
+        _ => return UserIntent::None,
+    };
+    tui.host_menu = None;
+    intent.unwrap_or(UserIntent::None)
+}
+
 // ---------------------------------------------------------------------------
 // Rendering
 // ---------------------------------------------------------------------------
@@ -515,8 +729,8 @@ fn ui(frame: &mut Frame, gs: &ClientGameState, tui: &TuiState) {
     let middle_layout = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(8), // Game board
-            Constraint::Min(5),    // Messages
+            Constraint::Length(13), // Game board
+            Constraint::Min(5),     // Messages
         ])
         .split(content_layout[1]);
 
@@ -559,43 +773,88 @@ fn ui(frame: &mut Frame, gs: &ClientGameState, tui: &TuiState) {
     let status = Paragraph::new(Line::from(status_spans));
     frame.render_widget(status, main_layout[2]);
 
+    // Paused popup
+    if gs.paused {
+        render_paused_popup(frame, gs);
+    }
+
+    // Tournament results popup
+    if results_visible(tui, gs) {
+        render_results_popup(frame, gs);
+    }
+
+    // Host menu
+    if let Some(selected) = tui.host_menu {
+        render_host_menu_popup(frame, gs, selected);
+    }
+
     // Help popup
     if tui.show_help {
         render_help_popup(frame);
     }
 }
 
+/// Whether the final standings popup should be shown.
+fn results_visible(tui: &TuiState, gs: &ClientGameState) -> bool {
+    !gs.standings.is_empty() && !gs.game_started && tui.results_dismissed_at != Some(gs.hand_number)
+}
+
 fn render_players_panel(frame: &mut Frame, gs: &ClientGameState, area: Rect) {
     let my_id = gs.our_player_id;
 
-    let items: Vec<ListItem> = gs
-        .players
-        .iter()
-        .map(|PlayerInfo { id, name, chips }| {
-            let is_me = *id == my_id;
-            let is_dealer = *id == gs.dealer_id;
+    let mut players: Vec<&PlayerInfo> = gs.players.iter().collect();
+    players.sort_by_key(|p| p.seat);
 
-            let mut spans = vec![];
-            if is_dealer {
-                spans.push(Span::styled("(D) ", Style::default().fg(Color::Yellow)));
-            } else {
-                spans.push(Span::raw("  "));
-            }
+    let items: Vec<ListItem> = players
+        .into_iter()
+        .map(
+            |PlayerInfo {
+                 id,
+                 name,
+                 chips,
+                 seat,
+             }| {
+                let is_me = *id == my_id;
+                let is_dealer = *id == gs.dealer_id;
+
+                let mut spans = vec![Span::styled(
+                    format!("{:>2} ", seat + 1),
+                    Style::default().fg(Color::DarkGray),
+                )];
+                if is_dealer {
+                    spans.push(Span::styled("(D) ", Style::default().fg(Color::Yellow)));
+                } else {
+                    spans.push(Span::raw("  "));
+                }
 
-            let name_style = if is_me {
-                Style::default().fg(Color::Cyan).bold()
-            } else {
-                Style::default().fg(Color::White)
-            };
+                let name_style = if is_me {
+                    Style::default().fg(Color::Cyan).bold()
+                } else {
+                    Style::default().fg(Color::White)
+                };
 
-            spans.push(Span::styled(name.to_string(), name_style));
-            spans.push(Span::styled(
-                format!(" ${}", chips),
-                Style::default().fg(Color::Green),
-            ));
+                spans.push(Span::styled(name.to_string(), name_style));
+                spans.push(Span::styled(
+                    format!(" ${}", chips),
+                    Style::default().fg(Color::Green),
+                ));
+                // Streamer view: spectators see every hand.
+                if let Some([c1, c2]) = gs.spectator_hole_cards.get(id) {
+                    spans.push(Span::styled(
+                        format!(
+                            " {}{} {}{}",
+                            c1.rank_str(),
+                            c1.suit_str(),
+                            c2.rank_str(),
+                            c2.suit_str()
+                        ),
+                        Style::default().fg(Color::Magenta),
+                    ));
+                }
 
-            ListItem::new(Line::from(spans))
-        })
+                ListItem::new(Line::from(spans))
+            },
+        )
         .collect();
 
     let players_list = List::new(items).block(
@@ -609,6 +868,36 @@ fn render_players_panel(frame: &mut Frame, gs: &ClientGameState, area: Rect) {
     frame.render_widget(players_list, area);
 }
 
+/// Width of a seat label drawn around the table.
+const SEAT_LABEL_WIDTH: u16 = 14;
+
+/// One seat around the table: `(D) name $chips`, or the empty seat number.
+fn seat_label(gs: &ClientGameState, seat: u32) -> Line<'static> {
+    let Some(player) = gs.player_at_seat(seat) else {
+        return Line::from(Span::styled(
+            format!("· {} ·", seat + 1),
+            Style::default().fg(Color::DarkGray),
+        ));
+    };
+    let mut style = if player.id == gs.our_player_id {
+        Style::default().fg(Color::Cyan).bold()
+    } else {
+        Style::default().fg(Color::White)
+    };
+    if gs.is_player_folded(player.id) || gs.is_player_sitting_out(player.id) {
+        style = style.fg(Color::DarkGray);
+    }
+    if gs.turn_timer_player == Some(player.id) {
+        style = style.fg(Color::Black).bg(Color::Yellow);
+    }
+    let dealer = if player.id == gs.dealer_id { "(D)" } else { "" };
+    let label: String = format!("{dealer}{} ${}", player.name, player.chips)
+        .chars()
+        .take(SEAT_LABEL_WIDTH as usize)
+        .collect();
+    Line::from(Span::styled(label, style))
+}
+
 fn render_game_board(frame: &mut Frame, gs: &ClientGameState, area: Rect) {
     let mut lines = vec![];
 
@@ -632,8 +921,6 @@ fn render_game_board(frame: &mut Frame, gs: &ClientGameState, area: Rect) {
         ),
     ]));
 
-    lines.push(Line::from(""));
-
     // Community cards
     let community_str = if gs.community_cards.is_empty() {
         "[ ? ] [ ? ] [ ? ] [ ? ] [ ? ]".to_string()
@@ -650,14 +937,7 @@ fn render_game_board(frame: &mut Frame, gs: &ClientGameState, area: Rect) {
         Style::default().fg(Color::White),
     )]));
 
-    lines.push(Line::from(""));
-
     // Hole cards
-    lines.push(Line::from(vec![Span::styled(
-        "Your Cards:",
-        Style::default().fg(Color::Gray),
-    )]));
-
     let hole_str = if let Some(cards) = &gs.hole_cards {
         format!("{}  {}", format_card(&cards[0]), format_card(&cards[1]))
     } else {
@@ -669,15 +949,40 @@ fn render_game_board(frame: &mut Frame, gs: &ClientGameState, area: Rect) {
         Style::default().fg(Color::Cyan).bold(),
     )]));
 
-    let board = Paragraph::new(lines).alignment(Alignment::Center).block(
-        Block::default()
-            .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Magenta))
-            .title(" Table ")
-            .title_style(Style::default().fg(Color::Magenta).bold()),
-    );
-
-    frame.render_widget(board, area);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Magenta))
+        .title(" Table ")
+        .title_style(Style::default().fg(Color::Magenta).bold());
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    // Stage, board and hole cards in the middle of the table.
+    let middle = inner.y + inner.height.saturating_sub(lines.len() as u16) / 2;
+    let centre = Rect::new(inner.x, middle, inner.width, lines.len() as u16).intersection(inner);
+    frame.render_widget(Paragraph::new(lines).alignment(Alignment::Center), centre);
+
+    // Seats on an ellipse around it, clockwise, ours at the bottom.
+    let seats = gs.seats_from_ours();
+    let cx = f64::from(inner.x) + f64::from(inner.width) / 2.0;
+    let cy = f64::from(inner.y) + f64::from(inner.height.saturating_sub(1)) / 2.0;
+    let rx = f64::from(inner.width.saturating_sub(SEAT_LABEL_WIDTH)) / 2.0;
+    let ry = f64::from(inner.height.saturating_sub(1)) / 2.0;
+    for (index, &seat) in seats.iter().enumerate() {
+        let angle =
+            std::f64::consts::FRAC_PI_2 + index as f64 * std::f64::consts::TAU / seats.len() as f64;
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let (x, y) = (
+            (cx + rx * angle.cos() - f64::from(SEAT_LABEL_WIDTH) / 2.0).round() as u16,
+            (cy + ry * angle.sin()).round() as u16,
+        );
+        let slot = Rect::new(x, y, SEAT_LABEL_WIDTH, 1).intersection(inner);
+        frame.render_widget(Clear, slot);
+        frame.render_widget(
+            Paragraph::new(seat_label(gs, seat)).alignment(Alignment::Center),
+            slot,
+        );
+    }
 }
 
 fn format_card(card: &CardInfo) -> String {
@@ -700,11 +1005,26 @@ fn format_event(event: &GameEvent) -> String {
             format!("👤 {} (#{}) joined", name, player_id)
         }
         GameEvent::PlayerLeft { name, .. } => format!("👋 {} left", name),
+        GameEvent::SeatChanged {
+            name,
+            seat,
+            pending,
+            ..
+        } => {
+            if *pending {
+                format!("💺 {} moves to seat {} next hand", name, seat + 1)
+            } else {
+                format!("💺 {} moved to seat {}", name, seat + 1)
+            }
+        }
         GameEvent::Chat {
             player_name,
             message,
             ..
         } => format!("💬 {}: {}", player_name, message),
+        GameEvent::SpectatorChat { name, message } => {
+            format!("👁 {} (watching): {}", name, message)
+        }
         GameEvent::GameStarted => "🎮 Game has started!".to_string(),
         GameEvent::NewHand {
             hand_number,
@@ -754,16 +1074,29 @@ fn format_event(event: &GameEvent) -> String {
         GameEvent::RoundWinner {
             name, amount, hand, ..
         } => format!("🏆 {} wins ${} with {}", name, amount, hand),
-        GameEvent::PlayerEliminated { name, .. } => {
-            format!("💀 {} eliminated!", name)
+        GameEvent::PlayerEliminated { name, place, .. } => {
+            if *place > 0 {
+                format!("💀 {} eliminated in {} place!", name, ordinal(*place))
+            } else {
+                format!("💀 {} eliminated!", name)
+            }
         }
         GameEvent::GameOver {
             winner_id,
             winner_name,
-        } => format!("🎊 GAME OVER! {} (#{}) WINS! 🎊", winner_name, winner_id),
+            prize_pool,
+        } => format!(
+            "🎊 GAME OVER! {} (#{}) WINS! 🎊 Prize pool: ${}",
+            winner_name, winner_id, prize_pool
+        ),
         GameEvent::Pong => "🏓 Pong!".to_string(),
         GameEvent::ServerError { message } => format!("❌ {}", message),
         GameEvent::Disconnected => "❌ Server disconnected".to_string(),
+        GameEvent::ServerShutdown {
+            reason,
+            reconnect_after,
+        } => format!("⚠ {}. Reconnecting in {}s…", reason, reconnect_after),
+        GameEvent::Announcement { message } => format!("📢 {}", message),
         GameEvent::Text { text, .. } => text.clone(),
         GameEvent::BlindsIncreased {
             small_blind,
@@ -781,6 +1114,100 @@ fn format_event(event: &GameEvent) -> String {
             format!("✅ {} is back in", name)
         }
         GameEvent::WaitingForPlayers => "⏸ Waiting for more players to sit in...".to_string(),
+        GameEvent::GamePaused => "⏸ Game paused by the host".to_string(),
+        GameEvent::GameResumed => "▶ Game resumed".to_string(),
+        GameEvent::PlayerBoughtIn {
+            name,
+            amount,
+            pending,
+            ..
+        } => {
+            if *pending {
+                format!("💵 {} bought in for ${} (next hand)", name, amount)
+            } else {
+                format!("💵 {} bought in for ${}", name, amount)
+            }
+        }
+        GameEvent::PlayerCashedOut { name, amount, .. } => {
+            format!("🚪 {} left the table with ${}", name, amount)
+        }
+        GameEvent::PlayerRebought {
+            name,
+            amount,
+            pending,
+            ..
+        } => {
+            if *pending {
+                format!("🔁 {} rebought for ${} (next hand)", name, amount)
+            } else {
+                format!("🔁 {} rebought for ${}", name, amount)
+            }
+        }
+        GameEvent::PlayerAddedOn {
+            name,
+            amount,
+            pending,
+            ..
+        } => {
+            if *pending {
+                format!("➕ {} took the add-on for ${} (next hand)", name, amount)
+            } else {
+                format!("➕ {} took the add-on for ${}", name, amount)
+            }
+        }
+        GameEvent::RebuyPeriodEnded { add_on_open } => {
+            if *add_on_open {
+                "🔒 Rebuy period over — add-on break".to_string()
+            } else {
+                "🔒 Rebuy period over".to_string()
+            }
+        }
+        GameEvent::AddOnClosed => "🔒 Add-on break over".to_string(),
+        GameEvent::TableMoved { room_id } => format!("🪑 Moved to table {room_id}"),
+        GameEvent::InviteCreated {
+            token,
+            single_use,
+            expires_in_secs,
+        } => format!(
+            "🎟 Invite created ({}): join with --invite {}",
+            invite_terms(*single_use, *expires_in_secs),
+            token
+        ),
+        GameEvent::PlayerKicked { name, banned, .. } => {
+            if *banned {
+                format!("⛔ {} was banned by the host", name)
+            } else {
+                format!("👢 {} was kicked by the host", name)
+            }
+        }
+        GameEvent::HostChanged { name, .. } => format!("👑 {} is now the host", name),
+        GameEvent::GameEnded => "🛑 The host ended the game".to_string(),
+        GameEvent::BlindsChanged {
+            small_blind,
+            big_blind,
+            pending,
+        } => {
+            if *pending {
+                format!(
+                    "📊 Blinds change to {}/{} next hand",
+                    small_blind, big_blind
+                )
+            } else {
+                format!("📊 Blinds changed to {}/{}", small_blind, big_blind)
+            }
+        }
+        GameEvent::FinalStandings { standings } => {
+            let mut lines = vec!["🏁 FINAL STANDINGS".to_string()];
+            for s in standings {
+                lines.push(format!(
+                    "   {} {} - ${}",
+                    ordinal(s.place),
+                    s.name,
+                    s.payout
+                ));
+            }
+            lines.join("\n")
+        }
     }
 }
 
@@ -818,11 +1245,29 @@ fn render_messages(frame: &mut Frame, gs: &ClientGameState, area: Rect) {
 fn render_actions_panel(frame: &mut Frame, gs: &ClientGameState, area: Rect) {
     let mut lines = vec![];
 
+    if gs.spectating {
+        lines.push(Line::from(vec![Span::styled(
+            " 👁 Watching",
+            Style::default().fg(Color::Cyan).bold(),
+        )]));
+        lines.push(Line::from(vec![Span::styled(
+            " S to take a seat",
+            Style::default().fg(Color::DarkGray),
+        )]));
+        lines.push(Line::from(""));
+    }
+
     if gs.is_our_turn {
         lines.push(Line::from(vec![Span::styled(
             " 🎯 YOUR TURN!",
             Style::default().fg(Color::Yellow).bold().rapid_blink(),
         )]));
+        if gs.action_pending() {
+            lines.push(Line::from(vec![Span::styled(
+                " Sending…",
+                Style::default().fg(Color::DarkGray),
+            )]));
+        }
 
         lines.push(Line::from(""));
 
@@ -859,6 +1304,63 @@ fn render_actions_panel(frame: &mut Frame, gs: &ClientGameState, area: Rect) {
             " ready!",
             Style::default().fg(Color::DarkGray),
         )]));
+    } else if gs.is_cash_game() && !gs.ledger.is_empty() {
+        lines.push(Line::from(vec![Span::styled(
+            " Session",
+            Style::default().fg(Color::Gray),
+        )]));
+        for entry in &gs.ledger {
+            let net = entry.net();
+            let color = if net >= 0 { Color::Green } else { Color::Red };
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!(" {} ", entry.name),
+                    Style::default().fg(Color::White),
+                ),
+                Span::styled(format!("{:+}", net), Style::default().fg(color)),
+            ]));
+        }
+    } else if let Some(progress) = gs.tournament {
+        lines.push(Line::from(vec![
+            Span::styled(" Table: ", Style::default().fg(Color::Gray)),
+            Span::styled(gs.room_id.clone(), Style::default().fg(Color::White)),
+        ]));
+        lines.push(Line::from(vec![Span::styled(
+            format!(
+                " {}/{} left, {} tables",
+                progress.players_remaining, progress.entrants, progress.tables
+            ),
+            Style::default().fg(Color::Gray),
+        )]));
+    } else if gs.rebuy_config.is_enabled() {
+        let status = if gs.rebuy_open {
+            " Rebuys open"
+        } else if gs.add_on_open {
+            " Add-on break"
+        } else {
+            " Rebuys closed"
+        };
+        lines.push(Line::from(vec![Span::styled(
+            status,
+            Style::default().fg(Color::Gray),
+        )]));
+        lines.push(Line::from(vec![
+            Span::styled(" Prize pool: ", Style::default().fg(Color::Gray)),
+            Span::styled(
+                format!("${}", gs.prize_pool),
+                Style::default().fg(Color::Green),
+            ),
+        ]));
+        if let Some(entry) = gs.our_ledger_entry() {
+            lines.push(Line::from(vec![Span::styled(
+                format!(
+                    " You: {} rebuys{}",
+                    entry.rebuys,
+                    if entry.add_on { " + add-on" } else { "" }
+                ),
+                Style::default().fg(Color::White),
+            )]));
+        }
     } else {
         lines.push(Line::from(vec![Span::styled(
             " Waiting for",
@@ -870,6 +1372,22 @@ fn render_actions_panel(frame: &mut Frame, gs: &ClientGameState, area: Rect) {
         )]));
     }
 
+    if gs.spectators > 0 {
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![Span::styled(
+            format!(
+                " {} watching{}",
+                gs.spectators,
+                if gs.spectator_chat_allowed {
+                    ""
+                } else {
+                    " (chat off)"
+                }
+            ),
+            Style::default().fg(Color::DarkGray),
+        )]));
+    }
+
     let actions = Paragraph::new(lines).block(
         Block::default()
             .borders(Borders::ALL)
@@ -897,6 +1415,9 @@ fn action_label(gs: &ClientGameState, action: ActionButton) -> String {
 }
 
 fn control_button_enabled(gs: &ClientGameState, button: ControlButton) -> bool {
+    if gs.action_pending() && !matches!(button, ControlButton::Action(ActionButton::Start)) {
+        return false;
+    }
     match button {
         ControlButton::Preset(_) => {
             gs.is_our_turn
@@ -1097,6 +1618,15 @@ fn render_help_popup(frame: &mut Frame) {
             "  SYSTEM",
             Style::default().fg(Color::Cyan).bold(),
         )]),
+        Line::from("  P             Pause / resume (host only)"),
+        Line::from("  B             Top up (cash) / rebuy (cash, tournaments)"),
+        Line::from("  A             Take the add-on (tournament break)"),
+        Line::from("  E             End the rebuy period (host only)"),
+        Line::from("  L             Leave table with stack (cash games)"),
+        Line::from("  S             Move to the next empty seat (take one when watching)"),
+        Line::from("  V             Toggle spectator chat (host only)"),
+        Line::from("  M             Host menu: kick, ban, make host, end game, blinds"),
+        Line::from("  I             Create a single-use invite (host only)"),
         Line::from("  F1            Toggle this help"),
         Line::from("  ESC           Quit"),
         Line::from(""),
@@ -1118,6 +1648,227 @@ fn render_help_popup(frame: &mut Frame) {
     frame.render_widget(help, area);
 }
 
+fn render_host_menu_popup(frame: &mut Frame, gs: &ClientGameState, selected: usize) {
+    let area = centered_rect(50, 60, frame.area());
+
+    frame.render_widget(Clear, area);
+
+    let players = moderated_players(gs);
+    let selected = selected.min(players.len().saturating_sub(1));
+    let mut lines = vec![Line::from("")];
+    if players.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "No other players",
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+    for (i, p) in players.iter().enumerate() {
+        let style = if i == selected {
+            Style::default().fg(Color::Black).bg(Color::Cyan).bold()
+        } else {
+            Style::default().fg(Color::White)
+        };
+        lines.push(Line::from(Span::styled(
+            format!(" {:<16} ${:<8}", p.name, p.chips),
+            style,
+        )));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        format!("Blinds {}/{}", (gs.big_blind / 2).max(1), gs.big_blind),
+        Style::default().fg(Color::Gray),
+    )));
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "K: kick  X: ban  T: make host",
+        Style::default().fg(Color::DarkGray),
+    )));
+    lines.push(Line::from(Span::styled(
+        "+/-: double / halve blinds  G: end game",
+        Style::default().fg(Color::DarkGray),
+    )));
+    lines.push(Line::from(Span::styled(
+        "Press ESC to close",
+        Style::default().fg(Color::DarkGray),
+    )));
+
+    let popup = Paragraph::new(Text::from(lines))
+        .alignment(Alignment::Center)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan))
+                .title(" Host ")
+                .title_style(Style::default().fg(Color::Cyan).bold())
+                .style(Style::default().bg(Color::Black)),
+        );
+
+    frame.render_widget(popup, area);
+}
+
+fn render_room_picker(frame: &mut Frame, gs: &ClientGameState, selected: usize) {
+    let selected = selected.min(gs.rooms.len().saturating_sub(1));
+    let mut items: Vec<ListItem> = gs
+        .rooms
+        .iter()
+        .enumerate()
+        .map(|(i, room)| {
+            let style = if i == selected {
+                Style::default().fg(Color::Black).bg(Color::Cyan).bold()
+            } else if room.is_joinable() {
+                Style::default().fg(Color::White)
+            } else {
+                Style::default().fg(Color::DarkGray)
+            };
+            ListItem::new(Line::from(Span::styled(
+                format!(
+                    " {} {:<20} {:<24} {:>9} {:>6} {:<8}",
+                    if room.password { "🔒" } else { "  " },
+                    room.room_id,
+                    room.variant(),
+                    format!("{}/{}", room.small_blind, room.big_blind),
+                    format!("{}/{}", room.players, room.table_size),
+                    room.status(),
+                ),
+                style,
+            )))
+        })
+        .collect();
+    if items.is_empty() {
+        items.push(ListItem::new(Line::from(Span::styled(
+            " No open rooms",
+            Style::default().fg(Color::DarkGray),
+        ))));
+    }
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(3), Constraint::Length(1)])
+        .split(frame.area());
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .title(" Rooms ")
+            .title_style(Style::default().fg(Color::Cyan).bold()),
+    );
+    frame.render_widget(list, chunks[0]);
+    frame.render_widget(
+        Paragraph::new(Span::styled(
+            "Up/Down: select  Enter: join  ESC: quit",
+            Style::default().fg(Color::DarkGray),
+        ))
+        .alignment(Alignment::Center),
+        chunks[1],
+    );
+}
+
+fn render_paused_popup(frame: &mut Frame, gs: &ClientGameState) {
+    let area = centered_rect(40, 20, frame.area());
+
+    frame.render_widget(Clear, area);
+
+    let hint = if gs.is_host {
+        "Press P to resume"
+    } else {
+        "Waiting for the host to resume..."
+    };
+    let title = if gs.add_on_open {
+        "☕ ADD-ON BREAK"
+    } else {
+        "⏸ GAME PAUSED"
+    };
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            title,
+            Style::default().fg(Color::Yellow).bold(),
+        )),
+        Line::from(""),
+    ];
+    if gs.can_add_on() {
+        lines.push(Line::from(Span::styled(
+            "Press A to take the add-on",
+            Style::default().fg(Color::Green),
+        )));
+    }
+    lines.push(Line::from(Span::styled(
+        hint,
+        Style::default().fg(Color::DarkGray),
+    )));
+    let text = Text::from(lines);
+
+    let popup = Paragraph::new(text).alignment(Alignment::Center).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Yellow))
+            .style(Style::default().bg(Color::Black)),
+    );
+
+    frame.render_widget(popup, area);
+}
+
+fn render_results_popup(frame: &mut Frame, gs: &ClientGameState) {
+    let area = centered_rect(50, 60, frame.area());
+
+    frame.render_widget(Clear, area);
+
+    let headline = match gs.our_standing() {
+        Some(s) if s.place == 1 => "🏆 YOU WON THE TOURNAMENT!".to_string(),
+        Some(s) => format!("You finished {}", ordinal(s.place)),
+        None => "Tournament over".to_string(),
+    };
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            headline,
+            Style::default().fg(Color::Yellow).bold(),
+        )),
+        Line::from(Span::styled(
+            format!("Prize pool: ${}", gs.prize_pool),
+            Style::default().fg(Color::Gray),
+        )),
+        Line::from(""),
+    ];
+    for s in &gs.standings {
+        let color = if s.player_id == gs.our_player_id {
+            Color::Cyan
+        } else {
+            Color::White
+        };
+        let payout_color = if s.payout > 0 {
+            Color::Green
+        } else {
+            Color::DarkGray
+        };
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("{:>5} {:<16}", ordinal(s.place), s.name),
+                Style::default().fg(color),
+            ),
+            Span::styled(format!("${}", s.payout), Style::default().fg(payout_color)),
+        ]));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "Press ESC to close",
+        Style::default().fg(Color::DarkGray),
+    )));
+
+    let popup = Paragraph::new(Text::from(lines))
+        .alignment(Alignment::Center)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Yellow))
+                .title(" Results ")
+                .title_style(Style::default().fg(Color::Yellow).bold())
+                .style(Style::default().bg(Color::Black)),
+        );
+
+    frame.render_widget(popup, area);
+}
+
 fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
     let popup_layout = Layout::default()
         .direction(Direction::Vertical)
@@ -1,9 +1,10 @@
 //! Platform-agnostic Dioxus application lifecycle logic.
 //!
 //! Contains the game loop, reconnection state machine, and session recovery
-//! — parameterised over a [`SessionStore`](poker_client::session::SessionStore)
-//! and an async sleep function so that platform crates (poker-web, a future
-//! desktop crate, etc.) only need to provide thin adapters.
+//! — parameterised over a [`SessionStore`](poker_client::session::SessionStore),
+//! an async sleep function and a reload function so that platform crates
+//! (poker-web, a future desktop crate, etc.) only need to provide thin
+//! adapters.
 //!
 //! Lower-level primitives (`try_rejoin`, `SessionStore`, reconnect constants)
 //! live in [`poker_client::session`].
@@ -12,11 +13,18 @@ use dioxus::prelude::*;
 use futures_util::StreamExt;
 use poker_client::client_controller::{ClientController, PollResult};
 use poker_client::game_state::{ClientGameState, LogCategory};
-use poker_client::session::{self, MAX_RECONNECT_ATTEMPTS, RECONNECT_BASE_DELAY_MS, SessionStore};
-use poker_core::protocol::ClientMessage;
+use poker_client::session::{self, MAX_RECONNECT_ATTEMPTS, RejoinError, SessionStore};
+use poker_core::protocol::{ClientMessage, Encoding};
 
 use crate::{Screen, UiMessage};
 
+/// How often the lobby browser asks the server for a fresh room list.
+const ROOM_LIST_REFRESH_MS: u64 = 3000;
+
+/// Encoding asked for in the handshake.  Browsers are how players on
+/// phones and slow connections play, so they use the compact one.
+const ENCODING: Encoding = Encoding::MessagePack;
+
 // ---------------------------------------------------------------------------
 // Game loop
 // ---------------------------------------------------------------------------
@@ -58,8 +66,10 @@ pub async fn game_loop(
                     Some(UiMessage::ExitGame) => {
                         return GameLoopExit::UserExit;
                     }
-                    Some(UiMessage::Connect { .. }) => {
-                        // Ignore duplicate connect requests.
+                    Some(UiMessage::Connect { .. })
+                    | Some(UiMessage::BrowseRooms { .. })
+                    | Some(UiMessage::StopBrowsing) => {
+                        // Ignore connection-screen requests.
                     }
                     None => return GameLoopExit::Disconnected,
                 }
@@ -76,11 +86,14 @@ pub async fn game_loop(
 ///
 /// When the WebSocket drops, this function will attempt up to
 /// [`MAX_RECONNECT_ATTEMPTS`] to rejoin using the saved session token,
-/// with exponential back-off between attempts.
+/// with exponential back-off between attempts.  After a server shutdown
+/// notice the first attempt waits as long as the server asked, and a
+/// player who was in the game sits back in once rejoined.
 ///
 /// `sleep_ms` is an async function that sleeps for the given number of
 /// milliseconds — callers provide a platform-appropriate implementation
-/// (e.g. `gloo_timers` on web, `tokio::time::sleep` on native).
+/// (e.g. `gloo_timers` on web, `tokio::time::sleep` on native).  `reload`
+/// reloads the client when the server has a newer build.
 #[allow(clippy::too_many_arguments)]
 pub async fn run_with_reconnect<F, Fut>(
     ctrl: &mut ClientController,
@@ -92,6 +105,7 @@ pub async fn run_with_reconnect<F, Fut>(
     name: &str,
     session: &dyn SessionStore,
     sleep_ms: F,
+    reload: &dyn Fn(),
 ) where
     F: Fn(u64) -> Fut,
     Fut: std::future::Future<Output = ()>,
@@ -123,10 +137,11 @@ pub async fn run_with_reconnect<F, Fut>(
         );
         game_state.set(ctrl.state.clone());
 
+        let sit_back_in = session::sit_back_in_after_rejoin(&ctrl.state);
         let mut reconnected = false;
+        let mut refusal = None;
         for attempt in 0..MAX_RECONNECT_ATTEMPTS {
-            let delay = RECONNECT_BASE_DELAY_MS * 2u64.pow(attempt);
-            sleep_ms(delay).await;
+            sleep_ms(session::reconnect_delay_ms(&ctrl.state, attempt)).await;
 
             ctrl.state.add_message(
                 format!(
@@ -137,21 +152,52 @@ pub async fn run_with_reconnect<F, Fut>(
             );
             game_state.set(ctrl.state.clone());
 
-            if let Some(new_ctrl) = session::try_rejoin(ws_url, room_id, name, &session_token).await
-            {
-                *ctrl = new_ctrl;
-                session.save(ws_url, room_id, name, &ctrl.state.session_token);
-                game_state.set(ctrl.state.clone());
-                reconnected = true;
-                break;
+            // A multi-table tournament may have moved us since we joined.
+            let current_room = if ctrl.state.room_id.is_empty() {
+                room_id.to_string()
+            } else {
+                ctrl.state.room_id.clone()
+            };
+            let rejoined = session::try_rejoin(
+                ws_url,
+                &current_room,
+                name,
+                &session_token,
+                Some(&ctrl.state),
+                ENCODING,
+            )
+            .await;
+            match rejoined {
+                Ok(new_ctrl) => {
+                    *ctrl = new_ctrl;
+                    if sit_back_in {
+                        ctrl.send(ClientMessage::SitIn);
+                    }
+                    session.save(ws_url, &ctrl.state.room_id, name, &ctrl.state.session_token);
+                    game_state.set(ctrl.state.clone());
+                    reconnected = true;
+                    break;
+                }
+                Err(RejoinError::ReloadRequired) => {
+                    // The saved session rejoins after the reload.
+                    session.save(ws_url, &current_room, name, &session_token);
+                    reload_client(reload).await;
+                }
+                Err(RejoinError::Failed) => {}
+                Err(RejoinError::Refused(error)) => {
+                    // Trying again will not change the answer.
+                    refusal = Some(error);
+                    break;
+                }
             }
         }
 
         if !reconnected {
-            ctrl.state.add_message(
-                "Could not reconnect. Session may have expired.".to_string(),
-                LogCategory::Error,
-            );
+            let message = match refusal {
+                Some(error) => format!("Could not rejoin: {error}"),
+                None => "Could not reconnect. Session may have expired.".to_string(),
+            };
+            ctrl.state.add_message(message, LogCategory::Error);
             game_state.set(ctrl.state.clone());
             break;
         }
@@ -169,9 +215,11 @@ pub async fn run_with_reconnect<F, Fut>(
 /// 1. Attempts to recover a previous session via `session.load()`.
 /// 2. Enters a loop waiting for [`UiMessage::Connect`], connecting,
 ///    joining a room, and running the game loop with auto-reconnect.
+///    While waiting it keeps the room list fresh for the lobby browser
+///    between [`UiMessage::BrowseRooms`] and [`UiMessage::StopBrowsing`].
 ///
-/// Platform crates only need to provide a [`SessionStore`] and a sleep
-/// function.
+/// Platform crates only need to provide a [`SessionStore`], a sleep
+/// function and a reload function.
 pub async fn run_app_session<F, Fut>(
     mut rx: UnboundedReceiver<UiMessage>,
     mut screen: Signal<Screen>,
@@ -179,37 +227,40 @@ pub async fn run_app_session<F, Fut>(
     mut conn_error: Signal<String>,
     session: impl SessionStore,
     sleep_ms: F,
+    reload: impl Fn(),
 ) where
     F: Fn(u64) -> Fut,
     Fut: std::future::Future<Output = ()>,
 {
     // ── Check for a saved session from a previous page load ──────────
     if let Some((ws_url, room_id, name, session_token)) = session.load() {
-        if let Some(mut ctrl) = session::try_rejoin(&ws_url, &room_id, &name, &session_token).await
-        {
-            // Update the session token (may have been refreshed).
-            session.save(&ws_url, &room_id, &name, &ctrl.state.session_token);
-            game_state.set(ctrl.state.clone());
-            screen.set(Screen::Game);
+        match session::try_rejoin(&ws_url, &room_id, &name, &session_token, None, ENCODING).await {
+            Ok(mut ctrl) => {
+                // Update the session token (may have been refreshed).
+                session.save(&ws_url, &room_id, &name, &ctrl.state.session_token);
+                game_state.set(ctrl.state.clone());
+                screen.set(Screen::Game);
 
-            // Enter the game loop with reconnection support.
-            run_with_reconnect(
-                &mut ctrl,
-                &mut rx,
-                &mut game_state,
-                &mut screen,
-                &ws_url,
-                &room_id,
-                &name,
-                &session,
-                &sleep_ms,
-            )
-            .await;
-            session.clear();
-            // Fall through to the loop below so the user can
-            // create/join again without reloading.
-        } else {
-            session.clear();
+                // Enter the game loop with reconnection support.
+                run_with_reconnect(
+                    &mut ctrl,
+                    &mut rx,
+                    &mut game_state,
+                    &mut screen,
+                    &ws_url,
+                    &room_id,
+                    &name,
+                    &session,
+                    &sleep_ms,
+                    &reload,
+                )
+                .await;
+                session.clear();
+                // Fall through to the loop below so the user can
+                // create/join again without reloading.
+            }
+            Err(RejoinError::ReloadRequired) => reload_client(&reload).await,
+            Err(RejoinError::Failed | RejoinError::Refused(_)) => session.clear(),
         }
     }
 
@@ -219,32 +270,103 @@ pub async fn run_app_session<F, Fut>(
         screen.set(Screen::Connection);
         game_state.set(ClientGameState::new(""));
 
-        // Wait for a Connect message from the connection screen.
-        let (name, server_url, room_id, create, blind_config, starting_bbs) = loop {
-            if let Some(UiMessage::Connect {
-                name,
-                server_url,
-                room_id,
-                create,
-                blind_config,
-                starting_bbs,
-            }) = rx.next().await
-            {
-                break (
-                    name,
-                    server_url,
-                    room_id,
-                    create,
-                    blind_config,
-                    starting_bbs,
-                );
+        // Wait for a Connect message from the connection screen, listing
+        // rooms over a separate connection while the user browses.
+        let mut lobby: Option<ClientController> = None;
+        let (
+            name,
+            server_url,
+            room_id,
+            create,
+            spectate,
+            blind_config,
+            starting_bbs,
+            game_mode,
+            rebuy_config,
+            payouts,
+            multi_table,
+            table_size,
+            spectator_delay,
+            password,
+            unlisted,
+            invite,
+        ) = loop {
+            tokio::select! {
+                msg = rx.next() => match msg {
+                    Some(UiMessage::Connect {
+                        name,
+                        server_url,
+                        room_id,
+                        create,
+                        spectate,
+                        blind_config,
+                        starting_bbs,
+                        game_mode,
+                        rebuy_config,
+                        payouts,
+                        multi_table,
+                        table_size,
+                        spectator_delay,
+                        password,
+                        unlisted,
+                        invite,
+                    }) => {
+                        break (
+                            name,
+                            server_url,
+                            room_id,
+                            create,
+                            spectate,
+                            blind_config,
+                            starting_bbs,
+                            game_mode,
+                            rebuy_config,
+                            payouts,
+                            multi_table,
+                            table_size,
+                            spectator_delay,
+                            password,
+                            unlisted,
+                            invite,
+                        );
+                    }
+                    Some(UiMessage::BrowseRooms { server_url }) => {
+                        let ws_url = format!("{server_url}/ws");
+                        lobby = ClientController::connect_ws(&ws_url, "", ENCODING).await.ok();
+                        if let Some(ctrl) = &mut lobby {
+                            ctrl.send(ClientMessage::ListRooms);
+                        } else {
+                            game_state.write().rooms.clear();
+                        }
+                    }
+                    Some(UiMessage::StopBrowsing) => lobby = None,
+                    Some(_) => {}
+                    None => return,
+                },
+                poll = async { lobby.as_mut().unwrap().recv().await }, if lobby.is_some() => {
+                    if lobby.as_ref().is_some_and(|ctrl| ctrl.state.reload_required) {
+                        reload_client(&reload).await;
+                    }
+                    if let PollResult::Disconnected = poll {
+                        lobby = None;
+                        game_state.write().rooms.clear();
+                    } else if let Some(ctrl) = &lobby {
+                        game_state.write().rooms = ctrl.state.rooms.clone();
+                    }
+                }
+                _ = sleep_ms(ROOM_LIST_REFRESH_MS), if lobby.is_some() => {
+                    if let Some(ctrl) = &mut lobby {
+                        ctrl.send(ClientMessage::ListRooms);
+                    }
+                }
             }
         };
+        drop(lobby);
 
         // Build WS URL and attempt connection.
         conn_error.set(String::new());
         let ws_url = format!("{server_url}/ws");
-        let result = ClientController::connect_ws(&ws_url, &name).await;
+        let result = ClientController::connect_ws(&ws_url, &name, ENCODING).await;
 
         let mut ctrl = match result {
             Ok(c) => c,
@@ -254,27 +376,54 @@ pub async fn run_app_session<F, Fut>(
             }
         };
 
-        // Send CreateRoom (if requested) then JoinRoom.
+        // Send CreateRoom (if requested) then JoinRoom, or Spectate to watch.
         if create {
             ctrl.send(ClientMessage::CreateRoom {
                 room_id: room_id.clone(),
                 blind_config,
                 starting_bbs,
+                game_mode,
+                rebuy_config,
+                payouts,
+                multi_table,
+                table_size,
+                spectator_delay,
+                password: password.clone(),
+                unlisted,
+            });
+        }
+        if spectate {
+            ctrl.send(ClientMessage::Spectate {
+                room_id: room_id.clone(),
+                name: name.clone(),
+                password,
+                invite,
+            });
+        } else {
+            ctrl.send(ClientMessage::JoinRoom {
+                room_id: room_id.clone(),
+                name: name.clone(),
+                seat: None,
+                password,
+                invite,
             });
         }
-        ctrl.send(ClientMessage::JoinRoom {
-            room_id: room_id.clone(),
-            name: name.clone(),
-        });
 
         // Wait for room confirmation before switching to game screen.
         let joined = loop {
             match ctrl.recv().await {
                 PollResult::Updated(changed) => {
                     game_state.set(ctrl.state.clone());
-                    if (changed.phase || changed.players) && ctrl.state.our_player_id != 0 {
-                        // Persist session for reconnection.
-                        session.save(&ws_url, &room_id, &name, &ctrl.state.session_token);
+                    if ctrl.state.reload_required {
+                        reload_client(&reload).await;
+                    }
+                    if (changed.phase || changed.players)
+                        && (ctrl.state.our_player_id != 0 || ctrl.state.spectating)
+                    {
+                        // Persist session for reconnection (spectators have none).
+                        if !ctrl.state.spectating {
+                            session.save(&ws_url, &room_id, &name, &ctrl.state.session_token);
+                        }
                         screen.set(Screen::Game);
                         break true;
                     }
@@ -302,8 +451,16 @@ pub async fn run_app_session<F, Fut>(
             &name,
             &session,
             &sleep_ms,
+            &reload,
         )
         .await;
         session.clear();
     }
 }
+
+/// Reload the client to pick up the server's newer build.  The page is
+/// going away, so this never completes.
+async fn reload_client(reload: &dyn Fn()) {
+    reload();
+    std::future::pending::<()>().await;
+}
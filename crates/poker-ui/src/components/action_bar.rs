@@ -20,7 +20,11 @@ pub fn ActionBar(state: Signal<ClientGameState>) -> Element {
     if !gs.is_our_turn {
         return rsx! {
             div { class: "h-16 bg-surface border-t border-muted/50 flex items-center justify-center gap-4 text-foreground/50 text-sm",
-                if is_sitting_out {
+                if gs.paused {
+                    "Game paused"
+                } else if gs.spectating {
+                    "Watching — pick an empty seat to play"
+                } else if is_sitting_out {
                     "Sitting out…"
                 } else {
                     "Waiting for your turn…"
@@ -29,6 +33,15 @@ pub fn ActionBar(state: Signal<ClientGameState>) -> Element {
         };
     }
 
+    // Hold the buttons back until the server answers the last action.
+    if gs.action_pending() {
+        return rsx! {
+            div { class: "h-16 bg-surface border-t border-muted/50 flex items-center justify-center gap-4 text-foreground/50 text-sm",
+                "Sending…"
+            }
+        };
+    }
+
     let can_fold = gs.has_action(PlayerAction::Fold);
     let can_check = gs.has_action(PlayerAction::Check);
     let can_call = gs.has_action(PlayerAction::Call);
@@ -25,6 +25,20 @@ pub fn Card(card: CardInfo) -> Element {
     }
 }
 
+/// Render a card as compact inline text, e.g. next to a seat.
+#[component]
+pub fn MiniCard(card: CardInfo) -> Element {
+    let color_class = if is_red(card.suit) {
+        "suit-red"
+    } else {
+        "suit-black"
+    };
+
+    rsx! {
+        span { class: "font-semibold {color_class}", "{card.rank_str()}{card.suit_str()}" }
+    }
+}
+
 /// Render an empty card slot (placeholder).
 #[component]
 pub fn EmptyCard() -> Element {
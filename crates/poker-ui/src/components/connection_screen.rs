@@ -1,7 +1,12 @@
 //! Connection screen — name, server address, room ID, create/join buttons.
 
 use dioxus::prelude::*;
-use poker_core::protocol::{BlindConfig, validate_room_id};
+use poker_client::game_state::ClientGameState;
+use poker_core::protocol::{
+    BlindConfig, GameMode, RebuyConfig, SpectatorDelay, validate_game_mode, validate_multi_table,
+    validate_payouts, validate_rebuy_config, validate_room_id, validate_room_password,
+    validate_spectator_delay, validate_table_size,
+};
 
 use crate::UiMessage;
 
@@ -13,17 +18,22 @@ const MAX_NAME_LEN: usize = 16;
 enum ConnectingAction {
     Create,
     Join,
+    Watch,
 }
 
 /// Props for the connection screen.
 ///
 /// `default_server` pre-fills the server address field. For web builds this
 /// is typically derived from the page origin; for desktop it defaults to
-/// `localhost:8080`.
+/// `localhost:8080`.  `invite` is an invite token (e.g. from the page URL)
+/// to join or watch with; the room ID is then optional.  `state` carries
+/// the room list while the lobby browser is open.
 #[component]
 pub fn ConnectionScreen(
     error: Signal<String>,
+    state: Signal<ClientGameState>,
     #[props(default = "localhost:8080".to_string())] default_server: String,
+    #[props(default)] invite: String,
 ) -> Element {
     let mut name = use_signal(String::new);
     let mut server_url = use_signal(|| default_server.clone());
@@ -32,10 +42,27 @@ pub fn ConnectionScreen(
     let mut blind_interval_mins = use_signal(String::new);
     let mut blind_increase_pct = use_signal(String::new);
     let mut starting_bbs_input = use_signal(|| "100".to_string());
+    let mut cash_game = use_signal(|| false);
+    let mut min_buy_in_input = use_signal(|| "40".to_string());
+    let mut max_buy_in_input = use_signal(|| "100".to_string());
+    let mut rebuy_levels_input = use_signal(String::new);
+    let mut max_rebuys_input = use_signal(String::new);
+    let mut add_on = use_signal(|| false);
+    let mut payouts_input = use_signal(String::new);
+    let mut multi_table = use_signal(|| false);
+    let mut table_size_input = use_signal(|| "9".to_string());
+    let mut delay_secs_input = use_signal(String::new);
+    let mut delay_hands_input = use_signal(String::new);
+    let mut streamer_view = use_signal(|| false);
+    let mut password = use_signal(String::new);
+    let mut unlisted = use_signal(|| false);
     let mut show_server = use_signal(|| false);
+    let mut show_rooms = use_signal(|| false);
     let mut show_host_settings = use_signal(|| false);
     let mut connecting: Signal<Option<ConnectingAction>> = use_signal(|| None);
     let coroutine = use_coroutine_handle::<UiMessage>();
+    let has_invite = !invite.is_empty();
+    let invite = use_signal(|| invite);
 
     // Reset connecting state when a server error arrives.
     use_effect(move || {
@@ -44,7 +71,7 @@ pub fn ConnectionScreen(
         }
     });
 
-    let mut on_submit = move |create: bool| {
+    let mut on_submit = move |action: ConnectingAction| {
         if connecting.read().is_some() {
             return;
         }
@@ -52,6 +79,14 @@ pub fn ConnectionScreen(
         let n = name.read().trim().to_string();
         let s = server_url.read().trim().to_string();
         let r = room_id.read().clone();
+        let p = password.read().clone();
+        let create = action == ConnectingAction::Create;
+        // Invites are for joining or watching; the server finds the room.
+        let invite = if create {
+            String::new()
+        } else {
+            invite.read().clone()
+        };
 
         // Client-side validation
         if n.is_empty() {
@@ -68,7 +103,14 @@ pub fn ConnectionScreen(
             validation_error.set("Server address cannot be empty".to_string());
             return;
         }
-        if let Err(e) = validate_room_id(&r) {
+        // With an invite the room ID may be left empty.
+        if (!r.is_empty() || invite.is_empty())
+            && let Err(e) = validate_room_id(&r)
+        {
+            validation_error.set(e);
+            return;
+        }
+        if let Err(e) = validate_room_password(&p) {
             validation_error.set(e);
             return;
         }
@@ -125,19 +167,153 @@ pub fn ConnectionScreen(
             }
         };
 
-        validation_error.set(String::new());
-        connecting.set(Some(if create {
-            ConnectingAction::Create
+        let game_mode = if create && *cash_game.read() {
+            let parse_bbs = |raw: String| raw.trim().parse::<u32>().ok();
+            let (Some(min_buy_in_bbs), Some(max_buy_in_bbs)) = (
+                parse_bbs(min_buy_in_input.read().clone()),
+                parse_bbs(max_buy_in_input.read().clone()),
+            ) else {
+                validation_error.set("Buy-in limits must be valid numbers".to_string());
+                return;
+            };
+            let mode = GameMode::Cash {
+                min_buy_in_bbs,
+                max_buy_in_bbs,
+            };
+            if let Err(e) = validate_game_mode(&mode) {
+                validation_error.set(e);
+                return;
+            }
+            mode
+        } else {
+            GameMode::Tournament
+        };
+
+        let rebuy_config = if create && !game_mode.is_cash() {
+            let parse_count = |raw: String| {
+                let raw = raw.trim().to_string();
+                if raw.is_empty() {
+                    Some(0)
+                } else {
+                    raw.parse::<u32>().ok()
+                }
+            };
+            let (Some(levels), Some(max_rebuys)) = (
+                parse_count(rebuy_levels_input.read().clone()),
+                parse_count(max_rebuys_input.read().clone()),
+            ) else {
+                validation_error.set("Rebuy settings must be valid numbers".to_string());
+                return;
+            };
+            let config = RebuyConfig {
+                levels,
+                max_rebuys,
+                add_on: *add_on.read(),
+            };
+            if let Err(e) = validate_rebuy_config(&config, &game_mode) {
+                validation_error.set(e);
+                return;
+            }
+            config
+        } else {
+            RebuyConfig::default()
+        };
+
+        // Payout percentages, e.g. "50, 30, 20" (empty = winner takes all).
+        let payouts = if create && !game_mode.is_cash() {
+            let raw = payouts_input.read().trim().to_string();
+            if raw.is_empty() {
+                vec![100]
+            } else {
+                let Ok(parsed) = raw
+                    .split(',')
+                    .map(|p| p.trim().parse::<u32>())
+                    .collect::<Result<Vec<u32>, _>>()
+                else {
+                    validation_error.set("Payouts must be comma-separated percentages".to_string());
+                    return;
+                };
+                if let Err(e) = validate_payouts(&parsed) {
+                    validation_error.set(e);
+                    return;
+                }
+                parsed
+            }
+        } else {
+            vec![100]
+        };
+
+        // Seats at the table (per table in a multi-table tournament).
+        let multi_table = create && !game_mode.is_cash() && *multi_table.read();
+        let table_size = if create {
+            let Ok(size) = table_size_input.read().trim().parse::<u32>() else {
+                validation_error.set("Seats must be a number".to_string());
+                return;
+            };
+            let valid = if multi_table {
+                validate_multi_table(&r, size, &game_mode, &rebuy_config)
+            } else {
+                validate_table_size(size)
+            };
+            if let Err(e) = valid {
+                validation_error.set(e);
+                return;
+            }
+            size
+        } else {
+            9
+        };
+
+        // Spectator delay and streamer view.
+        let spectator_delay = if create {
+            let parse = |raw: String| {
+                let raw = raw.trim().to_string();
+                if raw.is_empty() {
+                    Some(0)
+                } else {
+                    raw.parse::<u32>().ok()
+                }
+            };
+            let (Some(secs), Some(hands)) = (
+                parse(delay_secs_input.read().clone()),
+                parse(delay_hands_input.read().clone()),
+            ) else {
+                validation_error.set("Spectator delay must be a valid number".to_string());
+                return;
+            };
+            let delay = SpectatorDelay {
+                secs,
+                hands,
+                hole_cards: *streamer_view.read(),
+            };
+            if let Err(e) = validate_spectator_delay(&delay) {
+                validation_error.set(e);
+                return;
+            }
+            delay
         } else {
-            ConnectingAction::Join
-        }));
+            SpectatorDelay::default()
+        };
+
+        validation_error.set(String::new());
+        connecting.set(Some(action));
         coroutine.send(UiMessage::Connect {
             name: n,
             server_url: s,
             room_id: r,
             create,
+            spectate: action == ConnectingAction::Watch,
             blind_config,
             starting_bbs,
+            game_mode,
+            rebuy_config,
+            payouts,
+            multi_table,
+            table_size,
+            spectator_delay,
+            password: p,
+            unlisted: create && *unlisted.read(),
+            invite,
         });
     };
 
@@ -145,6 +321,7 @@ pub fn ConnectionScreen(
     let val_err = validation_error.read().clone();
     let connecting_action = *connecting.read();
     let is_connecting = connecting_action.is_some();
+    let rooms = state.read().rooms.clone();
 
     rsx! {
         div {
@@ -152,7 +329,11 @@ pub fn ConnectionScreen(
             // Enter key → Create if host settings are open, otherwise Join
             onkeydown: move |e| {
                 if e.key() == Key::Enter {
-                    on_submit(*show_host_settings.read());
+                    on_submit(if *show_host_settings.read() {
+                        ConnectingAction::Create
+                    } else {
+                        ConnectingAction::Join
+                    });
                 }
             },
             div { class: "bg-surface w-full max-w-sm rounded-2xl shadow-2xl p-6 flex flex-col gap-4 sm:p-8 sm:gap-5 conn-card",
@@ -187,7 +368,24 @@ pub fn ConnectionScreen(
                             value: "{room_id}",
                             oninput: move |e| room_id.set(e.value()),
                         }
-                        p { class: "text-xs text-foreground/40", "Alphanumeric, up to 19 characters" }
+                        if has_invite {
+                            p { class: "text-xs text-accent", "You have an invite: no room ID or password needed to join" }
+                        } else {
+                            p { class: "text-xs text-foreground/40", "Alphanumeric, up to 19 characters" }
+                        }
+                    }
+
+                    // Password input
+                    div { class: "flex flex-col gap-1",
+                        label { class: "text-sm text-foreground/60", "Password" }
+                        input {
+                            class: "bg-muted rounded-lg px-4 py-2 text-foreground outline-none focus:ring-2 focus:ring-accent",
+                            r#type: "password",
+                            placeholder: "Optional",
+                            value: "{password}",
+                            oninput: move |e| password.set(e.value()),
+                        }
+                        p { class: "text-xs text-foreground/40", "For private rooms; creating with one makes the room private" }
                     }
 
                     // Server address (collapsed by default)
@@ -223,6 +421,68 @@ pub fn ConnectionScreen(
                         }
                     }
 
+                    // Lobby browser (collapsed by default); the room list is
+                    // refreshed while it is open.
+                    div { class: "flex flex-col gap-2",
+                        button {
+                            class: "text-sm text-foreground/60 flex items-center gap-1 hover:text-foreground/80 transition",
+                            r#type: "button",
+                            onclick: move |_| {
+                                show_rooms.toggle();
+                                if *show_rooms.read() {
+                                    coroutine.send(UiMessage::BrowseRooms {
+                                        server_url: server_url.read().trim().to_string(),
+                                    });
+                                } else {
+                                    coroutine.send(UiMessage::StopBrowsing);
+                                }
+                            },
+                            "Browse rooms"
+                            span {
+                                class: if *show_rooms.read() {
+                                    "text-xs transition-transform duration-150 rotate-180"
+                                } else {
+                                    "text-xs transition-transform duration-150"
+                                },
+                                "▾"
+                            }
+                        }
+                        div {
+                            class: if *show_rooms.read() {
+                                "collapsible collapsible-open"
+                            } else {
+                                "collapsible"
+                            },
+                            div { class: "room-list",
+                                if rooms.is_empty() {
+                                    p { class: "text-xs text-foreground/40", "No open rooms" }
+                                }
+                                for room in rooms {
+                                    button {
+                                        key: "{room.room_id}",
+                                        class: if room.is_joinable() { "room-row" } else { "room-row opacity-50" },
+                                        r#type: "button",
+                                        onclick: {
+                                            let id = room.room_id.clone();
+                                            move |_| room_id.set(id.clone())
+                                        },
+                                        div { class: "flex justify-between gap-2",
+                                            span { class: "font-semibold truncate",
+                                                if room.password { "🔒 " }
+                                                "{room.room_id}"
+                                            }
+                                            span { class: "text-xs text-foreground/60", "{room.status()}" }
+                                        }
+                                        div { class: "flex justify-between gap-2 text-xs text-foreground/60",
+                                            span { "{room.variant()} · {room.small_blind}/{room.big_blind}" }
+                                            span { "{room.players}/{room.table_size} seated" }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+
                     // Host settings (collapsed by default)
                     div { class: "flex flex-col gap-2",
                         button {
@@ -259,6 +519,20 @@ pub fn ConnectionScreen(
                                     p { class: "text-xs text-foreground/40", "BBs per player" }
                                 }
 
+                                // Seats at the table
+                                div { class: "flex-1 flex flex-col gap-1",
+                                    label { class: "text-sm text-foreground/60", "Seats" }
+                                    input {
+                                        class: "bg-muted rounded-lg px-4 py-2 text-foreground outline-none focus:ring-2 focus:ring-accent w-full",
+                                        r#type: "number",
+                                        min: "2",
+                                        max: "10",
+                                        value: "{table_size_input}",
+                                        oninput: move |e| table_size_input.set(e.value()),
+                                    }
+                                    p { class: "text-xs text-foreground/40", "Per table (2-10)" }
+                                }
+
                                 // Blind interval
                                 div { class: "flex-1 flex flex-col gap-1",
                                     label { class: "text-sm text-foreground/60", "Blind interval" }
@@ -286,6 +560,122 @@ pub fn ConnectionScreen(
                                     }
                                     p { class: "text-xs text-foreground/40", "Percent" }
                                 }
+
+                                // Spectator delay + streamer view
+                                div { class: "flex-1 flex flex-col gap-1",
+                                    label { class: "text-sm text-foreground/60", "Spectator delay" }
+                                    div { class: "flex gap-2",
+                                        input {
+                                            class: "bg-muted rounded-lg px-3 py-2 text-foreground outline-none focus:ring-2 focus:ring-accent w-full",
+                                            r#type: "number",
+                                            min: "0",
+                                            placeholder: "0",
+                                            value: "{delay_secs_input}",
+                                            oninput: move |e| delay_secs_input.set(e.value()),
+                                        }
+                                        input {
+                                            class: "bg-muted rounded-lg px-3 py-2 text-foreground outline-none focus:ring-2 focus:ring-accent w-full",
+                                            r#type: "number",
+                                            min: "0",
+                                            placeholder: "0",
+                                            value: "{delay_hands_input}",
+                                            oninput: move |e| delay_hands_input.set(e.value()),
+                                        }
+                                    }
+                                    p { class: "text-xs text-foreground/40", "Seconds / hands (0 = live)" }
+                                    label { class: "flex items-center gap-2 text-sm text-foreground/60",
+                                        input {
+                                            r#type: "checkbox",
+                                            checked: *streamer_view.read(),
+                                            onchange: move |e| streamer_view.set(e.checked()),
+                                        }
+                                        "Show hole cards (needs a delay)"
+                                    }
+                                    label { class: "flex items-center gap-2 text-sm text-foreground/60",
+                                        input {
+                                            r#type: "checkbox",
+                                            checked: *unlisted.read(),
+                                            onchange: move |e| unlisted.set(e.checked()),
+                                        }
+                                        "Unlisted (hidden from the room list)"
+                                    }
+                                }
+
+                                // Cash game toggle + buy-in limits
+                                div { class: "flex-1 flex flex-col gap-1",
+                                    label { class: "flex items-center gap-2 text-sm text-foreground/60",
+                                        input {
+                                            r#type: "checkbox",
+                                            checked: *cash_game.read(),
+                                            onchange: move |e| cash_game.set(e.checked()),
+                                        }
+                                        "Cash game"
+                                    }
+                                    if *cash_game.read() {
+                                        div { class: "flex gap-2",
+                                            input {
+                                                class: "bg-muted rounded-lg px-3 py-2 text-foreground outline-none focus:ring-2 focus:ring-accent w-full",
+                                                r#type: "number",
+                                                min: "1",
+                                                value: "{min_buy_in_input}",
+                                                oninput: move |e| min_buy_in_input.set(e.value()),
+                                            }
+                                            input {
+                                                class: "bg-muted rounded-lg px-3 py-2 text-foreground outline-none focus:ring-2 focus:ring-accent w-full",
+                                                r#type: "number",
+                                                min: "1",
+                                                value: "{max_buy_in_input}",
+                                                oninput: move |e| max_buy_in_input.set(e.value()),
+                                            }
+                                        }
+                                        p { class: "text-xs text-foreground/40", "Min / max buy-in (BBs); blinds stay fixed" }
+                                    } else {
+                                        // Tournament rebuy period + add-on
+                                        div { class: "flex gap-2",
+                                            input {
+                                                class: "bg-muted rounded-lg px-3 py-2 text-foreground outline-none focus:ring-2 focus:ring-accent w-full",
+                                                r#type: "number",
+                                                min: "0",
+                                                placeholder: "0",
+                                                value: "{rebuy_levels_input}",
+                                                oninput: move |e| rebuy_levels_input.set(e.value()),
+                                            }
+                                            input {
+                                                class: "bg-muted rounded-lg px-3 py-2 text-foreground outline-none focus:ring-2 focus:ring-accent w-full",
+                                                r#type: "number",
+                                                min: "0",
+                                                placeholder: "0",
+                                                value: "{max_rebuys_input}",
+                                                oninput: move |e| max_rebuys_input.set(e.value()),
+                                            }
+                                        }
+                                        p { class: "text-xs text-foreground/40", "Rebuy levels / max rebuys (0 = unlimited)" }
+                                        label { class: "flex items-center gap-2 text-sm text-foreground/60",
+                                            input {
+                                                r#type: "checkbox",
+                                                checked: *add_on.read(),
+                                                onchange: move |e| add_on.set(e.checked()),
+                                            }
+                                            "Add-on at the break"
+                                        }
+                                        input {
+                                            class: "bg-muted rounded-lg px-3 py-2 text-foreground outline-none focus:ring-2 focus:ring-accent w-full",
+                                            r#type: "text",
+                                            placeholder: "100",
+                                            value: "{payouts_input}",
+                                            oninput: move |e| payouts_input.set(e.value()),
+                                        }
+                                        p { class: "text-xs text-foreground/40", "Payouts per place (%), e.g. 50, 30, 20" }
+                                        label { class: "flex items-center gap-2 text-sm text-foreground/60",
+                                            input {
+                                                r#type: "checkbox",
+                                                checked: *multi_table.read(),
+                                                onchange: move |e| multi_table.set(e.checked()),
+                                            }
+                                            "Multi-table tournament"
+                                        }
+                                    }
+                                }
                             }
                         }
                     }
@@ -310,7 +700,7 @@ pub fn ConnectionScreen(
                             "flex-1 bg-primary hover:bg-primary-light text-foreground font-semibold rounded-lg py-2 transition"
                         },
                         disabled: is_connecting,
-                        onclick: move |_| on_submit(true),
+                        onclick: move |_| on_submit(ConnectingAction::Create),
                         if connecting_action == Some(ConnectingAction::Create) { "Connecting…" } else { "Create Room" }
                     }
                     button {
@@ -320,9 +710,20 @@ pub fn ConnectionScreen(
                             "flex-1 bg-muted hover:bg-muted-light text-foreground font-semibold rounded-lg py-2 transition"
                         },
                         disabled: is_connecting,
-                        onclick: move |_| on_submit(false),
+                        onclick: move |_| on_submit(ConnectingAction::Join),
                         if connecting_action == Some(ConnectingAction::Join) { "Connecting…" } else { "Join Room" }
                     }
+                    button {
+                        class: if is_connecting {
+                            "flex-1 bg-muted/50 text-foreground/50 font-semibold rounded-lg py-2 cursor-not-allowed"
+                        } else {
+                            "flex-1 bg-muted hover:bg-muted-light text-foreground font-semibold rounded-lg py-2 transition"
+                        },
+                        disabled: is_connecting,
+                        title: "Watch the room without taking a seat",
+                        onclick: move |_| on_submit(ConnectingAction::Watch),
+                        if connecting_action == Some(ConnectingAction::Watch) { "Connecting…" } else { "Watch" }
+                    }
                 }
             }
         }
@@ -1,7 +1,7 @@
 //! Event log — scrollable list of game events.
 
 use dioxus::prelude::*;
-use poker_client::game_state::{ClientGameState, GameEvent, LogCategory};
+use poker_client::game_state::{ClientGameState, GameEvent, LogCategory, invite_terms, ordinal};
 
 #[component]
 pub fn EventLog(state: Signal<ClientGameState>) -> Element {
@@ -31,6 +31,19 @@ fn render_event(event: &GameEvent) -> Element {
             format!("{name} joined the game"),
             category_color(LogCategory::Info),
         ),
+        GameEvent::SeatChanged {
+            name,
+            seat,
+            pending,
+            ..
+        } => (
+            if *pending {
+                format!("{name} moves to seat {} next hand", seat + 1)
+            } else {
+                format!("{name} moved to seat {}", seat + 1)
+            },
+            category_color(LogCategory::Info),
+        ),
         GameEvent::PlayerLeft { name, .. } => {
             (format!("{name} left"), category_color(LogCategory::Info))
         }
@@ -42,6 +55,10 @@ fn render_event(event: &GameEvent) -> Element {
             format!("{player_name}: {message}"),
             category_color(LogCategory::Chat),
         ),
+        GameEvent::SpectatorChat { name, message } => (
+            format!("{name} (watching): {message}"),
+            category_color(LogCategory::Chat),
+        ),
         GameEvent::GameStarted => (
             "Game started!".to_string(),
             category_color(LogCategory::System),
@@ -112,12 +129,20 @@ fn render_event(event: &GameEvent) -> Element {
             format!("{name} wins {amount} ({hand})"),
             category_color(LogCategory::Winner),
         ),
-        GameEvent::PlayerEliminated { name, .. } => (
-            format!("{name} eliminated"),
-            category_color(LogCategory::Info),
-        ),
-        GameEvent::GameOver { winner_name, .. } => (
-            format!("🏆 {winner_name} wins the game!"),
+        GameEvent::PlayerEliminated { name, place, .. } => {
+            let text = if *place > 0 {
+                format!("{name} eliminated in {} place", ordinal(*place))
+            } else {
+                format!("{name} eliminated")
+            };
+            (text, category_color(LogCategory::Info))
+        }
+        GameEvent::GameOver {
+            winner_name,
+            prize_pool,
+            ..
+        } => (
+            format!("🏆 {winner_name} wins the game! Prize pool: {prize_pool}"),
             category_color(LogCategory::Winner),
         ),
         GameEvent::Pong => ("Pong".to_string(), category_color(LogCategory::Info)),
@@ -125,6 +150,16 @@ fn render_event(event: &GameEvent) -> Element {
             format!("Error: {message}"),
             category_color(LogCategory::Error),
         ),
+        GameEvent::ServerShutdown {
+            reason,
+            reconnect_after,
+        } => (
+            format!("{reason}. Reconnecting in {reconnect_after}s…"),
+            category_color(LogCategory::Error),
+        ),
+        GameEvent::Announcement { message } => {
+            (format!("📢 {message}"), category_color(LogCategory::System))
+        }
         GameEvent::Disconnected => (
             "Disconnected from server".to_string(),
             category_color(LogCategory::Error),
@@ -155,6 +190,119 @@ fn render_event(event: &GameEvent) -> Element {
             "Waiting for more players to sit in...".to_string(),
             category_color(LogCategory::System),
         ),
+        GameEvent::GamePaused => (
+            "Game paused by the host".to_string(),
+            category_color(LogCategory::System),
+        ),
+        GameEvent::GameResumed => (
+            "Game resumed".to_string(),
+            category_color(LogCategory::System),
+        ),
+        GameEvent::PlayerBoughtIn {
+            name,
+            amount,
+            pending,
+            ..
+        } => {
+            let when = if *pending { " (next hand)" } else { "" };
+            (
+                format!("{name} bought in for {amount}{when}"),
+                category_color(LogCategory::Info),
+            )
+        }
+        GameEvent::PlayerCashedOut { name, amount, .. } => (
+            format!("{name} left the table with {amount}"),
+            category_color(LogCategory::Info),
+        ),
+        GameEvent::PlayerRebought {
+            name,
+            amount,
+            pending,
+            ..
+        } => {
+            let when = if *pending { " (next hand)" } else { "" };
+            (
+                format!("{name} rebought for {amount}{when}"),
+                category_color(LogCategory::Info),
+            )
+        }
+        GameEvent::PlayerAddedOn {
+            name,
+            amount,
+            pending,
+            ..
+        } => {
+            let when = if *pending { " (next hand)" } else { "" };
+            (
+                format!("{name} took the add-on for {amount}{when}"),
+                category_color(LogCategory::Info),
+            )
+        }
+        GameEvent::RebuyPeriodEnded { add_on_open } => {
+            let text = if *add_on_open {
+                "Rebuy period over — add-on break"
+            } else {
+                "Rebuy period over"
+            };
+            (text.to_string(), category_color(LogCategory::System))
+        }
+        GameEvent::AddOnClosed => (
+            "Add-on break over".to_string(),
+            category_color(LogCategory::System),
+        ),
+        GameEvent::TableMoved { room_id } => (
+            format!("Moved to table {room_id}"),
+            category_color(LogCategory::System),
+        ),
+        GameEvent::InviteCreated {
+            token,
+            single_use,
+            expires_in_secs,
+        } => (
+            format!(
+                "Invite created ({}): {token}",
+                invite_terms(*single_use, *expires_in_secs)
+            ),
+            category_color(LogCategory::System),
+        ),
+        GameEvent::PlayerKicked { name, banned, .. } => (
+            if *banned {
+                format!("{name} was banned by the host")
+            } else {
+                format!("{name} was kicked by the host")
+            },
+            category_color(LogCategory::Info),
+        ),
+        GameEvent::HostChanged { name, .. } => (
+            format!("{name} is now the host"),
+            category_color(LogCategory::Info),
+        ),
+        GameEvent::GameEnded => (
+            "The host ended the game".to_string(),
+            category_color(LogCategory::System),
+        ),
+        GameEvent::BlindsChanged {
+            small_blind,
+            big_blind,
+            pending,
+        } => (
+            if *pending {
+                format!("Blinds change to {small_blind}/{big_blind} next hand")
+            } else {
+                format!("Blinds changed to {small_blind}/{big_blind}")
+            },
+            category_color(LogCategory::System),
+        ),
+        GameEvent::FinalStandings { standings } => {
+            let lines: Vec<String> = standings
+                .iter()
+                .map(|s| format!("  {} {} — {}", ordinal(s.place), s.name, s.payout))
+                .collect();
+            (
+                format!("Final standings:\n{}", lines.join("\n")),
+                category_color(LogCategory::Winner),
+            )
+        }
     };
 
     rsx! {
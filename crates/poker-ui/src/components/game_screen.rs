@@ -7,7 +7,10 @@
 use dioxus::prelude::*;
 use poker_client::game_state::ClientGameState;
 
-use super::{action_bar, event_log, game_log_overlay, game_table, player_list};
+use super::{
+    action_bar, event_log, game_log_overlay, game_table, pause_overlay, player_list,
+    results_overlay,
+};
 
 #[component]
 pub fn GameScreen(state: Signal<ClientGameState>) -> Element {
@@ -33,6 +36,10 @@ pub fn GameScreen(state: Signal<ClientGameState>) -> Element {
                 div { class: "lg:hidden",
                     game_log_overlay::GameLogOverlay { state }
                 }
+                // Paused overlay (host pause)
+                pause_overlay::PauseOverlay { state }
+                // Tournament results
+                results_overlay::ResultsOverlay { state }
             }
         }
     }
@@ -1,10 +1,19 @@
-//! Game table — community cards, hole cards, pot.
+//! Game table — seats, community cards, hole cards, pot.
 
 use dioxus::prelude::*;
 use poker_client::game_state::ClientGameState;
+use poker_core::protocol::ClientMessage;
 
 use super::card;
-use crate::{StackDisplayMode, format_stack};
+use crate::{StackDisplayMode, UiMessage, format_stack};
+
+/// Position of the `index`-th seat (counting clockwise from ours, which is
+/// drawn at the bottom) as `(left, top)` percentages of the table area.
+fn seat_position(index: usize, count: usize) -> (f64, f64) {
+    let angle =
+        std::f64::consts::FRAC_PI_2 + index as f64 * std::f64::consts::TAU / count.max(1) as f64;
+    (50.0 + 44.0 * angle.cos(), 50.0 + 40.0 * angle.sin())
+}
 
 #[component]
 pub fn GameTable(state: Signal<ClientGameState>) -> Element {
@@ -16,6 +25,10 @@ pub fn GameTable(state: Signal<ClientGameState>) -> Element {
     let community = &gs.community_cards;
     let hole = gs.hole_cards;
     let pot_text = format_stack(gs.pot, bb, mode);
+    let coroutine = use_coroutine_handle::<UiMessage>();
+    let seats = gs.seats_from_ours();
+    let seated = gs.our_seat().is_some();
+    let spectating = gs.spectating;
 
     rsx! {
         div { class: "flex flex-col items-center h-full gap-2 p-2 lg:justify-center lg:gap-6 lg:p-4",
@@ -28,6 +41,88 @@ pub fn GameTable(state: Signal<ClientGameState>) -> Element {
                 div { "Hand #{gs.hand_number}  ·  {gs.stage}" }
             }
 
+            div { class: "table-ring",
+                div { class: "table-felt" }
+
+                // Seats around the table, ours at the bottom
+                for (index, &seat) in seats.iter().enumerate() {
+                    {
+                        let (left, top) = seat_position(index, seats.len());
+                        let style = format!("left: {left:.1}%; top: {top:.1}%");
+                        match gs.player_at_seat(seat) {
+                            Some(player) => {
+                                let is_us = player.id == gs.our_player_id;
+                                let is_turn = gs.turn_timer_player == Some(player.id);
+                                let faded = if gs.is_player_folded(player.id) || gs.is_player_sitting_out(player.id) {
+                                    "opacity-50"
+                                } else {
+                                    ""
+                                };
+                                let ring = if is_turn { "ring-2 ring-accent" } else { "" };
+                                let name_class = if is_us { "text-accent font-semibold" } else { "text-foreground" };
+                                let bet = gs.player_bets.get(&player.id).copied().unwrap_or(0).min(player.chips);
+                                let stack_text = format_stack(player.chips.saturating_sub(bet), bb, mode);
+                                let badge = if player.id == gs.dealer_id {
+                                    Some("D")
+                                } else if player.id == gs.small_blind_id {
+                                    Some("SB")
+                                } else if player.id == gs.big_blind_id {
+                                    Some("BB")
+                                } else {
+                                    None
+                                };
+                                rsx! {
+                                    div {
+                                        key: "s{seat}",
+                                        class: "seat flex flex-col items-center bg-elevated rounded-lg px-2 py-1 text-xs shadow {ring} {faded}",
+                                        style: "{style}",
+                                        title: "Seat {seat + 1}",
+                                        div { class: "flex items-center gap-1",
+                                            if let Some(b) = badge {
+                                                span { class: "text-accent font-bold", "{b}" }
+                                            }
+                                            span { class: "truncate max-w-[5rem] {name_class}", "{player.name}" }
+                                        }
+                                        span { class: "text-foreground/60", "{stack_text}" }
+                                        // Streamer view: spectators see every hand
+                                        if let Some(cards) = gs.spectator_hole_cards.get(&player.id) {
+                                            div { class: "seat-cards",
+                                                card::MiniCard { card: cards[0] }
+                                                card::MiniCard { card: cards[1] }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                            None => rsx! {
+                                button {
+                                    key: "s{seat}",
+                                    class: "seat rounded-lg border border-dashed border-muted/50 px-2 py-1 text-xs text-foreground/40 hover:text-foreground hover:border-accent transition",
+                                    style: "{style}",
+                                    title: if seated {
+                                        "Move here (between hands)"
+                                    } else if spectating {
+                                        "Take this seat"
+                                    } else {
+                                        "Empty seat"
+                                    },
+                                    disabled: !seated && !spectating,
+                                    onclick: move |_| {
+                                        let msg = if spectating {
+                                            ClientMessage::TakeSeat { seat: Some(seat) }
+                                        } else {
+                                            ClientMessage::ChangeSeat { seat }
+                                        };
+                                        coroutine.send(UiMessage::Action(msg));
+                                    },
+                                    "Seat {seat + 1}"
+                                }
+                            },
+                        }
+                    }
+                }
+
+                div { class: "absolute inset-0 flex flex-col items-center justify-center gap-2 lg:gap-6",
             // Community cards
             div { class: "flex gap-2 lg:gap-3",
                 for i in 0..5 {
@@ -102,6 +197,8 @@ pub fn GameTable(state: Signal<ClientGameState>) -> Element {
                     }
                 }
             }
+                }
+            }
         }
     }
 }
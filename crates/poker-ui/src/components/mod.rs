@@ -5,4 +5,7 @@ pub mod event_log;
 pub mod game_log_overlay;
 pub mod game_screen;
 pub mod game_table;
+pub mod pause_overlay;
 pub mod player_list;
+pub mod results_overlay;
+pub mod session_ledger;
@@ -0,0 +1,50 @@
+//! Paused overlay — shown over the table while the host has paused the game.
+
+use dioxus::prelude::*;
+use poker_client::game_state::ClientGameState;
+use poker_core::protocol::ClientMessage;
+
+use crate::UiMessage;
+
+/// Dims the table and action bar while the game is paused (including the
+/// tournament add-on break). The host also gets a Resume button here.
+#[component]
+pub fn PauseOverlay(state: Signal<ClientGameState>) -> Element {
+    let gs = state.read();
+    let coroutine = use_coroutine_handle::<UiMessage>();
+
+    if !gs.paused {
+        return rsx! {};
+    }
+
+    rsx! {
+        div { class: "absolute inset-0 z-30 bg-base/80 flex flex-col items-center justify-center gap-4 backdrop-blur-sm",
+            if gs.add_on_open {
+                p { class: "text-3xl font-bold text-accent tracking-wide uppercase", "Add-on Break" }
+                p { class: "text-sm text-foreground/60", "Prize pool: {gs.prize_pool}" }
+                if gs.can_add_on() {
+                    button {
+                        class: "px-6 py-2 bg-accent/20 hover:bg-accent/30 rounded-lg font-semibold text-accent transition",
+                        onclick: move |_| {
+                            coroutine.send(UiMessage::Action(ClientMessage::AddOn));
+                        },
+                        "Take Add-on"
+                    }
+                }
+            } else {
+                p { class: "text-3xl font-bold text-accent tracking-wide uppercase", "Paused" }
+            }
+            if gs.is_host {
+                button {
+                    class: "px-6 py-2 bg-primary hover:bg-primary-light rounded-lg font-semibold text-foreground transition",
+                    onclick: move |_| {
+                        coroutine.send(UiMessage::Action(ClientMessage::ResumeGame));
+                    },
+                    "Resume"
+                }
+            } else {
+                p { class: "text-sm text-foreground/60", "Waiting for the host to resume…" }
+            }
+        }
+    }
+}
@@ -4,7 +4,8 @@ use dioxus::prelude::*;
 use poker_client::game_state::ClientGameState;
 use poker_core::protocol::ClientMessage;
 
-use crate::{StackDisplayMode, UiMessage, format_stack};
+use super::session_ledger;
+use crate::{InviteLinkBase, StackDisplayMode, UiMessage, format_stack};
 
 #[component]
 pub fn PlayerList(state: Signal<ClientGameState>) -> Element {
@@ -13,6 +14,15 @@ pub fn PlayerList(state: Signal<ClientGameState>) -> Element {
     let mut display_mode: Signal<StackDisplayMode> = use_context();
     let mode = *display_mode.read();
     let bb = gs.big_blind;
+    let mut sb_input = use_signal(String::new);
+    let mut bb_input = use_signal(String::new);
+    let link_base = try_use_context::<InviteLinkBase>();
+    let invite_link = gs.invite.as_ref().map(|token| match &link_base {
+        Some(InviteLinkBase(base)) => format!("{base}{token}"),
+        None => token.clone(),
+    });
+    // Multi-table tournaments are run by the tournament, not the table host.
+    let moderates = gs.is_host && !gs.spectating && gs.tournament.is_none();
 
     rsx! {
         div { class: "flex flex-col h-full",
@@ -48,6 +58,7 @@ pub fn PlayerList(state: Signal<ClientGameState>) -> Element {
                         let effective_chips = player.chips.saturating_sub(bet);
                         let stack_text = format_stack(effective_chips, bb, mode);
                         let bet_text = if bet > 0 { Some(format_stack(bet, bb, mode)) } else { None };
+                        let player_id = player.id;
 
                         rsx! {
                             div {
@@ -68,6 +79,33 @@ pub fn PlayerList(state: Signal<ClientGameState>) -> Element {
                                     if is_folded {
                                         span { class: "text-foreground/40 text-xs italic", "(folded)" }
                                     }
+                                    // Host moderation
+                                    if moderates && !is_us {
+                                        button {
+                                            class: "text-xs text-foreground/40 hover:text-accent",
+                                            title: "Make host",
+                                            onclick: move |_| {
+                                                coroutine.send(UiMessage::Action(ClientMessage::TransferHost { player_id }));
+                                            },
+                                            "👑"
+                                        }
+                                        button {
+                                            class: "text-xs text-foreground/40 hover:text-accent",
+                                            title: "Kick",
+                                            onclick: move |_| {
+                                                coroutine.send(UiMessage::Action(ClientMessage::KickPlayer { player_id, ban: false }));
+                                            },
+                                            "✕"
+                                        }
+                                        button {
+                                            class: "text-xs text-foreground/40 hover:text-primary",
+                                            title: "Kick and ban",
+                                            onclick: move |_| {
+                                                coroutine.send(UiMessage::Action(ClientMessage::KickPlayer { player_id, ban: true }));
+                                            },
+                                            "⛔"
+                                        }
+                                    }
                                 }
                                 div {
                                     class: "flex items-center gap-1.5 cursor-pointer select-none",
@@ -87,10 +125,28 @@ pub fn PlayerList(state: Signal<ClientGameState>) -> Element {
                 }
             }
 
-            // Bottom controls: Start / Sit Out / Late Entry / Exit
+            // Multi-table tournament progress
+            if let Some(progress) = gs.tournament {
+                div { class: "px-3 py-2 border-t border-muted/50 flex justify-between text-xs text-foreground/50 uppercase tracking-wide",
+                    span { "Table {gs.room_id}" }
+                    span { "{progress.players_remaining}/{progress.entrants} left · {progress.tables} tables" }
+                }
+            }
+
+            // Cash game results / tournament rebuys and prize pool
+            session_ledger::SessionLedger { state }
+
+            // Spectator count
+            if gs.spectators > 0 {
+                div { class: "px-3 py-1 text-xs text-foreground/50",
+                    if gs.spectators == 1 { "1 spectator" } else { "{gs.spectators} spectators" }
+                }
+            }
+
+            // Bottom controls: Start / Pause / Blinds / End / Sit Out / Late Entry / Rebuy / Cash / Exit
             div { class: "p-3 border-t border-muted/50 flex flex-col gap-2",
                 // Start game button (lobby only)
-                if !gs.game_started {
+                if !gs.game_started && !gs.spectating {
                     button {
                         class: "w-full bg-primary hover:bg-primary-light text-foreground font-semibold rounded-lg py-2 transition",
                         onclick: move |_| {
@@ -100,6 +156,92 @@ pub fn PlayerList(state: Signal<ClientGameState>) -> Element {
                     }
                 }
 
+                // Pause / Resume (host only, visible once game has started)
+                if gs.game_started && gs.is_host {
+                    {
+                        let paused = gs.paused;
+                        rsx! {
+                            button {
+                                class: "w-full bg-elevated hover:bg-base rounded-lg py-1.5 text-sm font-semibold text-foreground transition",
+                                onclick: move |_| {
+                                    let msg = if paused { ClientMessage::ResumeGame } else { ClientMessage::PauseGame };
+                                    coroutine.send(UiMessage::Action(msg));
+                                },
+                                if paused { "Resume Game" } else { "Pause Game" }
+                            }
+                        }
+                    }
+                }
+
+                // End the rebuy period early (host only)
+                if gs.rebuy_open && gs.is_host {
+                    button {
+                        class: "w-full bg-elevated hover:bg-base rounded-lg py-1.5 text-sm font-semibold text-foreground transition",
+                        onclick: move |_| {
+                            coroutine.send(UiMessage::Action(ClientMessage::EndRebuyPeriod));
+                        },
+                        if gs.rebuy_config.add_on { "End Rebuys & Start Break" } else { "End Rebuy Period" }
+                    }
+                }
+
+                // Blinds (host only)
+                if moderates {
+                    {
+                        let blind_config = gs.blind_config;
+                        // An empty box keeps that blind as it is.
+                        let current_sb = (bb / 2).max(1);
+                        let current_bb = bb.max(1);
+                        rsx! {
+                            div { class: "flex items-center gap-1.5",
+                                span { class: "text-xs text-foreground/60", "Blinds" }
+                                input {
+                                    class: "bg-muted rounded-lg px-2 py-1 text-sm text-foreground outline-none focus:ring-2 focus:ring-accent w-full min-w-0",
+                                    r#type: "number",
+                                    min: "1",
+                                    placeholder: "{current_sb}",
+                                    value: "{sb_input}",
+                                    oninput: move |e| sb_input.set(e.value()),
+                                }
+                                span { class: "text-foreground/40", "/" }
+                                input {
+                                    class: "bg-muted rounded-lg px-2 py-1 text-sm text-foreground outline-none focus:ring-2 focus:ring-accent w-full min-w-0",
+                                    r#type: "number",
+                                    min: "1",
+                                    placeholder: "{current_bb}",
+                                    value: "{bb_input}",
+                                    oninput: move |e| bb_input.set(e.value()),
+                                }
+                                button {
+                                    class: "bg-elevated hover:bg-base rounded-lg px-2 py-1 text-sm font-semibold text-foreground transition",
+                                    onclick: move |_| {
+                                        let small_blind = sb_input.read().trim().parse().unwrap_or(current_sb);
+                                        let big_blind = bb_input.read().trim().parse().unwrap_or(current_bb);
+                                        coroutine.send(UiMessage::Action(ClientMessage::ChangeBlinds {
+                                            small_blind,
+                                            big_blind,
+                                            blind_config,
+                                        }));
+                                        sb_input.set(String::new());
+                                        bb_input.set(String::new());
+                                    },
+                                    "Set"
+                                }
+                            }
+                        }
+                    }
+                }
+
+                // End the game early (host only)
+                if gs.game_started && moderates {
+                    button {
+                        class: "w-full bg-elevated hover:bg-base rounded-lg py-1.5 text-sm font-semibold text-foreground transition",
+                        onclick: move |_| {
+                            coroutine.send(UiMessage::Action(ClientMessage::EndGame));
+                        },
+                        "End Game"
+                    }
+                }
+
                 // Late entry toggle (host only, visible once game has started)
                 if gs.game_started && gs.is_host {
                     {
@@ -121,8 +263,50 @@ pub fn PlayerList(state: Signal<ClientGameState>) -> Element {
                     }
                 }
 
+                // Invite (host only): single use, valid for a day
+                if gs.is_host && !gs.spectating {
+                    button {
+                        class: "w-full bg-elevated hover:bg-base rounded-lg py-1.5 text-sm font-semibold text-foreground transition",
+                        onclick: move |_| {
+                            coroutine.send(UiMessage::Action(ClientMessage::CreateInvite {
+                                single_use: true,
+                                expires_in_secs: 24 * 60 * 60,
+                            }));
+                        },
+                        "Create Invite Link"
+                    }
+                    if let Some(link) = &invite_link {
+                        input {
+                            class: "bg-muted rounded-lg px-2 py-1 text-xs text-foreground outline-none w-full",
+                            r#type: "text",
+                            readonly: true,
+                            value: "{link}",
+                        }
+                    }
+                }
+
+                // Spectator chat toggle (host only)
+                if gs.is_host {
+                    {
+                        let (label, btn_class) = if gs.spectator_chat_allowed {
+                            ("Spectator Chat: ON", "w-full bg-accent/20 hover:bg-accent/30 rounded-lg py-1.5 text-sm font-semibold text-accent transition")
+                        } else {
+                            ("Spectator Chat: OFF", "w-full bg-elevated hover:bg-base rounded-lg py-1.5 text-sm font-semibold text-foreground/60 transition")
+                        };
+                        rsx! {
+                            button {
+                                class: "{btn_class}",
+                                onclick: move |_| {
+                                    coroutine.send(UiMessage::Action(ClientMessage::ToggleSpectatorChat));
+                                },
+                                "{label}"
+                            }
+                        }
+                    }
+                }
+
                 // Sit Out / Sit In toggle (visible once game has started)
-                if gs.game_started {
+                if gs.game_started && !gs.spectating {
                     {
                         let is_sitting_out = gs.is_sitting_out();
                         let (label, btn_class) = if is_sitting_out {
@@ -146,6 +330,52 @@ pub fn PlayerList(state: Signal<ClientGameState>) -> Element {
                     }
                 }
 
+                // Tournament rebuy / add-on
+                if gs.can_rebuy() {
+                    button {
+                        class: "w-full bg-accent/20 hover:bg-accent/30 rounded-lg py-1.5 text-sm font-semibold text-accent transition",
+                        onclick: move |_| {
+                            coroutine.send(UiMessage::Action(ClientMessage::Rebuy));
+                        },
+                        "Rebuy"
+                    }
+                }
+                if gs.can_add_on() {
+                    button {
+                        class: "w-full bg-accent/20 hover:bg-accent/30 rounded-lg py-1.5 text-sm font-semibold text-accent transition",
+                        onclick: move |_| {
+                            coroutine.send(UiMessage::Action(ClientMessage::AddOn));
+                        },
+                        "Take Add-on"
+                    }
+                }
+
+                // Cash game: top up / rebuy and leave with the stack
+                if gs.is_cash_game() && gs.players.iter().any(|p| p.id == gs.our_player_id) {
+                    {
+                        let top_up = gs.top_up();
+                        let label = if gs.our_chips == 0 { "Rebuy" } else { "Top Up" };
+                        rsx! {
+                            if let Some(msg) = top_up {
+                                button {
+                                    class: "w-full bg-accent/20 hover:bg-accent/30 rounded-lg py-1.5 text-sm font-semibold text-accent transition",
+                                    onclick: move |_| {
+                                        coroutine.send(UiMessage::Action(msg.clone()));
+                                    },
+                                    "{label}"
+                                }
+                            }
+                            button {
+                                class: "w-full bg-elevated hover:bg-base rounded-lg py-1.5 text-sm font-semibold text-foreground transition",
+                                onclick: move |_| {
+                                    coroutine.send(UiMessage::Action(ClientMessage::LeaveTable));
+                                },
+                                "Leave Table"
+                            }
+                        }
+                    }
+                }
+
                 // Exit game button (always visible)
                 button {
                     class: "w-full bg-muted hover:bg-muted-light rounded-lg py-1.5 text-sm font-semibold text-foreground transition",
@@ -0,0 +1,64 @@
+//! Results overlay — final tournament standings and payouts.
+
+use dioxus::prelude::*;
+use poker_client::game_state::{ClientGameState, ordinal};
+
+use crate::UiMessage;
+
+/// Shown over the table once a tournament is over. Closing it leaves the
+/// player at the finished table.
+#[component]
+pub fn ResultsOverlay(state: Signal<ClientGameState>) -> Element {
+    let gs = state.read();
+    let coroutine = use_coroutine_handle::<UiMessage>();
+    // Hand number at which the player closed the results, so that the next
+    // tournament's results show up again.
+    let mut dismissed_at: Signal<Option<u32>> = use_signal(|| None);
+
+    if gs.standings.is_empty() || gs.game_started || *dismissed_at.read() == Some(gs.hand_number) {
+        return rsx! {};
+    }
+
+    let hand_number = gs.hand_number;
+    let headline = match gs.our_standing() {
+        Some(s) if s.place == 1 => "You won the tournament!".to_string(),
+        Some(s) => format!("You finished {}", ordinal(s.place)),
+        None => "Tournament over".to_string(),
+    };
+
+    rsx! {
+        div { class: "absolute inset-0 z-40 bg-base/90 flex flex-col items-center justify-center gap-4 backdrop-blur-sm",
+            p { class: "text-3xl font-bold text-accent tracking-wide", "{headline}" }
+            p { class: "text-sm text-foreground/60", "Prize pool: {gs.prize_pool}" }
+            div { class: "bg-surface rounded-lg p-4 w-72 flex flex-col gap-1",
+                for standing in gs.standings.iter() {
+                    {
+                        let is_us = standing.player_id == gs.our_player_id;
+                        let row_class = if is_us { "flex justify-between text-sm font-semibold text-foreground" } else { "flex justify-between text-sm text-foreground/80" };
+                        let payout_class = if standing.payout > 0 { "text-accent" } else { "text-foreground/40" };
+                        rsx! {
+                            div { key: "s{standing.player_id}", class: "{row_class}",
+                                span { "{ordinal(standing.place)}  {standing.name}" }
+                                span { class: "{payout_class}", "{standing.payout}" }
+                            }
+                        }
+                    }
+                }
+            }
+            div { class: "flex gap-3",
+                button {
+                    class: "px-6 py-2 bg-elevated hover:bg-base rounded-lg font-semibold text-foreground transition",
+                    onclick: move |_| dismissed_at.set(Some(hand_number)),
+                    "Close"
+                }
+                button {
+                    class: "px-6 py-2 bg-primary hover:bg-primary-light rounded-lg font-semibold text-foreground transition",
+                    onclick: move |_| {
+                        coroutine.send(UiMessage::ExitGame);
+                    },
+                    "Exit Game"
+                }
+            }
+        }
+    }
+}
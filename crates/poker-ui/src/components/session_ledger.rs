@@ -0,0 +1,62 @@
+//! Session ledger — net result per player in cash games, rebuys, add-ons
+//! and the prize pool in tournaments.
+
+use dioxus::prelude::*;
+use poker_client::game_state::ClientGameState;
+
+use crate::{StackDisplayMode, format_stack};
+
+#[component]
+pub fn SessionLedger(state: Signal<ClientGameState>) -> Element {
+    let gs = state.read();
+    let display_mode: Signal<StackDisplayMode> = use_context();
+    let mode = *display_mode.read();
+    let bb = gs.big_blind;
+
+    if gs.ledger.is_empty() {
+        return rsx! {};
+    }
+
+    if !gs.is_cash_game() {
+        if !gs.rebuy_config.is_enabled() {
+            return rsx! {};
+        }
+        return rsx! {
+            div { class: "px-3 py-2 border-t border-muted/50 flex flex-col gap-1",
+                div { class: "flex justify-between text-xs text-foreground/50 uppercase tracking-wide",
+                    span { "Rebuys" }
+                    span { "Pool {gs.prize_pool}" }
+                }
+                for entry in gs.ledger.iter().filter(|e| e.rebuys > 0 || e.add_on) {
+                    div { key: "r{entry.player_id}", class: "flex justify-between text-sm",
+                        span { class: "text-foreground/80", "{entry.name}" }
+                        span { class: "text-foreground/60",
+                            "{entry.rebuys}R"
+                            if entry.add_on { " +A" }
+                        }
+                    }
+                }
+            }
+        };
+    }
+
+    rsx! {
+        div { class: "px-3 py-2 border-t border-muted/50 flex flex-col gap-1",
+            p { class: "text-xs text-foreground/50 uppercase tracking-wide", "Session" }
+            for entry in gs.ledger.iter() {
+                {
+                    let net = entry.net();
+                    let net_text = format_stack(net.unsigned_abs() as u32, bb, mode);
+                    let (sign, color) = if net >= 0 { ("+", "text-accent") } else { ("-", "text-primary") };
+                    let name_class = if entry.seated { "text-foreground/80" } else { "text-foreground/40 italic" };
+                    rsx! {
+                        div { key: "l{entry.player_id}", class: "flex justify-between text-sm",
+                            span { class: "{name_class}", "{entry.name}" }
+                            span { class: "{color}", "{sign}{net_text}" }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
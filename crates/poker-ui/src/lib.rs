@@ -7,7 +7,7 @@
 pub mod app_logic;
 pub mod components;
 
-use poker_core::protocol::{BlindConfig, ClientMessage};
+use poker_core::protocol::{BlindConfig, ClientMessage, GameMode, RebuyConfig, SpectatorDelay};
 
 // ---------------------------------------------------------------------------
 // Shared types
@@ -39,6 +39,12 @@ impl StackDisplayMode {
     }
 }
 
+/// Start of an invite link; the invite token is appended.  Frontends that
+/// can link to themselves provide it as context, otherwise the host is
+/// shown the bare token.
+#[derive(Clone, Debug, PartialEq)]
+pub struct InviteLinkBase(pub String);
+
 /// Format a chip amount according to the chosen display mode.
 ///
 /// When `big_blind` is 0 (game hasn't started yet) we always fall back to
@@ -64,10 +70,36 @@ pub enum UiMessage {
         server_url: String,
         room_id: String,
         create: bool,
+        /// Watch the room as a spectator instead of joining it.
+        spectate: bool,
         blind_config: BlindConfig,
         /// Number of big blinds each player starts with (default: 50).
         starting_bbs: u32,
+        /// Tournament or cash game (only used when creating).
+        game_mode: GameMode,
+        /// Tournament rebuy / add-on rules (only used when creating).
+        rebuy_config: RebuyConfig,
+        /// Tournament payout percentages per place (only used when creating).
+        payouts: Vec<u32>,
+        /// Spread a tournament over several tables (only used when creating).
+        multi_table: bool,
+        /// Seats at the table, or per table in a multi-table tournament
+        /// (only used when creating).
+        table_size: u32,
+        /// How far spectators lag behind the table (only used when creating).
+        spectator_delay: SpectatorDelay,
+        /// Room password: set when creating, given when joining.
+        password: String,
+        /// Leave the room out of the public room list (only used when creating).
+        unlisted: bool,
+        /// Invite token to join with; the room ID may then be empty.
+        invite: String,
     },
+    /// Start listing the rooms on a server, refreshed until
+    /// [`UiMessage::StopBrowsing`] or [`UiMessage::Connect`].
+    BrowseRooms { server_url: String },
+    /// Stop listing rooms.
+    StopBrowsing,
     /// A game action to forward to the server.
     Action(ClientMessage),
     /// Deliberately exit the current game / room.
@@ -4,7 +4,9 @@
 //! [`poker_ui::app_logic`]; this module only provides:
 //!
 //! - Session persistence via `sessionStorage`
+//! - Reloading the page when the server has a newer build
 //! - The browser-origin WebSocket URL helper
+//! - Invite links (`/poker/?invite=<token>`)
 //! - PWA manifest / service-worker / theme-color tags
 //! - The root `<App>` Dioxus component that wires everything together
 
@@ -13,7 +15,7 @@ use poker_client::game_state::ClientGameState;
 use poker_client::session::SessionStore;
 use poker_ui::app_logic;
 use poker_ui::components::{connection_screen, game_screen};
-use poker_ui::{Screen, StackDisplayMode, UiMessage};
+use poker_ui::{InviteLinkBase, Screen, StackDisplayMode, UiMessage};
 
 // ---------------------------------------------------------------------------
 // Root component
@@ -83,11 +85,40 @@ fn default_ws_origin() -> String {
     format!("{ws_scheme}://{host}")
 }
 
+/// The invite token in the page URL (`?invite=<token>`), if any.
+fn invite_from_url() -> String {
+    let search = web_sys::window()
+        .and_then(|w| w.location().search().ok())
+        .unwrap_or_default();
+    search
+        .trim_start_matches('?')
+        .split('&')
+        .find_map(|pair| pair.strip_prefix("invite="))
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// Invite links point back at this page: `<origin>/poker/?invite=`.
+fn invite_link_base() -> String {
+    let origin = web_sys::window()
+        .and_then(|w| w.location().origin().ok())
+        .unwrap_or_default();
+    format!("{origin}/poker/?invite=")
+}
+
 /// Platform sleep using gloo-timers (Web / wasm).
 async fn sleep_ms(ms: u64) {
     gloo_timers::future::TimeoutFuture::new(ms as u32).await;
 }
 
+/// Reload the page to load the build the server is running.  The service
+/// worker fetches from the network first, so this gets the new bundle.
+fn reload_page() {
+    if let Some(window) = web_sys::window() {
+        let _ = window.location().reload();
+    }
+}
+
 // ---------------------------------------------------------------------------
 // App component
 // ---------------------------------------------------------------------------
@@ -99,9 +130,11 @@ pub fn App() -> Element {
     let game_state = use_signal(|| ClientGameState::new(""));
     let conn_error = use_signal(String::new);
     let ws_origin = use_signal(default_ws_origin);
+    let invite = use_signal(invite_from_url);
 
     // Shared display mode for stacks (blinds vs chips). Default: blinds.
     use_context_provider(|| Signal::new(StackDisplayMode::Blinds));
+    use_context_provider(|| InviteLinkBase(invite_link_base()));
 
     // Spawn the networking coroutine — all logic lives in poker_ui::app_logic.
     let _coroutine = use_coroutine(move |rx: UnboundedReceiver<UiMessage>| {
@@ -112,10 +145,12 @@ pub fn App() -> Element {
             conn_error,
             WebSessionStore,
             sleep_ms,
+            reload_page,
         )
     });
 
     let origin = ws_origin.read().clone();
+    let invite = invite.read().clone();
 
     rsx! {
         document::Stylesheet { href: TAILWIND_CSS }
@@ -132,7 +167,7 @@ pub fn App() -> Element {
         div { class: "min-h-screen bg-base text-foreground font-sans",
             match &*screen.read() {
                 Screen::Connection => rsx! {
-                    connection_screen::ConnectionScreen { error: conn_error, default_server: origin }
+                    connection_screen::ConnectionScreen { error: conn_error, state: game_state, default_server: origin, invite }
                 },
                 Screen::Game => rsx! {
                     game_screen::GameScreen { state: game_state }
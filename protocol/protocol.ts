@@ -0,0 +1,331 @@
+// Generated from poker-core by `protocol-schema`. Do not edit.
+
+/**
+ * Configuration for automatic blind increases.
+ *
+ * When `interval_secs` is 0 (or `None` on the wire) blinds never increase.
+ */
+export type BlindConfig = { 
+/**
+ * Seconds between each blind increase (0 = disabled).
+ */
+interval_secs: number, 
+/**
+ * Percentage by which blinds increase each interval (e.g. 50 = +50%).
+ */
+increase_percent: number, };
+
+/**
+ * Serializable card representation
+ */
+export type CardInfo = { rank: number, suit: number, };
+
+/**
+ * What kind of client is connecting.
+ */
+export type ClientKind = "web" | "native" | "other";
+
+/**
+ * Messages sent from client to server
+ */
+export type ClientMessage = { "type": "Hello", protocol_version: number, client_kind: ClientKind, 
+/**
+ * Optional features the client understands (see [`FEATURES`]).
+ */
+capabilities: Array<string>, 
+/**
+ * The client's [`BUILD_ID`].
+ */
+build: string, 
+/**
+ * The encoding the client would like to use after the handshake.
+ */
+encoding: Encoding, } | { "type": "Join", name: string, } | { "type": "CreateRoom", room_id: string, blind_config: BlindConfig, 
+/**
+ * Number of big blinds each player starts with (default: 50).
+ * In cash games this is the default buy-in, clamped to the range.
+ */
+starting_bbs: number, 
+/**
+ * Tournament (default) or cash game.
+ */
+game_mode: GameMode, 
+/**
+ * Tournament rebuy / add-on rules (default: freezeout).
+ */
+rebuy_config: RebuyConfig, 
+/**
+ * Percentage of the prize pool paid to each place, best first
+ * (default: winner takes all).
+ */
+payouts: Array<number>, 
+/**
+ * Tournaments: spread the entrants over several tables that are
+ * balanced and merged as players bust.
+ */
+multi_table: boolean, 
+/**
+ * Seats at the table (default: 9).  In a multi-table tournament,
+ * the seats at each table.
+ */
+table_size: number, 
+/**
+ * Delay before spectators see events (default: live, no hole
+ * cards).
+ */
+spectator_delay: SpectatorDelay, 
+/**
+ * Password needed to join or watch the room (default: none).
+ */
+password: string, 
+/**
+ * Leave the room out of the public room list.
+ */
+unlisted: boolean, } | { "type": "JoinRoom", room_id: string, name: string, seat: number | null, password: string, invite: string, } | { "type": "ChangeSeat", seat: number, } | { "type": "Spectate", room_id: string, name: string, password: string, invite: string, } | { "type": "TakeSeat", seat: number | null, } | { "type": "ToggleSpectatorChat" } | { "type": "GetPlayers" } | { "type": "Chat", message: string, } | { "type": "StartGame" } | { "type": "Fold", request_id?: number, } | { "type": "Check", request_id?: number, } | { "type": "Call", request_id?: number, } | { "type": "Raise", amount: number, request_id?: number, } | { "type": "AllIn", request_id?: number, } | { "type": "SitOut" } | { "type": "SitIn" } | { "type": "ToggleLateEntry" } | { "type": "PauseGame" } | { "type": "ResumeGame" } | { "type": "BuyIn", amount: number, } | { "type": "LeaveTable" } | { "type": "GetLedger" } | { "type": "Rebuy" } | { "type": "AddOn" } | { "type": "EndRebuyPeriod" } | { "type": "KickPlayer", player_id: number, ban: boolean, } | { "type": "TransferHost", player_id: number, } | { "type": "EndGame" } | { "type": "ChangeBlinds", small_blind: number, big_blind: number, blind_config: BlindConfig, } | { "type": "CreateInvite", single_use: boolean, expires_in_secs: number, } | { "type": "ListRooms" } | { "type": "Rejoin", room_id: string, session_token: string, last_seq: number, } | { "type": "Ping" };
+
+/**
+ * How messages are encoded on the wire.  JSON travels in text frames and
+ * the more compact binary encodings in binary frames.
+ */
+export type Encoding = "msgpack" | "cbor" | "json";
+
+/**
+ * A server message as sent on the wire.  Room events sent to a player carry
+ * the room's sequence number, counting up from 1; replies such as errors
+ * and pongs carry none.  A client that rejoins sends back the last number
+ * it saw.
+ */
+export type Envelope = { seq?: number, } & ({ "type": "ServerHello", protocol_version: number, build: string, features: Array<string>, 
+/**
+ * The encoding both sides use for every message after this one.
+ */
+encoding: Encoding, } | { "type": "UpgradeRequired", message: string, reload: boolean, } | { "type": "Resumed", room_id: string, player_id: number, replayed: number, } | { "type": "Welcome", message: string, } | { "type": "JoinedGame", player_id: number, chips: number, player_count: number, 
+/**
+ * Session token for reconnection after a disconnect.
+ */
+session_token: string, 
+/**
+ * Whether this player is the room host.
+ */
+is_host: boolean, 
+/**
+ * Whether late entry is currently allowed.
+ */
+allow_late_entry: boolean, } | { "type": "PlayerJoined", player_id: number, name: string, seat: number, } | { "type": "SeatChanged", player_id: number, seat: number, pending: boolean, } | { "type": "PlayerLeft", player_id: number, } | { "type": "PlayerList", players: Array<PlayerInfo>, } | { "type": "ChatMessage", player_id: number, message: string, } | { "type": "SpectatorChat", name: string, message: string, } | { "type": "Spectating", room_id: string, delay: SpectatorDelay, } | { "type": "SpectatorHoleCards", hands: Array<[number, [CardInfo, CardInfo]]>, } | { "type": "Spectators", count: number, chat_allowed: boolean, } | { "type": "GameStarted" } | { "type": "NewHand", hand_number: number, dealer_id: number, small_blind_id: number, big_blind_id: number, small_blind: number, big_blind: number, } | { "type": "HoleCards", cards: [CardInfo, CardInfo], } | { "type": "CommunityCards", stage: string, cards: Array<CardInfo>, } | { "type": "YourTurn", current_bet: number, your_bet: number, pot: number, min_raise: number, valid_actions: Array<PlayerAction>, } | { "type": "PlayerActed", player_id: number, action: PlayerAction, amount: number | null, } | { "type": "PotUpdate", pot: number, } | { "type": "ChipUpdate", player_id: number, chips: number, } | { "type": "Showdown", hands: Array<[number, [CardInfo, CardInfo], string]>, } | { "type": "AllInShowdown", hands: Array<[number, [CardInfo, CardInfo], number]>, community_cards: Array<CardInfo>, } | { "type": "RoundWinner", winners: Array<[number, number, string]>, } | { "type": "PlayerEliminated", player_id: number, place: number, } | { "type": "GameOver", winner_id: number, winner_name: string, } | { "type": "FinalStandings", standings: Array<Standing>, prize_pool: number, } | { "type": "TableMoved", room_id: string, } | { "type": "TournamentUpdate", entrants: number, players_remaining: number, tables: number, } | { "type": "BlindsIncreased", small_blind: number, big_blind: number, } | { "type": "TurnTimerStarted", player_id: number, timeout_secs: number, } | { "type": "PlayerSatOut", player_id: number, } | { "type": "PlayerSatIn", player_id: number, } | { "type": "LateEntryChanged", allowed: boolean, } | { "type": "PlayerKicked", player_id: number, banned: boolean, } | { "type": "HostChanged", player_id: number, } | { "type": "GameEnded" } | { "type": "BlindsChanged", small_blind: number, big_blind: number, blind_config: BlindConfig, pending: boolean, } | { "type": "WaitingForPlayers" } | { "type": "GamePaused" } | { "type": "GameResumed" } | { "type": "PlayerBoughtIn", player_id: number, amount: number, pending: boolean, } | { "type": "PlayerCashedOut", player_id: number, amount: number, } | { "type": "SessionLedger", entries: Array<LedgerEntry>, } | { "type": "PlayerRebought", player_id: number, amount: number, pending: boolean, } | { "type": "PlayerAddedOn", player_id: number, amount: number, pending: boolean, } | { "type": "RebuyPeriodEnded", add_on_open: boolean, } | { "type": "AddOnClosed" } | { "type": "PrizePool", total: number, } | { "type": "RoomCreated", room_id: string, } | { "type": "RoomList", rooms: Array<RoomSummary>, } | { "type": "InviteCreated", room_id: string, token: string, single_use: boolean, expires_in_secs: number, } | { "type": "RoomJoined", room_id: string, blind_config: BlindConfig, game_mode: GameMode, rebuy_config: RebuyConfig, table_size: number, } | { "type": "Rejoined", room_id: string, player_id: number, session_token: string, chips: number, game_started: boolean, hand_number: number, pot: number, stage: string, community_cards: Array<CardInfo>, hole_cards: [CardInfo, CardInfo] | null, players: Array<PlayerInfo>, sitting_out: Array<number>, folded: Array<number>, blind_config: BlindConfig, allow_late_entry: boolean, is_host: boolean, paused: boolean, game_mode: GameMode, rebuy_config: RebuyConfig, 
+/**
+ * Whether rebuys are currently allowed.
+ */
+rebuy_open: boolean, 
+/**
+ * Whether the add-on break is in progress.
+ */
+add_on_open: boolean, prize_pool: number, table_size: number, dealer_id: number, small_blind_id: number, big_blind_id: number, small_blind: number, big_blind: number, 
+/**
+ * Bets of the players who have put chips in this hand.
+ */
+bets: Array<PlayerBet>, 
+/**
+ * Bet to call in the current betting round.
+ */
+current_bet: number, min_raise: number, 
+/**
+ * Player whose turn it is, 0 when nobody is to act.
+ */
+to_act: number, 
+/**
+ * Seconds left on their turn timer, 0 when none is running.
+ */
+turn_secs_left: number, } | { "type": "ServerShutdown", reason: string, reconnect_after: number, } | { "type": "Announcement", message: string, } | { "type": "RoomError", message: string, error: ProtocolError, } | { "type": "Ok" } | { "type": "Pong" } | { "type": "ActionAck", request_id: number, } | { "type": "ActionRejected", request_id: number, message: string, error: ProtocolError, } | { "type": "Error", message: string, error: ProtocolError, });
+
+/**
+ * How a room is played.
+ */
+export type GameMode = { "kind": "tournament" } | { "kind": "cash", min_buy_in_bbs: number, max_buy_in_bbs: number, };
+
+/**
+ * One player's line in the session ledger: cash game buy-ins and cash-outs,
+ * or tournament entries, rebuys and add-ons.
+ */
+export type LedgerEntry = { player_id: number, name: string, 
+/**
+ * Total chips bought (initial buy-in, top-ups, rebuys and add-ons).
+ */
+bought_in: number, 
+/**
+ * Total chips taken off the table when leaving.
+ */
+cashed_out: number, 
+/**
+ * Chips currently on the table (0 once the player has left).
+ */
+stack: number, 
+/**
+ * Whether the player is still seated.
+ */
+seated: boolean, 
+/**
+ * Tournaments: number of rebuys taken.
+ */
+rebuys: number, 
+/**
+ * Tournaments: whether the add-on was taken.
+ */
+add_on: boolean, };
+
+/**
+ * An action the player can take during a betting round.
+ */
+export type PlayerAction = "fold" | "check" | "call" | "raise" | "allin";
+
+/**
+ * What one player has bet in the hand being played.
+ */
+export type PlayerBet = { player_id: number, 
+/**
+ * Bet in the current betting round.
+ */
+bet: number, 
+/**
+ * Chips put in since the hand was dealt, blinds included.
+ */
+committed: number, };
+
+/**
+ * Serializable player info for the wire protocol.
+ */
+export type PlayerInfo = { id: number, name: string, chips: number, 
+/**
+ * Seat index at the table, starting at 0.
+ */
+seat: number, };
+
+/**
+ * Why the server refused a request.  The `code` on the wire is stable, so
+ * clients can act on it; `Display` gives the message shown to players.
+ * Codes this client does not know read as `Unknown`.
+ */
+export type ProtocolError = { "code": "invalid_message", message: string, } | { "code": "out_of_date" } | { "code": "shutting_down" } | { "code": "not_in_room" } | { "code": "already_in_room" } | { "code": "room_not_found", room_id: string, } | { "code": "room_exists", room_id: string, } | { "code": "too_many_rooms", max: number, } | { "code": "password_required" } | { "code": "wrong_password" } | { "code": "invalid_invite" } | { "code": "banned" } | { "code": "session_expired" } | { "code": "game_in_progress" } | { "code": "room_full" } | { "code": "no_such_seat", seat: number, } | { "code": "seat_taken", seat: number, } | { "code": "too_many_spectators", max: number, } | { "code": "player_not_found" } | { "code": "not_host" } | { "code": "game_not_started" } | { "code": "game_already_started" } | { "code": "game_paused" } | { "code": "not_your_turn" } | { "code": "invalid_action", valid_actions: Array<PlayerAction>, } | { "code": "invalid_amount", min: number, max: number, } | { "code": "duplicate_request" } | { "code": "invalid_request", message: string, } | { "code": "not_allowed", message: string, } | { "code": "unknown" };
+
+/**
+ * Tournament rebuy and add-on rules.
+ *
+ * Rebuys (one starting stack each) are allowed while a player's stack is at
+ * or below the starting stack, during the first `levels` blind levels.  When
+ * the rebuy period ends the game breaks and every player still in may take
+ * a single add-on of one starting stack until the host resumes.  Without
+ * blind increases the rebuy period lasts until the host ends it.
+ */
+export type RebuyConfig = { 
+/**
+ * Number of blind levels during which rebuys are allowed (0 = disabled).
+ */
+levels: number, 
+/**
+ * Maximum rebuys per player (0 = unlimited).
+ */
+max_rebuys: number, 
+/**
+ * Whether an add-on is offered at the break after the rebuy period.
+ */
+add_on: boolean, };
+
+/**
+ * A room as shown in the lobby (`GET /api/rooms`, `RoomList`).
+ */
+export type RoomSummary = { room_id: string, 
+/**
+ * Seated players.
+ */
+players: number, 
+/**
+ * Seats at the table (per table in a multi-table tournament).
+ */
+table_size: number, spectators: number, 
+/**
+ * Current blinds.
+ */
+small_blind: number, big_blind: number, 
+/**
+ * Blind increase schedule.
+ */
+blind_config: BlindConfig, game_mode: GameMode, 
+/**
+ * A table (or the registration room) of a multi-table tournament.
+ */
+multi_table: boolean, 
+/**
+ * Whether the game has started.
+ */
+started: boolean, allow_late_entry: boolean, 
+/**
+ * Whether joining needs a password (or an invite).
+ */
+password: boolean, };
+
+/**
+ * Messages sent from server to client
+ */
+export type ServerMessage = { "type": "ServerHello", protocol_version: number, build: string, features: Array<string>, 
+/**
+ * The encoding both sides use for every message after this one.
+ */
+encoding: Encoding, } | { "type": "UpgradeRequired", message: string, reload: boolean, } | { "type": "Resumed", room_id: string, player_id: number, replayed: number, } | { "type": "Welcome", message: string, } | { "type": "JoinedGame", player_id: number, chips: number, player_count: number, 
+/**
+ * Session token for reconnection after a disconnect.
+ */
+session_token: string, 
+/**
+ * Whether this player is the room host.
+ */
+is_host: boolean, 
+/**
+ * Whether late entry is currently allowed.
+ */
+allow_late_entry: boolean, } | { "type": "PlayerJoined", player_id: number, name: string, seat: number, } | { "type": "SeatChanged", player_id: number, seat: number, pending: boolean, } | { "type": "PlayerLeft", player_id: number, } | { "type": "PlayerList", players: Array<PlayerInfo>, } | { "type": "ChatMessage", player_id: number, message: string, } | { "type": "SpectatorChat", name: string, message: string, } | { "type": "Spectating", room_id: string, delay: SpectatorDelay, } | { "type": "SpectatorHoleCards", hands: Array<[number, [CardInfo, CardInfo]]>, } | { "type": "Spectators", count: number, chat_allowed: boolean, } | { "type": "GameStarted" } | { "type": "NewHand", hand_number: number, dealer_id: number, small_blind_id: number, big_blind_id: number, small_blind: number, big_blind: number, } | { "type": "HoleCards", cards: [CardInfo, CardInfo], } | { "type": "CommunityCards", stage: string, cards: Array<CardInfo>, } | { "type": "YourTurn", current_bet: number, your_bet: number, pot: number, min_raise: number, valid_actions: Array<PlayerAction>, } | { "type": "PlayerActed", player_id: number, action: PlayerAction, amount: number | null, } | { "type": "PotUpdate", pot: number, } | { "type": "ChipUpdate", player_id: number, chips: number, } | { "type": "Showdown", hands: Array<[number, [CardInfo, CardInfo], string]>, } | { "type": "AllInShowdown", hands: Array<[number, [CardInfo, CardInfo], number]>, community_cards: Array<CardInfo>, } | { "type": "RoundWinner", winners: Array<[number, number, string]>, } | { "type": "PlayerEliminated", player_id: number, place: number, } | { "type": "GameOver", winner_id: number, winner_name: string, } | { "type": "FinalStandings", standings: Array<Standing>, prize_pool: number, } | { "type": "TableMoved", room_id: string, } | { "type": "TournamentUpdate", entrants: number, players_remaining: number, tables: number, } | { "type": "BlindsIncreased", small_blind: number, big_blind: number, } | { "type": "TurnTimerStarted", player_id: number, timeout_secs: number, } | { "type": "PlayerSatOut", player_id: number, } | { "type": "PlayerSatIn", player_id: number, } | { "type": "LateEntryChanged", allowed: boolean, } | { "type": "PlayerKicked", player_id: number, banned: boolean, } | { "type": "HostChanged", player_id: number, } | { "type": "GameEnded" } | { "type": "BlindsChanged", small_blind: number, big_blind: number, blind_config: BlindConfig, pending: boolean, } | { "type": "WaitingForPlayers" } | { "type": "GamePaused" } | { "type": "GameResumed" } | { "type": "PlayerBoughtIn", player_id: number, amount: number, pending: boolean, } | { "type": "PlayerCashedOut", player_id: number, amount: number, } | { "type": "SessionLedger", entries: Array<LedgerEntry>, } | { "type": "PlayerRebought", player_id: number, amount: number, pending: boolean, } | { "type": "PlayerAddedOn", player_id: number, amount: number, pending: boolean, } | { "type": "RebuyPeriodEnded", add_on_open: boolean, } | { "type": "AddOnClosed" } | { "type": "PrizePool", total: number, } | { "type": "RoomCreated", room_id: string, } | { "type": "RoomList", rooms: Array<RoomSummary>, } | { "type": "InviteCreated", room_id: string, token: string, single_use: boolean, expires_in_secs: number, } | { "type": "RoomJoined", room_id: string, blind_config: BlindConfig, game_mode: GameMode, rebuy_config: RebuyConfig, table_size: number, } | { "type": "Rejoined", room_id: string, player_id: number, session_token: string, chips: number, game_started: boolean, hand_number: number, pot: number, stage: string, community_cards: Array<CardInfo>, hole_cards: [CardInfo, CardInfo] | null, players: Array<PlayerInfo>, sitting_out: Array<number>, folded: Array<number>, blind_config: BlindConfig, allow_late_entry: boolean, is_host: boolean, paused: boolean, game_mode: GameMode, rebuy_config: RebuyConfig, 
+/**
+ * Whether rebuys are currently allowed.
+ */
+rebuy_open: boolean, 
+/**
+ * Whether the add-on break is in progress.
+ */
+add_on_open: boolean, prize_pool: number, table_size: number, dealer_id: number, small_blind_id: number, big_blind_id: number, small_blind: number, big_blind: number, 
+/**
+ * Bets of the players who have put chips in this hand.
+ */
+bets: Array<PlayerBet>, 
+/**
+ * Bet to call in the current betting round.
+ */
+current_bet: number, min_raise: number, 
+/**
+ * Player whose turn it is, 0 when nobody is to act.
+ */
+to_act: number, 
+/**
+ * Seconds left on their turn timer, 0 when none is running.
+ */
+turn_secs_left: number, } | { "type": "ServerShutdown", reason: string, reconnect_after: number, } | { "type": "Announcement", message: string, } | { "type": "RoomError", message: string, error: ProtocolError, } | { "type": "Ok" } | { "type": "Pong" } | { "type": "ActionAck", request_id: number, } | { "type": "ActionRejected", request_id: number, message: string, error: ProtocolError, } | { "type": "Error", message: string, error: ProtocolError, };
+
+/**
+ * How far spectators lag behind the table.
+ *
+ * Every event is held back until `secs` seconds have passed and `hands`
+ * more hands have started.  With `hole_cards` spectators also see every
+ * player's hole cards once the delay has passed (a streamer view).
+ */
+export type SpectatorDelay = { secs: number, hands: number, hole_cards: boolean, };
+
+/**
+ * One line of the final tournament standings.
+ */
+export type Standing = { 
+/**
+ * Finishing place, starting at 1.  Players who busted in the same hand
+ * with the same starting stack share a place.
+ */
+place: number, player_id: number, name: string, 
+/**
+ * Chips won from the prize pool.
+ */
+payout: number, };